@@ -0,0 +1,202 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::ClawgicError;
+
+/// Shared flag for cancelling an expensive operation from somewhere else (i.e. another thread).
+///
+/// Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken{
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken{
+    /// Constructs a new (not cancelled) `CancelToken`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Cancels every operation using this token (or a clone of it).
+    pub fn cancel(&self){
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool{
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// How far along an expensive operation is. Passed to the callback given to `Budget::with_progress()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress{
+    processed: u64,
+    models: u64,
+}
+
+impl Progress{
+    /// Number of truth assignments (rows) checked so far.
+    pub fn processed(&self) -> u64{
+        self.processed
+    }
+
+    /// Number of models (satisfying assignments) found so far.
+    ///
+    /// Stays at 0 for operations that aren't looking for models (i.e. `log_eq_within()`).
+    pub fn models(&self) -> u64{
+        self.models
+    }
+}
+
+/// Callback for reporting progress.
+#[derive(Clone)]
+struct Reporter{
+    /// How many steps to take between reports.
+    every: u64,
+    callback: Arc<dyn Fn(Progress) + Send + Sync>,
+}
+
+impl std::fmt::Debug for Reporter{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reporter").field("every", &self.every).finish_non_exhaustive()
+    }
+}
+
+/// Limits on how long an expensive operation (the `_within` functions) can run before giving up with `ClawgicError::Interrupted`.
+///
+/// A step is one row of a truth table (or, for a `KnowledgeBase`, one operation on its decision diagram). The default `Budget` has no limits.
+///
+/// Can also report progress as it goes (see `with_progress()`).
+/// ```
+/// use clawgic::prelude::*;
+/// use std::time::Duration;
+/// let mut tree = ExpressionTree::new("A0").unwrap();
+/// for i in 1..40{
+///     tree &= ExpressionTree::new(&format!("A{i}")).unwrap();
+/// }
+/// //the only model is the very last row of the truth table
+/// let budget = Budget::new().with_max_steps(1_000_000).with_timeout(Duration::from_secs(1));
+/// assert_eq!(tree.is_satisfiable_within(&budget), Err(ClawgicError::Interrupted));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Budget{
+    /// When to give up.
+    deadline: Option<Instant>,
+    /// How many steps to take before giving up.
+    max_steps: Option<u64>,
+    /// Token that can cancel the operation.
+    token: Option<CancelToken>,
+    /// What to call with progress reports.
+    reporter: Option<Reporter>,
+}
+
+impl Budget{
+    /// Constructs a `Budget` with no limits.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Gives up at the given time; returns self.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self{
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Gives up once the given amount of time has passed (starting now); returns self.
+    pub fn with_timeout(self, timeout: Duration) -> Self{
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Gives up after the given number of steps; returns self.
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self{
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Gives up once the token is cancelled; returns self.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self{
+        self.token = Some(token);
+        self
+    }
+
+    /// Calls `callback` roughly every `every` steps with how far along the operation is,
+    /// and once more when it finishes (or is interrupted); returns self.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// let reports = Arc::new(Mutex::new(Vec::new()));
+    /// let r = reports.clone();
+    /// let budget = Budget::new().with_progress(4, move |p| r.lock().unwrap().push(p.processed()));
+    /// let tree = ExpressionTree::new("(A|B)|(C|D)").unwrap();
+    /// assert_eq!(tree.satisfy_count_within(&budget), Ok(vec![15]));
+    /// assert_eq!(*reports.lock().unwrap(), vec![4, 8, 12, 16, 16]);
+    /// ```
+    pub fn with_progress<F>(mut self, every: u64, callback: F) -> Self
+    where F: Fn(Progress) + Send + Sync + 'static{
+        self.reporter = Some(Reporter { every: every.max(1), callback: Arc::new(callback) });
+        self
+    }
+
+    /// Gets the deadline.
+    pub fn deadline(&self) -> Option<Instant>{
+        self.deadline
+    }
+
+    /// Gets the maximum number of steps.
+    pub fn max_steps(&self) -> Option<u64>{
+        self.max_steps
+    }
+
+    /// Starts counting steps against the budget.
+    pub(crate) fn meter(&self) -> Meter<'_>{
+        Meter { budget: self, steps: 0, models: 0, reported: 0 }
+    }
+}
+
+/// Counts the steps of one operation against a `Budget`.
+#[derive(Debug)]
+pub(crate) struct Meter<'a>{
+    budget: &'a Budget,
+    steps: u64,
+    models: u64,
+    /// Steps taken as of the last progress report.
+    reported: u64,
+}
+
+impl Meter<'_>{
+    /// Takes `n` more steps. Fails with `Interrupted` if the budget has run out.
+    pub(crate) fn step(&mut self, n: u64) -> Result<(), ClawgicError>{
+        self.steps = self.steps.saturating_add(n);
+        if let Some(reporter) = &self.budget.reporter && self.steps - self.reported >= reporter.every{
+            self.report();
+        }
+        if self.budget.max_steps.is_some_and(|max| self.steps > max)
+            || self.budget.token.as_ref().is_some_and(|t| t.is_cancelled())
+            || self.budget.deadline.is_some_and(|d| Instant::now() >= d){
+            return Err(ClawgicError::Interrupted);
+        }
+
+        Ok(())
+    }
+
+    /// Counts `n` more models.
+    pub(crate) fn found(&mut self, n: u64){
+        self.models += n;
+    }
+
+    /// Sends a progress report (if anyone is listening).
+    fn report(&mut self){
+        if let Some(reporter) = &self.budget.reporter{
+            self.reported = self.steps;
+            (reporter.callback)(Progress { processed: self.steps, models: self.models });
+        }
+    }
+}
+
+impl Drop for Meter<'_>{
+    fn drop(&mut self){
+        self.report();
+    }
+}
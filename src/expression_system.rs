@@ -0,0 +1,207 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::expression_tree::aig::Aig;
+use crate::expression_tree::compiled::Compiled;
+use crate::expression_tree::grounding;
+use crate::expression_tree::node::Node;
+use crate::prelude::{Assignment, ExpressionTree, Sentence, VarContext};
+use crate::ClawgicError;
+
+/// Several expressions (outputs) over one shared set of sentences. Nice for multi-output functions (i.e. a full adder).
+///
+/// Truth values are set once for every output, and the outputs are evaluated (or tabled) together.
+/// Every output is attached to one `VarContext` (see `ExpressionTree::attach()`), so they share one universe:
+/// the individuals of every output are in the range of every output's quantifiers.
+/// ```
+/// use clawgic::prelude::*;
+/// //full adder
+/// let mut adder = ExpressionSystem::new();
+/// adder.push(ExpressionTree::new("(A<->B)<->C").unwrap());
+/// adder.push(ExpressionTree::new("(A&B)v(C&~(A<->B))").unwrap());
+/// let a = Predicate::new("A", 0).unwrap().inst(&vec![]).unwrap();
+/// let b = Predicate::new("B", 0).unwrap().inst(&vec![]).unwrap();
+/// let c = Predicate::new("C", 0).unwrap().inst(&vec![]).unwrap();
+/// adder.set_tval(&a, true);
+/// adder.set_tval(&b, true);
+/// adder.set_tval(&c, false);
+/// assert_eq!(adder.evaluate().unwrap(), vec![false, true]);
+/// assert_eq!(adder.truth_table().len(), 8);
+/// ```
+#[derive(Debug, Default)]
+pub struct ExpressionSystem{
+    /// Every output, in order.
+    trees: Vec<ExpressionTree>,
+    /// The truth values every output is attached to.
+    context: VarContext,
+}
+
+impl ExpressionSystem{
+    /// Constructs an empty `ExpressionSystem`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Adds an output to the system, attaching it to the system's context (and detaching it from whatever
+    /// context it had before). Returns its index.
+    pub fn push(&mut self, mut tree: ExpressionTree) -> usize{
+        tree.attach(&self.context);
+        self.trees.push(tree);
+        self.trees.len() - 1
+    }
+
+    /// Gets the i'th output.
+    pub fn get(&self, i: usize) -> Option<&ExpressionTree>{
+        self.trees.get(i)
+    }
+
+    /// creates an iterator over every output.
+    pub fn iter(&self) -> std::slice::Iter<'_, ExpressionTree>{
+        self.trees.iter()
+    }
+
+    /// Number of outputs.
+    pub fn len(&self) -> usize{
+        self.trees.len()
+    }
+
+    /// Whether there are no outputs.
+    pub fn is_empty(&self) -> bool{
+        self.trees.is_empty()
+    }
+
+    /// Gets the context every output is attached to.
+    pub fn context(&self) -> &VarContext{
+        &self.context
+    }
+
+    /// Gets every sentence used by any of the outputs (in order).
+    /// Sentences that use a variable bound by a quantifier aren't included.
+    pub fn sentences(&self) -> Vec<Sentence>{
+        let mut found = BTreeSet::new();
+        for t in self.trees.iter(){
+            grounding::free_sentences(t.node(), &mut Vec::new(), &mut found);
+        }
+        found.into_iter().collect()
+    }
+
+    /// Sets the truth value of the given sentence (once, for every output).
+    pub fn set_tval(&mut self, sentence: &Sentence, value: bool){
+        self.context.set_tval(sentence, value);
+    }
+
+    /// Sets the truth values of the given sentences (once, for every output).
+    pub fn set_tvals(&mut self, sentences: &Assignment){
+        self.context.set_tvals(sentences);
+    }
+
+    /// Attempts to evaluate every output.
+    pub fn evaluate(&self) -> Result<Vec<bool>, ClawgicError>{
+        self.trees.iter().map(|t| t.evaluate()).collect()
+    }
+
+    /// Gets every row of the joint truth table: an assignment of every sentence and the value of each output under it.
+    /// Extremely expensive function.
+    pub fn truth_table(&self) -> Vec<(Assignment, Vec<bool>)>{
+        let grounding = ExpressionTree::grounding_of(&self.trees.iter().collect::<Vec<_>>());
+        let atoms = grounding.atoms().len();
+        let mut compiled: Vec<Compiled> = self.trees.iter().map(|t| Compiled::new(t.node(), &grounding)).collect();
+        let mask = Compiled::row_mask(atoms);
+
+        let mut rows = Vec::new();
+        for b in 0..Compiled::block_count(atoms){
+            let columns: Vec<u64> = compiled.iter_mut().map(|c| c.evaluate_block(b)).collect();
+            for lane in 0..64{
+                if (mask >> lane) & 1 == 0{
+                    break;
+                }
+                let assignment = Compiled::row(b, lane, atoms);
+                rows.push((grounding.to_assignment(&assignment), columns.iter().map(|c| (c >> lane) & 1 == 1).collect()));
+            }
+        }
+
+        rows
+    }
+
+    /// Builds one and-inverter graph with every output (in order), so gates shared between outputs are only stored once.
+    pub fn to_aig(&self) -> Aig{
+        let grounding = ExpressionTree::grounding_of(&self.trees.iter().collect::<Vec<_>>());
+        let mut aig = Aig::new(&grounding);
+        for t in self.trees.iter(){
+            aig.add_output(t.node(), &grounding);
+        }
+        aig
+    }
+
+    /// Gets every compound subexpression that shows up (literally) in more than one output,
+    /// along with the indices of the outputs it shows up in. Biggest subexpressions come first.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut system = ExpressionSystem::new();
+    /// system.push(ExpressionTree::new("(A&B)vC").unwrap());
+    /// system.push(ExpressionTree::new("(A&B)->D").unwrap());
+    /// let shared = system.shared_subexpressions();
+    /// assert_eq!(shared.len(), 1);
+    /// assert!(shared[0].0.lit_eq(&ExpressionTree::new("A&B").unwrap()));
+    /// assert_eq!(shared[0].1, vec![0, 1]);
+    /// ```
+    pub fn shared_subexpressions(&self) -> Vec<(ExpressionTree, Vec<usize>)>{
+        let mut seen: Vec<(&Node, BTreeSet<usize>, usize)> = Vec::new();
+        let mut index = HashMap::new();
+        for (i, t) in self.trees.iter().enumerate(){
+            collect_subexpressions(t.node(), i, &mut seen, &mut index);
+        }
+
+        seen.retain(|(_, outputs, _)| outputs.len() > 1);
+        seen.sort_by_key(|(.., size)| std::cmp::Reverse(*size));
+        seen.into_iter().map(|(node, outputs, _)| (ExpressionTree::from(node.clone()), outputs.into_iter().collect())).collect()
+    }
+}
+
+/// Records every compound subexpression of the node as being in the given output. Returns the size of the node.
+///
+/// `index` is where each subexpression already is in `seen` (which stays in the order they were found).
+fn collect_subexpressions<'a>(node: &'a Node, output: usize, seen: &mut Vec<(&'a Node, BTreeSet<usize>, usize)>, index: &mut HashMap<&'a Node, usize>) -> usize{
+    let size = match node{
+        Node::Operator { left, right, .. } => 1 + collect_subexpressions(left, output, seen, index) + collect_subexpressions(right, output, seen, index),
+        Node::Quantifier { subexpr, .. } => 1 + collect_subexpressions(subexpr, output, seen, index),
+        Node::Sentence { .. } | Node::Constant(..) => return 1,
+    };
+    match index.get(node){
+        Some(i) => {seen[*i].1.insert(output);},
+        None => {
+            index.insert(node, seen.len());
+            seen.push((node, BTreeSet::from([output]), size));
+        },
+    }
+
+    size
+}
+
+/// The clone gets its own context (with the same truth values), so setting one in the clone doesn't change the original.
+impl Clone for ExpressionSystem{
+    fn clone(&self) -> Self {
+        self.trees.iter().map(|t| {
+            let mut t = t.clone();
+            t.detach();
+            t
+        }).collect()
+    }
+}
+
+impl FromIterator<ExpressionTree> for ExpressionSystem{
+    fn from_iter<T: IntoIterator<Item = ExpressionTree>>(iter: T) -> Self {
+        let mut system = Self::new();
+        for tree in iter{
+            system.push(tree);
+        }
+        system
+    }
+}
+
+impl std::ops::Index<usize> for ExpressionSystem{
+    type Output = ExpressionTree;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.trees[index]
+    }
+}
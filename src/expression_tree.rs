@@ -1,255 +1,194 @@
 pub mod node;
 pub mod expression_var;
 pub mod universe;
-mod token;
-
-use token::Token;
+pub mod substitution;
+pub mod pattern;
+pub mod assignment;
+pub mod aig;
+pub mod bdd;
+pub mod cnf;
+pub mod ddnnf;
+pub mod zdd;
+pub mod var_context;
+pub mod diagnostic;
+pub(crate) mod grounding;
+pub(crate) mod compiled;
+pub(crate) mod lexer;
+pub(crate) mod token;
+mod compact;
+mod history;
+mod memo;
+mod minimize;
+
+use lexer::Lexer;
+use token::{Args, Token};
 use node::Node;
 use node::operator::Operator;
-use std::cell::Cell;
-use std::collections::HashMap;
-use std::iter::Filter;
-use std::str::Chars;
-
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+
+use crate::expression_tree::aig::Aig;
+use crate::expression_tree::bdd::Bdd;
+use crate::expression_tree::cnf::Cnf;
+use crate::expression_tree::compiled::Compiled;
+use crate::expression_tree::ddnnf::Ddnnf;
+use crate::expression_tree::diagnostic::Diagnostic;
+use crate::expression_tree::zdd::Zdd;
+use crate::expression_tree::grounding::Grounding;
+use crate::expression_tree::history::{History, Snapshot};
+use crate::expression_tree::memo::Memo;
 use crate::expression_tree::node::negation::Negation;
+use crate::expression_tree::substitution::Substitution;
 use crate::expression_tree::universe::Universe;
+use crate::expression_tree::var_context::{Attachment, VarContext};
 use crate::operator_notation::OperatorNotation;
-use crate::utils::is_valid_var_name;
-use crate::{ClawgicError, utils};
-use crate::prelude::{ExpressionVar, Predicate, Sentence};
+use crate::parser_config::ParserConfig;
+use crate::utils;
+use crate::ClawgicError;
+use crate::budget::{Budget, Meter};
+use crate::prelude::{Assignment, ExpressionVar, Predicate, Sentence};
 
 /// Expression tree for logical expressions in SL.
 #[derive(Debug, Clone)]
 pub struct ExpressionTree{
-    /// All the unique variables in the tree and their current value.
-    uni: Universe,
+    /// All the unique variables in the tree and their current value (shared between clones until one of them changes it).
+    uni: Arc<Universe>,
     /// Root node of the expression Tree.
     root: Node,
-    /// Cached previous result of `evaluate()`
-    value: Cell<Option<bool>>
+    /// Cached previous result of `evaluate()`. A `OnceLock` (instead of a `Cell`) so trees are `Sync`.
+    value: OnceLock<bool>,
+    /// Cached value of every node, so `set_tval()` only causes the nodes above that sentence to be re-evaluated.
+    memo: OnceLock<Memo>,
+    /// Callbacks to run whenever the truth value of a sentence changes (see `on_variable_change()`).
+    observers: Vec<Observer>,
+    /// Undo and redo stacks. `None` unless history mode is on (see `enable_history()`).
+    history: Option<History>,
+    /// The `VarContext` the tree gets its truth values from, if it's attached to one (see `attach()`).
+    context: Option<Attachment>,
+}
+
+/// Function that gets told about a sentence's new truth value.
+type ChangeCallback = dyn Fn(&Sentence, bool) + Send + Sync;
+
+/// Callback added with `ExpressionTree::on_variable_change()`.
+#[derive(Clone)]
+struct Observer(Arc<ChangeCallback>);
+
+impl Observer{
+    /// Every callback in either list (callbacks that are in both only show up once).
+    fn merge(mut first: Vec<Self>, second: Vec<Self>) -> Vec<Self>{
+        for observer in second{
+            if !first.iter().any(|o| Arc::ptr_eq(&o.0, &observer.0)){
+                first.push(observer);
+            }
+        }
+        first
+    }
+}
+
+impl std::fmt::Debug for Observer{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Observer").finish_non_exhaustive()
+    }
 }
 
 impl ExpressionTree{
     ///returns a tree that is just a true node
     #[allow(non_snake_case)]
     pub fn TRUE() -> Self{
-        Self { uni: Universe::new(), root: Node::Constant(Negation::default(), true), value: Cell::new(Some(true)) }
+        Self { uni: Arc::new(Universe::new()), root: Node::Constant(Negation::default(), true), value: OnceLock::from(true), memo: OnceLock::new(), observers: Vec::new(), history: None, context: None }
     }
 
     /// Returns a tree that is just a false node
     #[allow(non_snake_case)]
     pub fn FALSE() -> Self{
-        Self { uni: Universe::new(), root: Node::Constant(Negation::default(), false), value: Cell::new(Some(false)) }
+        Self { uni: Arc::new(Universe::new()), root: Node::Constant(Negation::default(), false), value: OnceLock::from(false), memo: OnceLock::new(), observers: Vec::new(), history: None, context: None }
         
     }
 
     // Constructs a tree with a single constant node of the given value.
     pub fn constant(b: bool) -> Self{
-        Self { uni: Universe::new(), root: Node::Constant(Negation::default(), b), value: Cell::new(Some(b)) }
+        Self { uni: Arc::new(Universe::new()), root: Node::Constant(Negation::default(), b), value: OnceLock::from(b), memo: OnceLock::new(), observers: Vec::new(), history: None, context: None }
     }
 
     /// Constructs a new expression tree given a string representation of an infix logical expression.
     pub fn new(expression: &str) -> Result<Self, ClawgicError>{
-        let shells = &mut Self::shunting_yard(Self::tokenize_expression(expression, &OperatorNotation::default())?)?;
-        let root = Self::construct_tree(shells)?;
-        let vars = Self::create_uni(&root, Universe::new());
-        if !shells.is_empty(){
-            return Err(ClawgicError::NotEnoughOperators);
-        }
-        Ok(Self{
-            uni: vars,
-            root,
-            value: Cell::new(None),
-        })
+        Self::new_with_config(expression, None, &ParserConfig::default())
     }
 
     /// Constructs a new expression tree given a string representation of an infix logical expression and an 
     /// `OperatorNotation` detailing the accepted operators.
     pub fn new_with_notation(expression: &str, notation: &OperatorNotation) -> Result<Self, ClawgicError>{
-        let shells = &mut Self::shunting_yard(Self::tokenize_expression(expression, notation)?)?;
-        let root = Self::construct_tree(shells)?;
+        Self::new_with_config(expression, Some(notation), &ParserConfig::default())
+    }
+
+    /// Constructs a new expression tree given a string representation of an infix logical expression,
+    /// an (optional) `OperatorNotation` detailing the accepted operators, and a `ParserConfig`.
+    pub fn new_with_config(expression: &str, notation: Option<&OperatorNotation>, config: &ParserConfig) -> Result<Self, ClawgicError>{
+        //only copy the expression if there are subscripts to get rid of
+        let normalized;
+        let expression = if expression.contains(|c| ('₀'..='₉').contains(&c)){
+            normalized = utils::normalize_subscripts(expression);
+            &normalized
+        }else{
+            expression
+        };
+        if expression.trim().is_empty(){
+            return Err(ClawgicError::EmptyExpression);
+        }
+        let tokens = Lexer::new(expression, notation.unwrap_or(OperatorNotation::shared_default()), config);
+        let shells = &mut Self::shunting_yard(tokens, config)?;
+        let root = Self::construct_tree(shells, config)?;
         let vars = Self::create_uni(&root, Universe::new());
         if !shells.is_empty(){
             return Err(ClawgicError::NotEnoughOperators);
         }
         Ok(Self{
-            uni: vars,
+            uni: Arc::new(vars),
             root,
-            value: Cell::new(None),
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Vec::new(),
+            history: None,
+            context: None,
         })
     }
 
-    fn parse_vars(c: &mut char, chars: &mut Filter<Chars<'_>, impl FnMut(&char) -> bool>, more_to_parse: &mut bool) -> Result<Vec<ExpressionVar>, ClawgicError>{
-        let mut variables = Vec::new();
-        let mut substring = String::new();
-        if *c == '('{
-            *c = match chars.next(){
-                Some(next_char) => next_char,
-                None => return Err(ClawgicError::InvalidExpression),
-            };
-            if *c != ')'{ //in the form A(x1,y2,...)
-                while *c != ')'{
-                    substring.clear();
-                    while *c != ',' && *c != ')'{
-                        substring.push(*c);
-                        *c = match chars.next(){
-                            Some(next_char) => next_char,
-                            None => {*more_to_parse = false; break;},
-                        };
-                    }
-
-                    if !utils::is_valid_var_name(&substring){
-                        return Err(ClawgicError::InvalidVariableName(substring));
-                    }
-
-                    variables.push(substring.clone());
-                    let last_char = *c;
-                    *c = match chars.next(){
-                        Some(next_char) => next_char,
-                        None => {*more_to_parse = false; break;},
-                    };
-                    if last_char == ')'{
-                        break;
-                    }
-                }
-            }else{
-                *c = match chars.next(){
-                    Some(next_char) => next_char,
-                    None => {*more_to_parse = false; *c},
-                };
-            }
-        }else{ //in the form Ax1y2...
-            while c.is_lowercase() && *c != 'v'{
-                substring.clear();
-                substring.push(*c);
-                *c = match chars.next(){
-                    Some(next_char) => next_char,
-                    None => {*more_to_parse = false; variables.push(substring.clone()); break;}
-                };
-                while c.is_numeric(){
-                    substring.push(*c);
-                    *c = match chars.next(){
-                        Some(next_char) => next_char,
-                        None => {*more_to_parse = false; break;}
-                    };
-                }
-
-                if !is_valid_var_name(&substring){
-                    return Err(ClawgicError::InvalidVariableName(substring));
-                }
-                variables.push(substring.clone());
-            }
-        }
-        let mut exprvars = Vec::new();
-        for v in variables{
-            exprvars.push(ExpressionVar::new(&v)?);
-        }
-        Ok(exprvars)
+    /// `new_with_config()`, but instead of stopping at the first error, keeps going and returns all of them
+    /// along with where they are (see `diagnose()`).
+    pub fn new_with_diagnostics(expression: &str, notation: Option<&OperatorNotation>, config: &ParserConfig) -> Result<Self, Vec<Diagnostic>>{
+        Self::new_with_config(expression, notation, config).map_err(|_| Self::diagnose_with_config(expression, notation, config))
     }
 
-    /// Tokenizes a string representation of an infix logical expression and produces a Vec of `Shell`'s
-    fn tokenize_expression(expression: &str, notation: &OperatorNotation) -> Result<Vec<Token>, ClawgicError>{
-        //using chars enforces exactly one pass.
-        let mut chars = expression.chars().filter(|c| !c.is_whitespace());
-        let mut result = Vec::new();
-        let mut c = match chars.next(){
-            Some(next_char) => next_char,
-            None => return Err(ClawgicError::EmptyExpression)
-        };
-        let mut substring = String::new();
-        let mut more_to_parse = true;
-
-        while more_to_parse{
-            substring.clear();
-            //handle predicates
-            if c.is_alphanumeric() && c != 'v'{
-                while c.is_uppercase(){
-                    substring.push(c);
-                    c = match chars.next(){
-                        Some(next_char) => next_char,
-                        None => {more_to_parse = false; break;},
-                    };
-                }
-
-                if substring.is_empty(){
-                    return Err(ClawgicError::InvalidPredicateName(c.to_string()));
-                }
-
-                if substring == "TRUE"{
-                    result.push(Token::Constant(Negation::default(), true));
-                }else if substring == "FALSE"{
-                    result.push(Token::Constant(Negation::default(), false));
-                }else if substring.len() > 1{
-                    return Err(ClawgicError::InvalidPredicateName(substring));
-                }else{
-                    while c.is_numeric(){
-                        substring.push(c);
-                        c = match chars.next(){
-                            Some(next_char) => next_char,
-                            None => {more_to_parse = false; break;},
-                        };
-                    }
-                    let pred_name = substring.clone();
-                    let variables = Self::parse_vars(&mut c, &mut chars, &mut more_to_parse)?;
-                    result.push(Token::Sentence(Negation::default(), Predicate::new(&pred_name, variables.len()).unwrap(), variables));
-                }
-            } else if !notation.get_potential_operators(&c.to_string()).is_empty() {
-                substring.push(c);
-                while !notation.get_potential_operators(&substring).is_empty(){
-                    c = match chars.next(){
-                        Some(next_char) => next_char,
-                        None => {substring.push(':'); more_to_parse = false; break;},
-                    };
-                    substring.push(c);
-                }
-                substring.pop();
-
-                let op = match notation.get_operator(&substring){
-                    Some(o) => o,
-                    None => return Err(ClawgicError::UnknownSymbol(substring)),
-                };
-
-                if op.is_not(){
-                    result.push(Token::Tilde(Negation::new(1)));
-                }else if op.is_quantifier(){
-                    let vars = Self::parse_vars(&mut c, &mut chars, &mut more_to_parse)?;
-                    if vars.is_empty(){
-                        return Err(ClawgicError::NoVarQuantifier);
-                    }
-                    result.push(Token::Quantifier(Negation::default(), op, vars));
-                }else{
-                    result.push(Token::Operator(Negation::default(), op));
-                }
-            }else if c == '('{
-                result.push(Token::OpenParenthesis);
-
-                c = match chars.next(){
-                    Some(next_char) => next_char,
-                    None => break,
-                };
-            }else if c == ')'{
-                result.push(Token::ClosedParenthesis);
-
-                c = match chars.next(){
-                    Some(next_char) => next_char,
-                    None => break,
-                };
-            }else{
-                return Err(ClawgicError::UnknownSymbol(c.to_string()));
-            }
-        }
+    /// Finds every error in the expression (i.e. to show them all at once in an editor).
+    /// Returns nothing if the expression is fine.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let diagnostics = ExpressionTree::diagnose("(A & ?) v B )");
+    /// let errors: Vec<_> = diagnostics.iter().map(|d| (d.error(), d.span())).collect();
+    /// assert_eq!(errors, vec![
+    ///     (&ClawgicError::UnknownSymbol("?".to_string()), 5..6),
+    ///     (&ClawgicError::InvalidParentheses, 12..13),
+    /// ]);
+    /// assert!(ExpressionTree::diagnose("(A & C) v B").is_empty());
+    /// ```
+    pub fn diagnose(expression: &str) -> Vec<Diagnostic>{
+        Self::diagnose_with_config(expression, None, &ParserConfig::default())
+    }
 
-        Ok(result)
+    /// `diagnose()` with an (optional) `OperatorNotation` detailing the accepted operators, and a `ParserConfig`.
+    pub fn diagnose_with_config(expression: &str, notation: Option<&OperatorNotation>, config: &ParserConfig) -> Vec<Diagnostic>{
+        diagnostic::diagnose(expression, notation.unwrap_or(OperatorNotation::shared_default()), config)
     }
 
     /// Takes a tokenized version of an infix logical expression and converts to postfix.
-    fn shunting_yard(expression: Vec<Token>) -> Result<Vec<Token>, ClawgicError>{
+    fn shunting_yard<'a>(expression: impl Iterator<Item = Result<Token<'a>, ClawgicError>>, config: &ParserConfig) -> Result<Vec<Token<'a>>, ClawgicError>{
 
         let mut postfix = Vec::new();
         let mut operators = Vec::new();
 
         for token in expression{
-            match token{
+            match token?{
                 Token::Tilde(negation) => operators.push(Token::Tilde(negation)),
                 Token::OpenParenthesis => operators.push(Token::OpenParenthesis),
                 Token::Constant(mut negation, value) => {
@@ -259,19 +198,29 @@ impl ExpressionTree{
                     }
                     postfix.push(Token::Constant(negation, value));
                 },
-                Token::Sentence(mut negation, predicate, vars) => {
+                Token::Sentence(mut negation, name, args) => {
                     while operators.last().is_some_and(|op| op.is_tilde()){
                         negation.negate();
                         operators.pop();
                     }
-                    postfix.push(Token::Sentence(negation, predicate, vars));
+                    postfix.push(Token::Sentence(negation, name, args));
+                },
+                Token::ImplicitAnd => {
+                    //implicit conjunctions are left associative
+                    while operators.last().is_some_and(|op| matches!(op, Token::ImplicitAnd)){
+                        postfix.push(operators.pop().unwrap());
+                    }
+                    operators.push(Token::ImplicitAnd);
                 },
                 Token::Operator(mut negation, op) => {
                     if !operators.is_empty(){
+                        while operators.last().is_some_and(|op| matches!(op, Token::ImplicitAnd)){
+                            postfix.push(operators.pop().unwrap());
+                        }
                         while let Some(Token::Operator(_, o)) = operators.last(){
-                            if o.precedence() < op.precedence(){
+                            if config.precedence(*o) < config.precedence(op){
                                 break;
-                            }else if o.precedence() == op.precedence(){
+                            }else if config.precedence(*o) == config.precedence(op){
                                 return Err(ClawgicError::AmbiguousExpression);
                             }
                             postfix.push(operators.pop().unwrap());
@@ -286,9 +235,9 @@ impl ExpressionTree{
                 Token::Quantifier(mut negation, op, vars) => {
                     if !operators.is_empty(){
                         while let Some(Token::Operator(_, o)) = operators.last(){
-                            if o.precedence() < op.precedence(){
+                            if config.precedence(*o) < config.precedence(op){
                                 break;
-                            }else if o.precedence() == op.precedence(){
+                            }else if config.precedence(*o) == config.precedence(op){
                                 return Err(ClawgicError::AmbiguousExpression);
                             }
                             postfix.push(operators.pop().unwrap());
@@ -325,13 +274,13 @@ impl ExpressionTree{
 
                                 postfix.push(Token::Operator(negation, op));
                             },
-                            Token::Sentence(mut negation, pred, vars) => {
+                            Token::Sentence(mut negation, name, args) => {
                                 while operators.last().is_some_and(|op| op.is_tilde()){
                                     negation.negate();
                                     operators.pop();
                                 }
 
-                                postfix.push(Token::Sentence(negation, pred, vars))
+                                postfix.push(Token::Sentence(negation, name, args))
                             },
                             Token::Quantifier(mut negation, op, vars) => {
                                 while operators.last().is_some_and(|op| op.is_tilde()){
@@ -341,6 +290,15 @@ impl ExpressionTree{
 
                                 postfix.push(Token::Quantifier(negation, op, vars))
                             }
+                            Token::ImplicitAnd => {
+                                let mut negation = Negation::default();
+                                while operators.last().is_some_and(|op| op.is_tilde()){
+                                    negation.negate();
+                                    operators.pop();
+                                }
+
+                                postfix.push(Token::Operator(negation, Operator::AND))
+                            },
                             Token::ClosedParenthesis | Token::OpenParenthesis | Token::Tilde(_) => panic!("this should be impossible"),
 
                         }
@@ -359,20 +317,28 @@ impl ExpressionTree{
     }
 
     /// Takes a Vec of `Shell`s, constructs a subtree of `Node`s and returns the root node of that subtree. 
-    fn construct_tree(shells: &mut Vec<Token>) -> Result<Node, ClawgicError>{
+    fn construct_tree(shells: &mut Vec<Token>, config: &ParserConfig) -> Result<Node, ClawgicError>{
         let node = match shells.pop(){
             Some(s) => {
                 match s {
+                    Token::ImplicitAnd => {
+                        let right = Self::construct_tree(shells, config)?;
+                        let left = Self::construct_tree(shells, config)?;
+                        Node::Operator { neg: Negation::default(), op: Operator::AND, left: Arc::new(left), right: Arc::new(right) }
+                    },
                     Token::Operator(denied, op) => {
-                        let right = Self::construct_tree(shells)?;
-                        let left = Self::construct_tree(shells)?;
-                        Node::Operator { neg: denied, op, left: Box::new(left), right: Box::new(right) }
+                        let right = Self::construct_tree(shells, config)?;
+                        let left = Self::construct_tree(shells, config)?;
+                        Node::Operator { neg: denied, op, left: Arc::new(left), right: Arc::new(right) }
                     },
-                    Token::Quantifier(neg, op, vars) => {
-                        let subexpr = Self::construct_tree(shells)?;
-                        Node::Quantifier { neg, op, vars, subexpr: Box::new(subexpr) }
+                    Token::Quantifier(neg, op, args) => {
+                        let subexpr = Self::construct_tree(shells, config)?;
+                        Node::Quantifier { neg, op, vars: Self::expression_vars(args, config)?, subexpr: Arc::new(subexpr) }
                     }
-                    Token::Sentence(denied, predicate, vars) => Node::Sentence { neg: denied, sen: predicate.inst(&vars)?},
+                    Token::Sentence(denied, name, args) => {
+                        let vars = Self::expression_vars(args, config)?;
+                        Node::Sentence { neg: denied, sen: Predicate::new_with_config(name, vars.len(), config)?.inst(&vars)?}
+                    },
                     Token::Constant(neg, value) => Node::Constant(neg, value),
                     Token::OpenParenthesis | Token::ClosedParenthesis => return Err(ClawgicError::InvalidParentheses),
                     Token::Tilde(_) => return Err(ClawgicError::InvalidExpression),
@@ -384,6 +350,11 @@ impl ExpressionTree{
         Ok(node)
     }
 
+    /// Makes the `ExpressionVar` for every variable a token has.
+    fn expression_vars(args: Args, config: &ParserConfig) -> Result<Vec<ExpressionVar>, ClawgicError>{
+        args.vars().map(|v| ExpressionVar::new_with_config(v, config)).collect()
+    }
+
     //OPTIMIZATION: create vars at the same time as construct_tree to avoid excessive work.
     /// Takes a `Node` and the `Universe` and does a depth-first-search for every variable, inserting them into the map as they are found.
     fn create_uni(node: & Node, mut uni: Universe) -> Universe{
@@ -406,115 +377,169 @@ impl ExpressionTree{
     }
 
     /// Sets the truth value of the given sentence.
+    ///
+    /// Only the parts of the tree that contain the sentence get re-evaluated by the next `evaluate()`.
     pub fn set_tval(&mut self, sentence: &Sentence, value: bool){
-        if let Some(tval) = self.uni.get_tval_mut(sentence){
-            self.value.replace(None);
+        if let Some(attachment) = &self.context{
+            if !attachment.set_tval(sentence, value){
+                return;
+            }
+        }else if let Some(tval) = Arc::make_mut(&mut self.uni).get_tval_mut(sentence){
+            if *tval == value{
+                return;
+            }
             *tval = value;
+            self.value.take();
+            if let Some(memo) = self.memo.get_mut(){
+                memo.invalidate(sentence);
+            }
         }else if self.uni.contains_predicate(sentence.predicate()){
-            self.value.replace(None);
-            self.uni.insert_variables(sentence.vars().iter().cloned());
-            self.uni.insert_sentence(sentence.clone(), value);
+            //new individuals change what the quantifiers range over, so everything has to be redone
+            self.value.take();
+            self.memo.take();
+            Arc::make_mut(&mut self.uni).insert_variables(sentence.vars().iter().cloned());
+            Arc::make_mut(&mut self.uni).insert_sentence(sentence.clone(), value);
+        }else{
+            return;
+        }
+
+        for observer in self.observers.iter(){
+            (observer.0)(sentence, value);
         }
     }
 
-    /// Updates the values of multiple .
-    pub fn set_tvals(&mut self, sentences: &HashMap<Sentence, bool>){
+    /// Updates the values of multiple sentences (see `set_tval()`).
+    pub fn set_tvals(&mut self, sentences: &Assignment){
         for (sen, b) in sentences.iter(){
-            if let Some(tval) = self.uni.get_tval_mut(sen){
-                *tval = *b;
-            }else if self.uni.contains_predicate(sen.predicate()){
-                self.uni.insert_variables(sen.vars().iter().cloned());
-                self.uni.insert_sentence(sen.clone(), *b);
-            }
+            self.set_tval(sen, *b);
         }
-        self.value.replace(None);
     }
 
-    /// Replaces all instances of var in the tree with new_expression. Adds all variables from new_expression to self as they are.
-    pub fn replace_sentence(&mut self, sentence: &Sentence, new_expression: &ExpressionTree) -> &mut Self{
-        if self.uni.contains_sentence(sentence){
-            self.uni.remove_sentence(sentence);
-            self.uni.add_universe(new_expression.uni.clone());
-            Self::replace_sentence_rec(&mut self.root, sentence, new_expression);
-            self.value.replace(None);
-        }
+    /// Calls `callback` with the sentence and its new truth value every time `set_tval()` or `set_tvals()`
+    /// actually changes something (which also means the cached result of `evaluate()` was thrown away).
+    /// Returns a mutable reference.
+    ///
+    /// Clones of the tree (and trees built out of it with `and()`, `not()`, etc.) keep the same callbacks;
+    /// a tree built out of two trees keeps the callbacks of both.
+    ///
+    /// If the tree is attached to a `VarContext`, only changes made through this tree are heard about, not ones
+    /// made through the context itself or through other trees attached to it.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// let changes = Arc::new(Mutex::new(Vec::new()));
+    /// let log = changes.clone();
+    /// let mut tree = ExpressionTree::new("A&B").unwrap();
+    /// tree.on_variable_change(move |sen, b| log.lock().unwrap().push((sen.to_string(), b)));
+    /// let a = Sentence::new(&Predicate::new("A", 0).unwrap(), &vec![]).unwrap();
+    /// tree.set_tval(&a, true);
+    /// tree.set_tval(&a, true);
+    /// assert_eq!(*changes.lock().unwrap(), vec![("A".to_string(), true)]);
+    /// ```
+    pub fn on_variable_change<F>(&mut self, callback: F) -> &mut Self
+    where F: Fn(&Sentence, bool) + Send + Sync + 'static{
+        self.observers.push(Observer(Arc::new(callback)));
+        self
+    }
 
+    /// Removes every callback added with `on_variable_change()`.
+    pub fn clear_observers(&mut self){
+        self.observers.clear();
+    }
+
+    /// Attaches the tree to the context, so it gets its truth values from there (and setting one through
+    /// the tree sets it for every tree attached to the context). Returns a mutable reference.
+    ///
+    /// Sentences the context doesn't know about yet keep the tree's truth values. Clones of the tree stay attached,
+    /// and so do trees built out of it with `and()`, `or()`, etc. (if both trees are attached to different contexts,
+    /// the result is attached to the first one's and gets the second one's current truth values).
+    /// `on_variable_change()` callbacks only hear about changes made through this tree, and `universe()` keeps
+    /// the tree's own truth values until it gets detached.
+    pub fn attach(&mut self, context: &VarContext) -> &mut Self{
+        self.detach();
+        self.context = Some(context.attach(&self.uni));
+        self.value.take();
+        self.memo.take();
         self
     }
 
-    /// Recursive helper function for `ExpressionTree::replace_variable()`
-    fn replace_sentence_rec(cur_node: &mut Node, sentence: &Sentence, new_expression: &ExpressionTree){
-        if cur_node.is_sentence(){
-            let Node::Sentence { neg: denied, sen} = cur_node.clone()
-                else{panic!("this should never happen (in replace_variable_rec())")};
-            if *sentence == sen{
-                *cur_node = new_expression.root.clone();
-                if denied.is_denied(){
-                    cur_node.deny();
-                }
-            }
-        }else if cur_node.is_operator(){
-            let Node::Operator { neg: _, op: _, left, right } = cur_node 
-                else{panic!("this should never happen (in replace_variable_rec())")};
-            Self::replace_sentence_rec(left, sentence, new_expression);
-            Self::replace_sentence_rec(right, sentence, new_expression);
-        }
+    /// Detaches the tree from its context (if it has one), keeping the context's current truth values. Returns the context.
+    pub fn detach(&mut self) -> Option<VarContext>{
+        let attachment = self.context.take()?;
+        attachment.copy_tvals(Arc::make_mut(&mut self.uni));
+        self.value.take();
+        self.memo.take();
+
+        Some(attachment.into_context())
     }
 
-    /// Replaces all instances of each sentence in the tree the correlating expression new_expression. Adds all variables from new_expression to self as they are.
-    pub fn replace_sentences(&mut self, sentences: &HashMap<Sentence, &ExpressionTree>) -> &mut Self{
-        // //gotta remove all vars before adding the new ones.
-        // let mut something_in_vars = false;
-        // let mut was_in_vars = Vec::with_capacity(sentences.len());
-        // for (sen, _) in sentences.iter(){
-        //     if self.uni.remove_sentence(sen){
-        //         was_in_vars.push(true);
-        //         something_in_vars = true;
-        //     }else{
-        //         was_in_vars.push(false);
-        //     }
-        // }
-        // for (i, (_, new_expression)) in sentences.iter().enumerate(){
-        //     if was_in_vars[i]{
-        //         for (name, val) in new_expression.uni.all_sentences().iter(){
-        //             if !self.uni.contains_key(name){
-        //                 self.uni.insert(name.clone(), val.clone());
-        //             }
-        //         }
-        //     }
-        // }
-        // if something_in_vars{
-        Self::replace_sentences_rec(&mut self.root, sentences);
-        self.value.replace(None);
-        self.uni = Self::create_uni(&self.root, Universe::new());
-        // }
+    /// Gets the context the tree is attached to (see `attach()`).
+    pub fn context(&self) -> Option<&VarContext>{
+        self.context.as_ref().map(Attachment::context)
+    }
 
+    /// Works out the context of a tree built out of both trees (before their universes get combined).
+    ///
+    /// Keeps whichever context there is. If they're attached to different ones, the second tree gets detached
+    /// (keeping that context's truth values) and the result stays attached to the first tree's context.
+    fn merge_contexts(first: &mut Self, second: &mut Self) -> Option<Attachment>{
+        if let (Some(a), Some(b)) = (&first.context, &second.context) && !a.context().same_as(b.context()){
+            second.detach();
+        }
+        first.context.take().or(second.context.take())
+    }
+
+    /// Returns a copy of the tree with every sentence in `tvals` replaced by its truth value
+    /// and all of the resulting constants folded away.
+    ///
+    /// The result only contains the sentences that are still unknown. Any truth values
+    /// already stored in the tree for those sentences are carried over.
+    pub fn restrict(&self, tvals: &Assignment) -> Self{
+        let mut root = self.root.clone();
+        root.restrict(tvals);
+        let mut uni = Self::create_uni(&root, Universe::new());
+        uni.inherit_tvals(&self.uni);
+
+        Self::from_parts(uni, root)
+    }
+
+    /// Constructs a tree from an already built universe and root.
+    fn from_parts(uni: Universe, root: Node) -> Self{
+        Self{
+            value: root.constant_value().map(OnceLock::from).unwrap_or_default(),
+            memo: OnceLock::new(),
+            observers: Vec::new(),
+            history: None,
+            context: None,
+            uni: Arc::new(uni),
+            root,
+        }
+    }
+
+    /// Replaces all instances of sentence in the tree with new_expression. Adds all variables from new_expression to self as they are.
+    pub fn replace_sentence(&mut self, sentence: &Sentence, new_expression: &ExpressionTree) -> &mut Self{
+        let tree = Substitution::new().with(sentence.clone(), new_expression.clone()).apply(self);
+        self.replace_with(tree);
         self
     }
 
-    /// Recursive helper function for `ExpressionTree::replace_variable()`
-    fn replace_sentences_rec(cur_node: &mut Node, sentences: &HashMap<Sentence, &ExpressionTree>){
-        if cur_node.is_sentence(){
-            let Node::Sentence { neg: denied, sen} = cur_node.clone()
-                else{panic!("this should never happen (in replace_variable_rec())")};
-            if let Some(new_expression) = sentences.get(&sen){
-                *cur_node = new_expression.root.clone();
-                if denied.is_denied(){
-                    cur_node.deny();
-                }
-            }
-        }else if cur_node.is_operator(){
-            let Node::Operator { neg: _, op: _, left, right } = cur_node 
-                else{panic!("this should never happen (in replace_variable_rec())")};
-            Self::replace_sentences_rec(left, sentences);
-            Self::replace_sentences_rec(right, sentences);
-        }
+    /// Replaces all instances of each sentence in the tree the correlating expression new_expression. Adds all variables from new_expression to self as they are.
+    /// 
+    /// All of the replacements happen simultaneously (see `Substitution`).
+    pub fn replace_sentences(&mut self, sentences: &HashMap<Sentence, &ExpressionTree>) -> &mut Self{
+        let sub: Substitution = sentences.iter().map(|(sen, expr)| (sen.clone(), (*expr).clone())).collect();
+        let tree = sub.apply(self);
+        self.replace_with(tree);
+        self
     }
 
     ///replaces all instances of old expression in the tree with new expression.
     pub fn replace_expression(&mut self, old: &ExpressionTree, new: &ExpressionTree){
+        self.record();
         Self::replace_expression_rec(&mut self.root, old, new);
-        self.uni = Self::create_uni(&self.root, Universe::new());
+        self.value.take();
+        self.memo.take();
+        self.uni = Arc::new(Self::create_uni(&self.root, Universe::new()));
     }
 
     fn replace_expression_rec(cur_node: &mut Node, old: &ExpressionTree, new: &ExpressionTree){
@@ -547,21 +572,24 @@ impl ExpressionTree{
                     cur_node.deny();
                 }
             }else{
-                Self::replace_expression_rec(cur_left, old, new);
-                Self::replace_expression_rec(cur_right, old, new);
+                Self::replace_expression_rec(Arc::make_mut(cur_left), old, new);
+                Self::replace_expression_rec(Arc::make_mut(cur_right), old, new);
             }
         }
     }
 
     /// Attempts to evaluate the tree.
     pub fn evaluate(&self) -> Result<bool, ClawgicError>{
+        if let Some(attachment) = &self.context{
+            return attachment.evaluate(&self.root, &self.uni, &self.memo);
+        }
         match self.value.get(){
-            Some(v) => Ok(v),
+            Some(v) => Ok(*v),
             None => {
-                let result = self.root.evaluate(&self.uni, &mut HashMap::new());
+                let result = self.memo.get_or_init(|| Memo::new(&self.root)).evaluate(&self.root, &self.uni);
                 match result{
                     Ok(b) => {
-                        self.value.replace(Some(b));
+                        let _ = self.value.set(b);
                         Ok(b)
                     },
                     Err(e) => Err(e),
@@ -640,6 +668,110 @@ impl ExpressionTree{
         }
     }
 
+    /// Returns the tree as LaTeX math (without the surrounding `$`s), i.e. `\neg A_{1} \rightarrow (B \land C)`.
+    pub fn latex(&self) -> String{
+        let mut latex = String::new();
+        match &self.root{
+            //no parentheses around the whole thing
+            Node::Operator { neg, .. } if neg.count() == 0 => Self::latex_binary(&self.root, &mut latex),
+            _ => Self::latex_rec(&self.root, &mut latex),
+        }
+        latex
+    }
+
+    /// Recursive helper function for `ExpressionTree::latex()`.
+    fn latex_rec(node: &Node, latex: &mut String){
+        latex.push_str(&"\\neg ".repeat(node.negation().count() as usize));
+        match node{
+            Node::Operator { .. } => {
+                latex.push('(');
+                Self::latex_binary(node, latex);
+                latex.push(')');
+            },
+            Node::Quantifier { op, vars, subexpr, .. } => {
+                let symbol = if op.is_uni() {"\\forall"} else {"\\exists"};
+                for v in vars.iter(){
+                    latex.push_str(&format!("{symbol} {} ", utils::to_latex_subscripts(v.name())));
+                }
+                Self::latex_rec(subexpr, latex);
+            },
+            Node::Sentence { sen, .. } => latex.push_str(&utils::to_latex_subscripts(&sen.to_string())),
+            Node::Constant(_, b) => latex.push_str(if *b {"\\top"} else {"\\bot"}),
+        }
+    }
+
+    /// Writes both operands of an operator node (and the operator between them) without the tildes or parentheses.
+    fn latex_binary(node: &Node, latex: &mut String){
+        if let Node::Operator { op, left, right, .. } = node{
+            let symbol = match op{
+                Operator::AND => "\\land",
+                Operator::OR => "\\lor",
+                Operator::CON => "\\rightarrow",
+                _ => "\\leftrightarrow",
+            };
+            Self::latex_rec(left, latex);
+            latex.push_str(&format!(" {symbol} "));
+            Self::latex_rec(right, latex);
+        }
+    }
+
+    /// Returns a string representation of the tree in infix notation with as few parentheses as possible,
+    /// according to the precedence in the given `ParserConfig` (so it parses back into the same tree with that config).
+    /// 
+    /// Quantifiers keep the parentheses around a binary subexpression (for readability),
+    /// and are always wrapped in parentheses when they're an operand.
+    pub fn infix_minimal(&self, notation: Option<&OperatorNotation>, config: &ParserConfig) -> String{
+        let mut infix = String::new();
+        Self::infix_minimal_rec(&self.root, &mut infix, notation.unwrap_or(&OperatorNotation::default()), config);
+        infix
+    }
+
+    /// Recursive helper function for `ExpressionTree::infix_minimal().`
+    fn infix_minimal_rec(node: &Node, infix: &mut String, notation: &OperatorNotation, config: &ParserConfig){
+        match node{
+            Node::Operator { neg, op, left, right } => {
+                infix.push_str(&notation[Operator::NOT].repeat(neg.count() as usize));
+                if neg.is_denied(){
+                    infix.push('(');
+                }
+                Self::infix_minimal_operand(left, *op, infix, notation, config);
+                infix.push_str(&notation[*op]);
+                Self::infix_minimal_operand(right, *op, infix, notation, config);
+                if neg.is_denied(){
+                    infix.push(')');
+                }
+            },
+            Node::Quantifier { subexpr, .. } => {
+                infix.push_str(&node.print(notation));
+                let wrap = matches!(**subexpr, Node::Operator { neg, .. } if !neg.is_denied());
+                if wrap{
+                    infix.push('(');
+                }
+                Self::infix_minimal_rec(subexpr, infix, notation, config);
+                if wrap{
+                    infix.push(')');
+                }
+            },
+            _ => infix.push_str(&node.print(notation)),
+        }
+    }
+
+    /// Writes an operand of a binary operator, wrapped in parentheses if it wouldn't parse back otherwise.
+    fn infix_minimal_operand(node: &Node, parent: Operator, infix: &mut String, notation: &OperatorNotation, config: &ParserConfig){
+        let wrap = match node{
+            Node::Operator { neg, op, .. } => !neg.is_denied() && config.precedence(*op) <= config.precedence(parent),
+            Node::Quantifier { .. } => true,
+            _ => false,
+        };
+        if wrap{
+            infix.push('(');
+        }
+        Self::infix_minimal_rec(node, infix, notation, config);
+        if wrap{
+            infix.push(')');
+        }
+    }
+
     /// Gets the variables map of the tree.
     pub fn universe(&self) -> &Universe{
         &self.uni
@@ -647,7 +779,9 @@ impl ExpressionTree{
 
     /// Converts all operators in the tree into conjunctions and disjunctions with no leading denials.
     pub fn monotenize(&mut self){
+        self.record();
         Self::monotenize_rec(&mut self.root);
+        self.memo.take();
     }
 
     //OPTIMIZE: make monotenization work from the bottom up (monotenization expands the tree)
@@ -672,13 +806,50 @@ impl ExpressionTree{
 
         match node{
             Node::Operator { neg: _, op: _, left, right } => {
-                Self::monotenize_rec(left);
-                Self::monotenize_rec(right);
+                Self::monotenize_rec(Arc::make_mut(left));
+                Self::monotenize_rec(Arc::make_mut(right));
             },
             _ => (),
         }
     }
 
+    /// Makes every repeated subexpression in the tree share one node, so the tree is stored as a DAG.
+    /// Doesn't change what the tree looks like or means, just how much memory it takes.
+    pub fn compact(&mut self) -> &mut Self{
+        self.root = (*compact::share(&self.root, &mut HashMap::new())).clone();
+        self
+    }
+
+    /// Pulls repeated subexpressions out of the tree. Returns the tree with each one replaced by a fresh sentence
+    /// (`D0`, `D1`, ... skipping names already in use) along with the definition of each new sentence.
+    ///
+    /// Only pulls out subexpressions when that makes things smaller (counting a biconditional for each definition),
+    /// and copies that differ only in leading tildes count as the same subexpression.
+    /// Definitions only use sentences defined before them.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("((A&B)v(C&D))->(((A&B)v(C&D))&~((A&B)v(C&D)))").unwrap();
+    /// let (compacted, definitions) = tree.compact_definitions();
+    /// assert_eq!(compacted, ExpressionTree::new("D0->(D0&~D0)").unwrap());
+    /// assert_eq!(definitions.len(), 1);
+    /// assert_eq!(definitions[0].1, ExpressionTree::new("(A&B)v(C&D)").unwrap());
+    /// ```
+    pub fn compact_definitions(&self) -> (ExpressionTree, Vec<(Sentence, ExpressionTree)>){
+        let taken = self.uni.predicates().map(|p| p.name().to_string()).collect();
+        let (root, definitions) = compact::definitions(&self.root, "D", &taken);
+        let mut uni = Self::create_uni(&root, Universe::new());
+        uni.inherit_tvals(&self.uni);
+
+        (Self::from_parts(uni, root), definitions.into_iter().map(|(sen, def)| (sen, ExpressionTree::from(def))).collect())
+    }
+
+    /// `compact_definitions()` as a single tree: the compacted tree and a biconditional for each definition.
+    /// Has exactly the same models as the original once the new sentences are left out.
+    pub fn definitional_form(&self) -> ExpressionTree{
+        let (compacted, definitions) = self.compact_definitions();
+        definitions.into_iter().fold(compacted, |tree, (sen, def)| tree.and(sen.expr().bicon(def)))
+    }
+
     /// Consumes tree and returns the root node. 
     /// 
     /// If you find yourself needing this, chances are that 
@@ -693,81 +864,469 @@ impl ExpressionTree{
     }
 
     ///consumes two trees and returns a tree in the form of self & second.
-    pub fn and(mut self, second: Self) -> Self{
-        self.uni.add_universe(second.uni.clone());
+    pub fn and(mut self, mut second: Self) -> Self{
+        let context = Self::merge_contexts(&mut self, &mut second);
+        Arc::make_mut(&mut self.uni).add_universe((*second.uni).clone());
 
         Self { 
             uni: self.uni, 
-            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::AND, left: Box::new(self.root), right: Box::new(second.root)},
-            value: Cell::new(None),
+            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::AND, left: Arc::new(self.root), right: Arc::new(second.root)},
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Observer::merge(self.observers, second.observers),
+            history: None,
+            context,
         }
     }
 
     ///consumes two trees and returns a tree in the form of self v (wedge) second.
-    pub fn or(mut self, second: Self) -> Self{
-                self.uni.add_universe(second.uni.clone());
+    pub fn or(mut self, mut second: Self) -> Self{
+        let context = Self::merge_contexts(&mut self, &mut second);
+        Arc::make_mut(&mut self.uni).add_universe((*second.uni).clone());
 
 
         Self { 
             uni: self.uni, 
-            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::OR, left: Box::new(self.root), right: Box::new(second.root)},
-            value: Cell::new(None),
+            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::OR, left: Arc::new(self.root), right: Arc::new(second.root)},
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Observer::merge(self.observers, second.observers),
+            history: None,
+            context,
         }
     }
 
     ///consumes two trees and returns a tree in the form of self->consequent.
-    pub fn con(mut self, consequent: Self) -> Self{
-        self.uni.add_universe(consequent.uni.clone());
+    pub fn con(mut self, mut consequent: Self) -> Self{
+        let context = Self::merge_contexts(&mut self, &mut consequent);
+        Arc::make_mut(&mut self.uni).add_universe((*consequent.uni).clone());
 
 
         Self { 
             uni: self.uni, 
-            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::CON, left: Box::new(self.root), right: Box::new(consequent.root)},
-            value: Cell::new(None),
+            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::CON, left: Arc::new(self.root), right: Arc::new(consequent.root)},
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Observer::merge(self.observers, consequent.observers),
+            history: None,
+            context,
         }
     }
 
     ///consumes two trees and returns a tree in the form of self->second.
-    pub fn bicon(mut self: Self, second: Self) -> Self{
-        self.uni.add_universe(second.uni.clone());
+    pub fn bicon(mut self: Self, mut second: Self) -> Self{
+        let context = Self::merge_contexts(&mut self, &mut second);
+        Arc::make_mut(&mut self.uni).add_universe((*second.uni).clone());
 
 
         Self { 
             uni: self.uni, 
-            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::BICON, left: Box::new(self.root), right: Box::new(second.root)},
-            value: Cell::new(None),
+            root: Node::Operator{neg: Negation::default(), op: node::operator::Operator::BICON, left: Arc::new(self.root), right: Arc::new(second.root)},
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Observer::merge(self.observers, second.observers),
+            history: None,
+            context,
         }
     }
 
     ///consumes the tree and produces a tree in the form of ~self.
     pub fn not(mut self) -> Self{
+        self.record();
         self.root.negate();
         match self.value.get_mut(){
             Some(v) => *v = !*v,
             None => (),
         };
+        if let Some(memo) = self.memo.get_mut(){
+            memo.negate_root();
+        }
         self
     }
 
     ///consumes the tree and produces a tree in the form of ∃(vars)(self)
     pub fn existential(self, vars: Vec<ExpressionVar>) -> Self{
         Self { uni: self.uni, 
-            root: Node::Quantifier { neg: Negation::default(), op: Operator::EXI, vars: vars, subexpr: Box::new(self.root) },
-            value: Cell::new(None) 
+            root: Node::Quantifier { neg: Negation::default(), op: Operator::EXI, vars: vars, subexpr: Arc::new(self.root) },
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: self.observers,
+            history: None,
+            context: self.context,
         }
     }
 
     ///consumes the tree and produces a tree in the form of ∀(vars)(self)
     pub fn universal(self, vars: Vec<ExpressionVar>) -> Self{
         Self { uni: self.uni, 
-            root: Node::Quantifier { neg: Negation::default(), op: Operator::UNI, vars: vars, subexpr: Box::new(self.root) },
-            value: Cell::new(None) 
+            root: Node::Quantifier { neg: Negation::default(), op: Operator::UNI, vars: vars, subexpr: Arc::new(self.root) },
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: self.observers,
+            history: None,
+            context: self.context,
         }
     }
 
     ///checks if the two expressions are logically equivalent (produce the same truth tables). Very expensive function.
+    /// 
+    /// Both trees share one ordering of their sentences and get compared 64 rows at a time,
+    /// stopping at the first row where they disagree.
     pub fn log_eq(&self, other: &Self) -> bool{
-        !Self::is_satisfiable(&!self.clone().bicon(other.clone()))
+        self.log_eq_within(other, &Budget::new()).unwrap()
+    }
+
+    ///`log_eq()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn log_eq_within(&self, other: &Self, budget: &Budget) -> Result<bool, ClawgicError>{
+        self.agrees_with(other, None, &mut budget.meter())
+    }
+
+    /// Whether the trees have the same truth value on every row where the don't-care tree (if there is one) is false.
+    fn agrees_with(&self, other: &Self, dont_care: Option<&Self>, meter: &mut Meter) -> Result<bool, ClawgicError>{
+        let trees: Vec<&Self> = [self, other].into_iter().chain(dont_care).collect();
+        let grounding = Self::grounding_of(&trees);
+        let atoms = grounding.atoms().len();
+        let (mut left, mut right) = (Compiled::new(&self.root, &grounding), Compiled::new(&other.root, &grounding));
+        let mut dc = dont_care.map(|t| Compiled::new(&t.root, &grounding));
+        let mask = Compiled::row_mask(atoms);
+
+        for b in 0..Compiled::block_count(atoms){
+            meter.step(mask.count_ones() as u64)?;
+            let cared = dc.as_mut().map_or(mask, |dc| !dc.evaluate_block(b) & mask);
+            if (left.evaluate_block(b) ^ right.evaluate_block(b)) & cared != 0{
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    ///returns every assignment where the two expressions have different truth values
+    ///(i.e. the rows where their truth tables disagree). Empty iff they're logically equivalent. Very expensive function.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let con = ExpressionTree::new("A->B").unwrap();
+    /// let converse = ExpressionTree::new("B->A").unwrap();
+    /// //A true and B false, or A false and B true
+    /// assert_eq!(con.disagreements(&converse).len(), 2);
+    /// ```
+    pub fn disagreements(&self, other: &Self) -> Vec<Assignment>{
+        self.disagreements_within(other, &Budget::new()).unwrap()
+    }
+
+    ///`disagreements()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn disagreements_within(&self, other: &Self, budget: &Budget) -> Result<Vec<Assignment>, ClawgicError>{
+        let grounding = Self::grounding_of(&[self, other]);
+        let atoms = grounding.atoms().len();
+        let (mut left, mut right) = (Compiled::new(&self.root, &grounding), Compiled::new(&other.root, &grounding));
+        let mask = Compiled::row_mask(atoms);
+        let mut meter = budget.meter();
+
+        let mut rows = Vec::new();
+        for b in 0..Compiled::block_count(atoms){
+            meter.step(mask.count_ones() as u64)?;
+            let mut column = (left.evaluate_block(b) ^ right.evaluate_block(b)) & mask;
+            while column != 0{
+                rows.push(grounding.to_assignment(&Compiled::row(b, column.trailing_zeros(), atoms)));
+                column &= column - 1;
+            }
+        }
+
+        Ok(rows)
+    }
+
+    ///checks if the two expressions are logically equivalent wherever the don't-care expression is false
+    ///(they can disagree on any row where it's true). Very expensive function.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let t1 = ExpressionTree::new("A&B").unwrap();
+    /// let t2 = ExpressionTree::new("A").unwrap();
+    /// assert!(!t1.log_eq(&t2));
+    /// //they only disagree when A is true and B is false
+    /// assert!(t1.log_eq_with_dont_care(&t2, &ExpressionTree::new("A&~B").unwrap()));
+    /// ```
+    pub fn log_eq_with_dont_care(&self, other: &Self, dont_care: &Self) -> bool{
+        self.log_eq_with_dont_care_within(other, dont_care, &Budget::new()).unwrap()
+    }
+
+    ///`log_eq_with_dont_care()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn log_eq_with_dont_care_within(&self, other: &Self, dont_care: &Self, budget: &Budget) -> Result<bool, ClawgicError>{
+        self.agrees_with(other, Some(dont_care), &mut budget.meter())
+    }
+
+    ///checks if the two expressions are logically equivalent wherever the constraint is true
+    ///(i.e. they agree on every assignment that satisfies it). Very expensive function.
+    /// 
+    ///The same as `log_eq_with_dont_care()` with the denial of the constraint as the don't-care expression.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// //two encodings of "exactly one of A and B" agree as long as A and B aren't both true
+    /// let xor = ExpressionTree::new("~(A<->B)").unwrap();
+    /// let or = ExpressionTree::new("AvB").unwrap();
+    /// assert!(!xor.log_eq(&or));
+    /// assert!(xor.log_eq_under(&or, &ExpressionTree::new("~(A&B)").unwrap()));
+    /// ```
+    pub fn log_eq_under(&self, other: &Self, constraint: &Self) -> bool{
+        self.log_eq_with_dont_care(other, &!constraint.clone())
+    }
+
+    ///`log_eq_under()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn log_eq_under_within(&self, other: &Self, constraint: &Self, budget: &Budget) -> Result<bool, ClawgicError>{
+        self.log_eq_with_dont_care_within(other, &!constraint.clone(), budget)
+    }
+
+    ///splits the trees into classes of logically equivalent ones (as indices into `trees`). Classes are in the order
+    ///of their first tree, and the indices in each class are increasing. Quantifiers range over the individuals
+    ///of every tree (plus one that none of them name, like with `log_eq()`).
+    ///
+    ///Every tree gets added to one shared binary decision diagram. The diagram is canonical, so equivalent trees
+    ///end up with the same root and nothing has to be compared pairwise.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let trees: Vec<ExpressionTree> = ["A->B", "~AvB", "B->A", "~(A&~B)"].into_iter().map(|s| ExpressionTree::new(s).unwrap()).collect();
+    /// assert_eq!(ExpressionTree::group_by_equivalence(&trees), vec![vec![0, 1, 3], vec![2]]);
+    /// ```
+    pub fn group_by_equivalence(trees: &[Self]) -> Vec<Vec<usize>>{
+        let grounding = Self::grounding_of(&trees.iter().collect::<Vec<_>>());
+        let mut bdd = Bdd::empty(grounding.atoms().clone(), (0..grounding.atoms().len()).collect());
+        let mut classes: Vec<Vec<usize>> = Vec::new();
+        let mut class_of: HashMap<usize, usize> = HashMap::new();
+        for (i, tree) in trees.iter().enumerate(){
+            let root = bdd.add(&tree.root, &grounding);
+            let class = *class_of.entry(root).or_insert_with(|| {
+                classes.push(Vec::new());
+                classes.len() - 1
+            });
+            classes[class].push(i);
+        }
+
+        classes
+    }
+
+    ///returns a small equivalent expression as a disjunction of conjunctions of (possibly denied) sentences
+    ///(Quine-McCluskey). Extremely expensive function.
+    /// 
+    /// Any quantifiers get expanded over the individuals in the expression.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("(A&B)v(A&~B)").unwrap();
+    /// assert!(tree.minimize().lit_eq(&ExpressionTree::new("A").unwrap()));
+    /// ```
+    pub fn minimize(&self) -> Self{
+        self.minimize_with_dont_care(&Self::FALSE())
+    }
+
+    ///`minimize()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn minimize_within(&self, budget: &Budget) -> Result<Self, ClawgicError>{
+        self.minimize_with_dont_care_within(&Self::FALSE(), budget)
+    }
+
+    ///returns a small expression that is equivalent to this one wherever the don't-care expression is false
+    ///(see `minimize()` and `log_eq_with_dont_care()`). Extremely expensive function.
+    /// 
+    ///Expressions over more than 64 sentences are too big to minimize, so they come back as they are.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// //A&B, but A&~B never happens
+    /// let tree = ExpressionTree::new("A&B").unwrap();
+    /// let dont_care = ExpressionTree::new("A&~B").unwrap();
+    /// assert!(tree.minimize_with_dont_care(&dont_care).lit_eq(&ExpressionTree::new("A").unwrap()));
+    /// ```
+    pub fn minimize_with_dont_care(&self, dont_care: &Self) -> Self{
+        self.minimize_with_dont_care_within(dont_care, &Budget::new()).unwrap()
+    }
+
+    ///`minimize_with_dont_care()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn minimize_with_dont_care_within(&self, dont_care: &Self, budget: &Budget) -> Result<Self, ClawgicError>{
+        let grounding = Self::grounding_of(&[self, dont_care]);
+        let atoms = grounding.atoms().len();
+        if atoms > 64{
+            return Ok(Self::from_parts(self.uni.as_ref().clone(), self.root.clone()));
+        }
+        let mut compiled = Compiled::new(&self.root, &grounding);
+        let mut dc = Compiled::new(&dont_care.root, &grounding);
+        let mask = Compiled::row_mask(atoms);
+        let mut meter = budget.meter();
+
+        let (mut on, mut dc_rows) = (Vec::new(), Vec::new());
+        for b in 0..Compiled::block_count(atoms){
+            meter.step(mask.count_ones() as u64)?;
+            let dc_column = dc.evaluate_block(b) & mask;
+            let on_column = compiled.evaluate_block(b) & !dc_column & mask;
+            for (column, rows) in [(on_column, &mut on), (dc_column, &mut dc_rows)]{
+                let mut column = column;
+                while column != 0{
+                    rows.push(((b as u64) << 6) | column.trailing_zeros() as u64);
+                    column &= column - 1;
+                }
+            }
+        }
+
+        let root = minimize::to_node(&minimize::minimize(atoms, &on, &dc_rows, &mut meter)?, grounding.atoms());
+        let mut uni = Self::create_uni(&root, Universe::new());
+        uni.inherit_tvals(&self.uni);
+        Ok(Self::from_parts(uni, root))
+    }
+
+    ///returns every ground sentence that minterm and maxterm indices are over, in order
+    ///(the first one is the most significant bit of an index).
+    pub fn term_vars(&self) -> Vec<Sentence>{
+        let mut atoms = self.grounding().atoms().clone();
+        atoms.sort();
+        atoms
+    }
+
+    ///returns the index of every row of the truth table where the expression is true, in increasing order.
+    ///Very expensive function.
+    /// 
+    ///Rows are numbered like in textbooks: the sentences of `term_vars()` are the bits of the index, most significant first.
+    ///(Use `satisfy_all()` to get the rows as assignments instead.)
+    /// 
+    ///Fails with `TooManySentences` if there are more than 64 sentences (so an index wouldn't fit in a `u64`).
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("A->B").unwrap();
+    /// //rows 00, 01 and 11 (A is the high bit)
+    /// assert_eq!(tree.minterms().unwrap(), vec![0, 1, 3]);
+    /// assert_eq!(tree.maxterms().unwrap(), vec![2]);
+    /// let sens: Vec<Sentence> = ["A", "B"].into_iter().map(|s| Sentence::try_from(s).unwrap()).collect();
+    /// assert!(ExpressionTree::from_minterms(&sens, &[0, 1, 3]).unwrap().log_eq(&tree));
+    /// ```
+    pub fn minterms(&self) -> Result<Vec<u64>, ClawgicError>{
+        self.minterms_within(&Budget::new())
+    }
+
+    ///`minterms()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn minterms_within(&self, budget: &Budget) -> Result<Vec<u64>, ClawgicError>{
+        self.terms(true, &mut budget.meter())
+    }
+
+    ///returns the index of every row of the truth table where the expression is false, in increasing order
+    ///(see `minterms()`). Very expensive function.
+    pub fn maxterms(&self) -> Result<Vec<u64>, ClawgicError>{
+        self.maxterms_within(&Budget::new())
+    }
+
+    ///`maxterms()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn maxterms_within(&self, budget: &Budget) -> Result<Vec<u64>, ClawgicError>{
+        self.terms(false, &mut budget.meter())
+    }
+
+    ///constructs the canonical sum of products of the given minterms, where the sentences are the bits of
+    ///each index (most significant first).
+    /// 
+    ///Fails with `TermOutOfRange` if an index needs more bits than there are sentences.
+    pub fn from_minterms(sentences: &[Sentence], minterms: &[u64]) -> Result<Self, ClawgicError>{
+        Self::from_terms(sentences, minterms, true)
+    }
+
+    ///constructs the canonical product of sums of the given maxterms (see `from_minterms()`).
+    pub fn from_maxterms(sentences: &[Sentence], maxterms: &[u64]) -> Result<Self, ClawgicError>{
+        Self::from_terms(sentences, maxterms, false)
+    }
+
+    /// Gets the index of every row of the truth table where the tree has the given value (see `minterms()`).
+    fn terms(&self, value: bool, meter: &mut Meter) -> Result<Vec<u64>, ClawgicError>{
+        let grounding = self.grounding();
+        let atoms = grounding.atoms();
+        let n = atoms.len();
+        if n > 64{
+            return Err(ClawgicError::TooManySentences(n));
+        }
+        //which bit of a term index each atom is
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|i| &atoms[*i]);
+        let mut bits = vec![0 ; n];
+        for (place, i) in order.into_iter().enumerate(){
+            bits[i] = n - 1 - place;
+        }
+
+        let mut compiled = Compiled::new(&self.root, &grounding);
+        let mask = Compiled::row_mask(n);
+        let mut terms = Vec::new();
+        for b in 0..Compiled::block_count(n){
+            meter.step(mask.count_ones() as u64)?;
+            let column = compiled.evaluate_block(b);
+            let mut column = if value {column} else {!column} & mask;
+            while column != 0{
+                let row = ((b as u64) << 6) | column.trailing_zeros() as u64;
+                terms.push((0..n).filter(|i| (row >> i) & 1 == 1).map(|i| 1 << bits[i]).sum());
+                column &= column - 1;
+            }
+        }
+
+        terms.sort();
+        Ok(terms)
+    }
+
+    /// Constructs the canonical form of the minterms or maxterms (see `from_minterms()`).
+    fn from_terms(sentences: &[Sentence], terms: &[u64], minterms: bool) -> Result<Self, ClawgicError>{
+        if let Some(t) = terms.iter().find(|t| sentences.len() < 64 && **t >> sentences.len() != 0){
+            return Err(ClawgicError::TermOutOfRange(*t));
+        }
+        let mut terms = terms.to_vec();
+        terms.sort();
+        terms.dedup();
+
+        let root = minimize::canonical(&terms, sentences, minterms);
+        Ok(Self::from_parts(Self::create_uni(&root, Universe::new()), root))
+    }
+
+    ///returns every sentence that actually affects the truth value of the expression
+    ///(i.e. every sentence `s` where the expression with `s` true isn't equivalent to it with `s` false). Very expensive function.
+    /// 
+    /// Sentences that use a variable bound by a quantifier aren't included.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("A&(Bv~B)").unwrap();
+    /// let a = Predicate::new("A", 0).unwrap().inst(&vec![]).unwrap();
+    /// let b = Predicate::new("B", 0).unwrap().inst(&vec![]).unwrap();
+    /// assert_eq!(tree.essential_vars(), vec![a]);
+    /// assert_eq!(tree.irrelevant_vars(), vec![b]);
+    /// ```
+    pub fn essential_vars(&self) -> Vec<Sentence>{
+        self.essential_vars_within(&Budget::new()).unwrap()
+    }
+
+    ///`essential_vars()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn essential_vars_within(&self, budget: &Budget) -> Result<Vec<Sentence>, ClawgicError>{
+        self.vars_by_relevance(true, &mut budget.meter())
+    }
+
+    ///returns every sentence in the expression that doesn't affect its truth value (see `essential_vars()`). Very expensive function.
+    pub fn irrelevant_vars(&self) -> Vec<Sentence>{
+        self.irrelevant_vars_within(&Budget::new()).unwrap()
+    }
+
+    ///`irrelevant_vars()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn irrelevant_vars_within(&self, budget: &Budget) -> Result<Vec<Sentence>, ClawgicError>{
+        self.vars_by_relevance(false, &mut budget.meter())
+    }
+
+    /// Gets every sentence that the truth value of the tree does (or doesn't) depend on.
+    fn vars_by_relevance(&self, essential: bool, meter: &mut Meter) -> Result<Vec<Sentence>, ClawgicError>{
+        let mut vars = Vec::new();
+        for sen in self.sentences(){
+            if self.depends_on(&sen, meter)? == essential{
+                vars.push(sen);
+            }
+        }
+
+        Ok(vars)
+    }
+
+    /// Gets every sentence in the tree that doesn't use a bound variable (in order).
+    fn sentences(&self) -> Vec<Sentence>{
+        let mut found = BTreeSet::new();
+        grounding::free_sentences(&self.root, &mut Vec::new(), &mut found);
+        found.into_iter().collect()
+    }
+
+    /// Whether the truth value of the tree depends on the sentence.
+    fn depends_on(&self, sentence: &Sentence, meter: &mut Meter) -> Result<bool, ClawgicError>{
+        let when_true = self.restrict(&Assignment::new().with(sentence.clone(), true));
+        let when_false = self.restrict(&Assignment::new().with(sentence.clone(), false));
+        Ok(!when_true.agrees_with(&when_false, None, meter)?)
     }
 
     ///checks if the two expressions are literally exactly the same (ignoring double negations).
@@ -777,7 +1336,7 @@ impl ExpressionTree{
 
     ///checks if the two expressions are syntactically the same (one can be transformed into the other with primitive logic rules). Very expensive function.
     pub fn syn_eq(&self, other: &Self) -> bool{
-        if self.uni == other.uni{
+        if self.uni != other.uni{
             return false;
         }
         //check for logical equivalence
@@ -786,25 +1345,12 @@ impl ExpressionTree{
 
     ///checks if the expression is satisfiable. Very expensive function.
     pub fn is_satisfiable(&self) -> bool{
-        todo!()
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         return true;
-        //     }
-
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
-
-        //     break;
-        // }
+        self.is_satisfiable_within(&Budget::new()).unwrap()
+    }
 
-        // false
+    ///`is_satisfiable()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn is_satisfiable_within(&self, budget: &Budget) -> Result<bool, ClawgicError>{
+        Ok(self.first_model(&mut budget.meter(), &Assignment::new())?.is_some())
     }
 
     ///checks if the expression is satisfiable given the auxiliary expression. Very expensive function.
@@ -813,91 +1359,89 @@ impl ExpressionTree{
     }
 
     ///returns a set of variables that satisfies the expression if one exists. Very expensive function.
-    pub fn satisfy_one(&self) -> Option<HashMap<Sentence, bool>>{
-        todo!();
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         return Some(vars);
-        //     }
-
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
-
-        //     break;
-        // }
+    pub fn satisfy_one(&self) -> Option<Assignment>{
+        self.satisfy_one_within(&Budget::new()).unwrap()
+    }
 
-        // None
+    ///`satisfy_one()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn satisfy_one_within(&self, budget: &Budget) -> Result<Option<Assignment>, ClawgicError>{
+        self.first_model(&mut budget.meter(), &Assignment::new())
     }
 
     ///returns a set of variables that satisfies the expression and the auxiliary expression if one exists. Very expensive function.
-    pub fn satisfy_one_with(&self, aux: &ExpressionTree) -> Option<HashMap<Sentence, bool>>{
+    pub fn satisfy_one_with(&self, aux: &ExpressionTree) -> Option<Assignment>{
         Self::satisfy_one(&(self.clone() & aux.clone()))
     }
 
-    ///returns a vector of all sets of variables that satisfy the expression. Extremely expensive function.
-    pub fn satisfy_all(&self) -> Vec<HashMap<Sentence, bool>>{
-        todo!()
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-        // let mut maps = Vec::new();
+    ///checks if the expression is satisfiable with some sentences fixed to the given truth values. Very expensive function.
+    /// 
+    ///Like assumptions in an incremental SAT solver: nothing gets changed or rebuilt, so lots of related queries on one expression are cheap.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("(A->B)&(B->C)").unwrap();
+    /// assert!(tree.is_satisfiable_assuming(&Assignment::try_from([("A", true)]).unwrap()));
+    /// assert!(!tree.is_satisfiable_assuming(&Assignment::try_from([("A", true), ("C", false)]).unwrap()));
+    /// let model = tree.satisfy_one_assuming(&Assignment::try_from([("A", true)]).unwrap()).unwrap();
+    /// assert_eq!(model.to_string(), "A=T, B=T, C=T");
+    /// ```
+    pub fn is_satisfiable_assuming(&self, assumptions: &Assignment) -> bool{
+        self.satisfy_one_assuming(assumptions).is_some()
+    }
 
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         maps.push(vars.clone());
-        //     }
+    ///returns a set of variables that satisfies the expression and agrees with the assumptions if one exists
+    ///(see `is_satisfiable_assuming()`). Also has the assumptions on sentences that aren't in the expression. Very expensive function.
+    pub fn satisfy_one_assuming(&self, assumptions: &Assignment) -> Option<Assignment>{
+        self.first_model(&mut Budget::new().meter(), assumptions).unwrap()
+    }
 
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
+    ///returns a vector of all sets of variables that satisfy the expression. Extremely expensive function.
+    pub fn satisfy_all(&self) -> Vec<Assignment>{
+        self.satisfy_all_within(&Budget::new()).unwrap()
+    }
 
-        //     break;
-        // }
+    ///`satisfy_all()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn satisfy_all_within(&self, budget: &Budget) -> Result<Vec<Assignment>, ClawgicError>{
+        let mut models = Vec::new();
+        self.enumerate(budget, |grounding, assignment, value| {
+            if value{
+                models.push(grounding.to_assignment(assignment));
+            }
+            true
+        })?;
 
-        // maps
+        Ok(models)
     }
 
     ///returns a vector of all sets of variables that satisfy the expression and the auxiliary expression. Extremely expensive function.
-    pub fn satisfy_all_with(&self, aux: &ExpressionTree) -> Vec<HashMap<Sentence, bool>>{
+    pub fn satisfy_all_with(&self, aux: &ExpressionTree) -> Vec<Assignment>{
         Self::satisfy_all(&(self.clone() & aux.clone()))
     }
 
     ///returns the total number of ways the expression can be satisfied. very expensive function.
     pub fn satisfy_count(&self) -> Vec<u128>{
-        todo!();
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-        // let len = 1 + vars.len() / 128;
-        // let mut count = vec![0 ; len];
-
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         for c in count.iter_mut(){
-        //             if *c != std::u128::MAX{
-        //                 *c += 1;
-        //                 break;
-        //             }
-        //             *c = 0;
-        //         }
-        //     }
-
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
-
-        //     break;
-        // }
-
-        // count
+        self.satisfy_count_within(&Budget::new()).unwrap()
+    }
+
+    ///`satisfy_count()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn satisfy_count_within(&self, budget: &Budget) -> Result<Vec<u128>, ClawgicError>{
+        let mut count = Vec::new();
+        self.enumerate(budget, |grounding, _, value| {
+            if count.is_empty(){
+                count = vec![0 ; 1 + grounding.atoms().len() / 128];
+            }
+            if value{
+                for c in count.iter_mut(){
+                    if *c != u128::MAX{
+                        *c += 1;
+                        break;
+                    }
+                    *c = 0;
+                }
+            }
+            true
+        })?;
+
+        Ok(count)
     }
 
     ///returns the total number if ways the expression can be satisfied with the auxiliary expression. very expensive function.
@@ -905,55 +1449,165 @@ impl ExpressionTree{
         Self::satisfy_count(&(self.clone() & aux.clone()))        
     }
 
-    ///returns whether the expression is a tautology (always true). Very expensive function.
-    pub fn is_tautology(&self) -> bool{
-        todo!();
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
+    ///returns every distinct assignment of just the given sentences that can be extended into a model of the expression.
+    ///Every other sentence (i.e. auxiliary/Tseitin variables) is treated as existentially quantified. Extremely expensive function.
+    /// 
+    /// Sentences that aren't in the expression are unconstrained, so they can be either true or false.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// //X is just a name for A&B
+    /// let tree = ExpressionTree::new("(X<->(A&B))&(XvC)").unwrap();
+    /// let a = Predicate::new("A", 0).unwrap().inst(&vec![]).unwrap();
+    /// let b = Predicate::new("B", 0).unwrap().inst(&vec![]).unwrap();
+    /// assert_eq!(tree.satisfy_all_projected(&[a, b]).len(), 4);
+    /// ```
+    pub fn satisfy_all_projected(&self, onto: &[Sentence]) -> Vec<Assignment>{
+        self.satisfy_all_projected_within(onto, &Budget::new()).unwrap()
+    }
+
+    ///`satisfy_all_projected()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn satisfy_all_projected_within(&self, onto: &[Sentence], budget: &Budget) -> Result<Vec<Assignment>, ClawgicError>{
+        Ok(self.projected_models(onto, &mut budget.meter())?.into_iter()
+            .map(|m| onto.iter().cloned().zip(m).collect())
+            .collect())
+    }
+
+    ///builds the and-inverter graph of the expression (with a single output).
+    pub fn to_aig(&self) -> Aig{
+        let grounding = self.grounding();
+        let mut aig = Aig::new(&grounding);
+        aig.add_output(&self.root, &grounding);
+        aig
+    }
+
+    ///builds the binary decision diagram of the expression, with the atoms ordered by `Bdd::best_order()`. Expensive function.
+    pub fn to_bdd(&self) -> Bdd{
+        let grounding = self.grounding();
+        Bdd::new(&self.root, &grounding, Bdd::force_order(&self.root, &grounding))
+    }
+
+    ///builds the binary decision diagram of the expression with the given order of sentences (top first).
+    ///sentences that don't show up are ignored, and ones that are left out go at the bottom. Expensive function.
+    pub fn to_bdd_with_order(&self, order: &[Sentence]) -> Bdd{
+        let grounding = self.grounding();
+        let mut levels: Vec<usize> = Vec::with_capacity(grounding.atoms().len());
+        for i in order.iter().filter_map(|s| grounding.atom_index(s)).chain(0..grounding.atoms().len()){
+            if !levels.contains(&i){
+                levels.push(i);
+            }
+        }
+        Bdd::new(&self.root, &grounding, levels)
+    }
 
-        // 'outer: loop{
-        //     if !self.evaluate_with_vars(&vars).unwrap(){
-        //         return false;
-        //     }
+    ///builds the zero-suppressed decision diagram of the models of the expression (through `to_bdd()`, so with the same order). Expensive function.
+    pub fn to_zdd(&self) -> Zdd{
+        Zdd::from(&self.to_bdd())
+    }
+
+    ///returns an equivalent `Cnf` (with no tautologies or subsumed clauses), distributing disjunctions over conjunctions.
+    ///Extremely expensive function (the number of clauses can blow up exponentially).
+    /// 
+    ///Any quantifiers get expanded over the individuals in the expression.
+    pub fn to_cnf(&self) -> Cnf{
+        Cnf::from_node(&self.root, &self.grounding())
+    }
+
+    ///compiles the expression into d-DNNF, which answers model counting, clause entailment and conditioning
+    ///queries in time linear in its size. Worth it when the same expression gets queried over and over. Expensive function.
+    pub fn compile_ddnnf(&self) -> Ddnnf{
+        Ddnnf::new(&self.root, &self.grounding())
+    }
+
+    ///returns the number of distinct assignments of just the given sentences that can be extended into a model of the expression.
+    ///(see `satisfy_all_projected()`). Extremely expensive function.
+    ///
+    ///Unlike `satisfy_count()` this is a single `u128`: the distinct assignments get collected along the way,
+    ///so there can't be more of them than fit in memory, and the count never saturates.
+    pub fn satisfy_count_projected(&self, onto: &[Sentence]) -> u128{
+        self.satisfy_count_projected_within(onto, &Budget::new()).unwrap()
+    }
+
+    ///`satisfy_count_projected()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn satisfy_count_projected_within(&self, onto: &[Sentence], budget: &Budget) -> Result<u128, ClawgicError>{
+        Ok(self.projected_models(onto, &mut budget.meter())?.len() as u128)
+    }
+
+    /// Gets every distinct assignment of `onto` (in the same order) that some model of the tree extends.
+    fn projected_models(&self, onto: &[Sentence], meter: &mut Meter) -> Result<Vec<Vec<bool>>, ClawgicError>{
+        let mut grounding = self.grounding();
+        let positions: Vec<usize> = onto.iter().map(|s| grounding.add_atom(s.clone())).collect();
+        let atoms = grounding.atoms().len();
+        let mut compiled = Compiled::new(&self.root, &grounding);
+        let mask = Compiled::row_mask(atoms);
+
+        let mut seen = HashSet::new();
+        let mut models = Vec::new();
+        for b in 0..Compiled::block_count(atoms){
+            meter.step(mask.count_ones() as u64)?;
+            let mut column = compiled.evaluate_block(b) & mask;
+            while column != 0{
+                let row = Compiled::row(b, column.trailing_zeros(), atoms);
+                column &= column - 1;
+                let projected: Vec<bool> = positions.iter().map(|p| row[*p]).collect();
+                if seen.insert(projected.clone()){
+                    meter.found(1);
+                    models.push(projected);
+                }
+            }
+        }
 
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
+        Ok(models)
+    }
 
-        //     break;
-        // }
+    ///returns whether the expression is a tautology (always true). Very expensive function.
+    /// 
+    /// Checked by refutation: it's a tautology iff its denial is unsatisfiable.
+    pub fn is_tautology(&self) -> bool{
+        self.is_tautology_within(&Budget::new()).unwrap()
+    }
 
-        // true
+    ///`is_tautology()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn is_tautology_within(&self, budget: &Budget) -> Result<bool, ClawgicError>{
+        Ok(self.counterexample_within(budget)?.is_none())
     }
 
-    ///returns whether the expression is tautological with the auxiliary expression. Very expensive function.
+    ///returns whether the expression is tautological with the auxiliary expression
+    ///(true whenever the auxiliary expression is, i.e. `aux -> self` is a tautology). Very expensive function.
     pub fn is_tautology_with(&self, aux: &ExpressionTree) -> bool{
-        Self::is_inconsistency(&(self.clone() & aux.clone()))
+        Self::is_tautology(&aux.clone().con(self.clone()))
     }
 
-    ///returns whether the expression is an inconsistency (always false). Very expensive function.
-    pub fn is_inconsistency(&self) -> bool{
-        todo!();
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         return false;
-        //     }
+    ///returns a set of variables that makes the expression false if one exists
+    ///(i.e. why it isn't a tautology). Very expensive function.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let tree = ExpressionTree::new("A->B").unwrap();
+    /// let counterexample = tree.counterexample().unwrap();
+    /// let a = Predicate::new("A", 0).unwrap().inst(&vec![]).unwrap();
+    /// let b = Predicate::new("B", 0).unwrap().inst(&vec![]).unwrap();
+    /// assert_eq!(counterexample[&a], true);
+    /// assert_eq!(counterexample[&b], false);
+    /// assert!(ExpressionTree::new("Av~A").unwrap().counterexample().is_none());
+    /// ```
+    pub fn counterexample(&self) -> Option<Assignment>{
+        self.counterexample_within(&Budget::new()).unwrap()
+    }
 
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
+    ///`counterexample()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn counterexample_within(&self, budget: &Budget) -> Result<Option<Assignment>, ClawgicError>{
+        (!self.clone()).first_model(&mut budget.meter(), &Assignment::new())
+    }
 
-        //     break;
-        // }
+    ///returns whether the expression is an inconsistency (always false). Very expensive function.
+    /// 
+    /// Checked by refutation: it's an inconsistency iff it's unsatisfiable.
+    pub fn is_inconsistency(&self) -> bool{
+        !self.is_satisfiable()
+    }
 
-        // true
+    ///`is_inconsistency()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn is_inconsistency_within(&self, budget: &Budget) -> Result<bool, ClawgicError>{
+        Ok(!self.is_satisfiable_within(budget)?)
     }
 
     ///returns whether the expression is inconsistent with the auxiliary expression. Very expensive function.
@@ -963,70 +1617,172 @@ impl ExpressionTree{
 
     ///returns whether the expression is a contingency (sometimes true, sometimes false). Very expensive function.
     pub fn is_contingency(&self) -> bool{
-        todo!();
-        // let mut vars: HashMap<String, bool> = self.uni.iter().map(|(n, _)| (n.to_owned(), false)).collect();
-        // let mut can_be_false = false;
-        // let mut can_be_true = false;
+        self.is_satisfiable() && !self.is_tautology()
+    }
 
-        // 'outer: loop{
-        //     if self.evaluate_with_vars(&vars).unwrap(){
-        //         can_be_true = true;
-        //     }else{
-        //         can_be_false = true;
-        //     }
+    ///`is_contingency()`, but gives up with `Interrupted` once the budget runs out
+    ///(the budget is shared between both halves of the check).
+    pub fn is_contingency_within(&self, budget: &Budget) -> Result<bool, ClawgicError>{
+        let mut meter = budget.meter();
+        Ok(self.first_model(&mut meter, &Assignment::new())?.is_some()
+            && (!self.clone()).first_model(&mut meter, &Assignment::new())?.is_some())
+    }
+
+    ///returns whether the expression is contingent with the auxiliary expression. Very expensive function.
+    pub fn is_contingency_with(&self, aux: &ExpressionTree) -> bool{
+        Self::is_contingency(&(self.clone() & aux.clone()))
+    }
 
-        //     if can_be_false && can_be_true{
-        //         return true;
-        //     }
+    ///returns whether the expression is in negation normal form: only conjunctions, disjunctions, and
+    ///quantifiers, with every tilde on a sentence or constant (and at most one each).
+    /// ```
+    /// use clawgic::prelude::*;
+    /// assert!(ExpressionTree::new("~Av(B&@x~Px)").unwrap().is_nnf());
+    /// assert!(!ExpressionTree::new("~(A&B)").unwrap().is_nnf());
+    /// assert!(!ExpressionTree::new("A->B").unwrap().is_nnf());
+    /// ```
+    pub fn is_nnf(&self) -> bool{
+        self.root.is_nnf()
+    }
+
+    ///returns whether the expression is in conjunctive normal form (a conjunction of disjunctions of literals).
+    ///A single clause or literal counts.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// assert!(ExpressionTree::new("(Av~B)&(C&(~AvD))").unwrap().is_cnf());
+    /// assert!(ExpressionTree::new("Av~B").unwrap().is_cnf());
+    /// assert!(!ExpressionTree::new("(A&B)vC").unwrap().is_cnf());
+    /// ```
+    pub fn is_cnf(&self) -> bool{
+        self.root.is_cnf()
+    }
+
+    ///returns whether the expression is in disjunctive normal form (a disjunction of conjunctions of literals).
+    ///A single term or literal counts.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// assert!(ExpressionTree::new("(A&~B)v(C&~D)").unwrap().is_dnf());
+    /// assert!(ExpressionTree::new("A&~B").unwrap().is_dnf());
+    /// assert!(!ExpressionTree::new("(AvB)&C").unwrap().is_dnf());
+    /// ```
+    pub fn is_dnf(&self) -> bool{
+        self.root.is_dnf()
+    }
+
+    /// Finds the first row of the truth table where the tree is true and every assumption holds (checking 64 rows at a time).
+    /// 
+    /// Assumptions on sentences that aren't in the tree are added to the model.
+    pub(crate) fn first_model(&self, meter: &mut Meter, assumptions: &Assignment) -> Result<Option<Assignment>, ClawgicError>{
+        let grounding = self.grounding();
+        let atoms = grounding.atoms().len();
+        let mut compiled = Compiled::new(&self.root, &grounding);
+        let mask = Compiled::row_mask(atoms);
+        let fixed: Vec<(usize, bool)> = assumptions.iter().filter_map(|(s, b)| grounding.atom_index(s).map(|i| (i, *b))).collect();
+
+        for b in 0..Compiled::block_count(atoms){
+            //rows that go against an assumption are just masked out
+            let allowed = fixed.iter().fold(mask, |m, (i, v)| {
+                let column = compiled::atom_column(*i, b);
+                m & if *v {column} else {!column}
+            });
+            if allowed == 0{
+                continue;
+            }
+            meter.step(allowed.count_ones() as u64)?;
+            let column = compiled.evaluate_block(b) & allowed;
+            if column != 0{
+                meter.found(1);
+                let mut model = grounding.to_assignment(&Compiled::row(b, column.trailing_zeros(), atoms));
+                for (s, v) in assumptions.iter(){
+                    if !model.contains(s){
+                        model.insert(s.clone(), *v);
+                    }
+                }
+                return Ok(Some(model));
+            }
+        }
 
-        //     for (_, b) in vars.iter_mut(){
-        //         *b = !*b;
-        //         if *b{
-        //             continue 'outer;
-        //         }
-        //     }
+        Ok(None)
+    }
 
-        //     break;
-        // }
+    /// Grounds the tree so that it can be checked by going through every truth assignment.
+    fn grounding(&self) -> Grounding{
+        Self::grounding_of(&[self])
+    }
 
-        // false
+    /// Grounds several trees together (so they share a domain and a list of atoms).
+    pub(crate) fn grounding_of(trees: &[&Self]) -> Grounding{
+        let domain = Grounding::domain_of(trees.iter().map(|t| &t.root), trees.iter().flat_map(|t| t.uni.variables().iter()));
+        let mut grounding = Grounding::new(domain);
+        for t in trees.iter(){
+            grounding.add_atoms(&t.root);
+        }
+        grounding
     }
 
-    ///returns whether the expression is contingent with the auxiliary expression. Very expensive function.
-    pub fn is_contingency_with(&self, aux: &ExpressionTree) -> bool{
-        Self::is_contingency(&(self.clone() & aux.clone()))
+    /// Goes through every truth assignment of the tree's (ground) sentences, calling `visit` with the
+    /// assignment and what the tree evaluates to under it. Stops early once `visit` returns false.
+    /// 
+    /// Fails with `Interrupted` once the budget runs out.
+    fn enumerate<F>(&self, budget: &Budget, mut visit: F) -> Result<(), ClawgicError>
+    where F: FnMut(&Grounding, &[bool], bool) -> bool{
+        let mut grounding = self.grounding();
+        let mut assignment = vec![false ; grounding.atoms().len()];
+        let mut meter = budget.meter();
+        loop{
+            let value = grounding.evaluate(&self.root, &assignment)?;
+            if value{
+                meter.found(1);
+            }
+            meter.step(1)?;
+            if !visit(&grounding, &assignment, value) || !grounding::next_assignment(&mut assignment){
+                break;
+            }
+        }
+
+        Ok(())
     }
 
     /// If the tree has at least one leading tilde,
     /// remove one. otherwise, add one. returns a mutable reference.
     pub fn deny(&mut self) -> &mut Self{
+        self.record();
         self.root.deny();
         match self.value.get_mut(){
             Some(v) => *v = !*v,
             None => (),
         };
+        if let Some(memo) = self.memo.get_mut(){
+            memo.negate_root();
+        }
         self
     }
 
     /// If the tree has at least 2 leading tildes,
     /// remove two. otherwise, add two. returns a mutable reference.
     pub fn double_deny(&mut self) -> &mut Self{
+        self.record();
         self.root.double_deny();
         self
     }
 
     /// Adds a leading tilde; returns a mutable reference.
     pub fn negate(&mut self) -> &mut Self{
+        self.record();
         self.root.negate();
         match self.value.get_mut(){
             Some(v) => *v = !*v,
             None => (),
         };
+        if let Some(memo) = self.memo.get_mut(){
+            memo.negate_root();
+        }
         self
     }
 
     /// Adds two leading tildes; returns a mutable reference.
     pub fn double_negate(&mut self) -> &mut Self{
+        self.record();
         self.root.double_negate();
         self
     }
@@ -1034,6 +1790,7 @@ impl ExpressionTree{
     /// Reduces the number of leading tildes to 0 or 1,
     /// retaining truth value; returns a mutable refernce.
     pub fn reduce_negation(&mut self) -> &mut Self{
+        self.record();
         self.root.reduce_negation();
         self
     }
@@ -1043,8 +1800,9 @@ impl ExpressionTree{
     /// 
     /// Otherwise, does nothing and returns `None`.
     pub fn demorgans(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.demorgans(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1056,8 +1814,9 @@ impl ExpressionTree{
     /// 
     /// Opts for negation over denial.
     pub fn demorgans_neg(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.demorgans_neg(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1067,8 +1826,9 @@ impl ExpressionTree{
     /// 
     /// otherwise, does nothing and returns `None`.
     pub fn transposition(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.transposition(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1080,8 +1840,9 @@ impl ExpressionTree{
     /// 
     /// Opts for negation over denial.
     pub fn transposition_neg(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.transposition_neg(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1092,8 +1853,9 @@ impl ExpressionTree{
     /// 
     /// Otherwise, does nothing and returns None.. 
     pub fn implication(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.implication(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1106,8 +1868,9 @@ impl ExpressionTree{
     /// 
     /// Opts for negation over denial.
     pub fn implication_neg(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.implication_neg(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1117,8 +1880,9 @@ impl ExpressionTree{
     /// 
     /// Otherwise does nothing and returns `None`.
     pub fn ncon(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.ncon(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1130,8 +1894,9 @@ impl ExpressionTree{
     /// 
     /// Opts for negation over denial.
     pub fn ncon_neg(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.ncon_neg(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1140,8 +1905,9 @@ impl ExpressionTree{
     /// if its main connective is a biconditional or a conjunction of conditionals; returns a mut reference. 
     /// Otherwise, does nothing and returns `None`.
     pub fn mat_eq(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.mat_eq(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1153,8 +1919,9 @@ impl ExpressionTree{
     /// Also if operator is denied, consumes the denial
     /// and handles it accordingly.
     pub fn mat_eq_mono(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.mat_eq_mono(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1164,8 +1931,9 @@ impl ExpressionTree{
     /// 
     /// Otherwise, does nothing and returns None.
     pub fn quant_exch(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.quant_exch(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
@@ -1177,12 +1945,123 @@ impl ExpressionTree{
     /// 
     /// Opts for negation instead of denial
     pub fn quant_exch_neg(&mut self) -> Option<&mut Self>{
+        let before = self.snapshot();
         match self.root.quant_exch_neg(){
-            Some(_) => Some(self),
+            Some(_) => Some(self.rewritten(before)),
             None => None,
         }
     }
 
+    /// Forgets the cached value of every node after the shape of the tree changed
+    /// (the value of the whole tree stays the same) and records the old tree in the history; returns a mutable reference.
+    fn rewritten(&mut self, before: Option<Snapshot>) -> &mut Self{
+        self.memo.take();
+        if let (Some(history), Some(before)) = (&mut self.history, before){
+            history.push(before);
+        }
+        self
+    }
+
+    /// Turns on history mode; returns a mutable reference.
+    ///
+    /// In history mode every transformation (`deny()`, `demorgans()`, `replace_sentence()`, `monotenize()`, etc.)
+    /// can be taken back with `undo()` (and then put back with `redo()`).
+    /// Truth values aren't part of the history: undoing keeps the current truth value of every sentence.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut tree = ExpressionTree::new("~(A&B)").unwrap();
+    /// tree.enable_history();
+    /// tree.demorgans();
+    /// assert_eq!(tree, ExpressionTree::new("~Av~B").unwrap());
+    /// assert!(tree.undo());
+    /// assert_eq!(tree, ExpressionTree::new("~(A&B)").unwrap());
+    /// assert!(tree.redo());
+    /// assert_eq!(tree, ExpressionTree::new("~Av~B").unwrap());
+    /// ```
+    pub fn enable_history(&mut self) -> &mut Self{
+        if self.history.is_none(){
+            self.history = Some(History::default());
+        }
+        self
+    }
+
+    /// Turns off history mode and forgets the undo and redo stacks.
+    pub fn disable_history(&mut self){
+        self.history = None;
+    }
+
+    /// Whether history mode is on (see `enable_history()`).
+    pub fn history_enabled(&self) -> bool{
+        self.history.is_some()
+    }
+
+    /// Takes back the last transformation. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool{
+        let current = Snapshot { root: self.root.clone(), uni: self.uni.clone() };
+        match self.history.as_mut().and_then(|h| h.undo(current)){
+            Some(previous) => {
+                self.restore(previous);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Puts back the last transformation that was undone. Returns false if there is nothing to redo.
+    ///
+    /// Doing a new transformation after an `undo()` throws away everything that could be redone.
+    pub fn redo(&mut self) -> bool{
+        let current = Snapshot { root: self.root.clone(), uni: self.uni.clone() };
+        match self.history.as_mut().and_then(|h| h.redo(current)){
+            Some(next) => {
+                self.restore(next);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Whether `undo()` would do anything.
+    pub fn can_undo(&self) -> bool{
+        self.history.as_ref().is_some_and(|h| h.can_undo())
+    }
+
+    /// Whether `redo()` would do anything.
+    pub fn can_redo(&self) -> bool{
+        self.history.as_ref().is_some_and(|h| h.can_redo())
+    }
+
+    /// Gets the current state of the tree if history mode is on.
+    fn snapshot(&self) -> Option<Snapshot>{
+        self.history.as_ref().map(|_| Snapshot { root: self.root.clone(), uni: self.uni.clone() })
+    }
+
+    /// Records the current state of the tree in the history (if history mode is on) before it gets transformed.
+    fn record(&mut self){
+        if let Some(history) = &mut self.history{
+            history.push(Snapshot { root: self.root.clone(), uni: self.uni.clone() });
+        }
+    }
+
+    /// Goes back (or forward) to the snapshot, keeping the current truth values.
+    fn restore(&mut self, snapshot: Snapshot){
+        let mut uni = snapshot.uni;
+        Arc::make_mut(&mut uni).inherit_tvals(&self.uni);
+        self.uni = uni;
+        self.root = snapshot.root;
+        self.value.take();
+        self.memo.take();
+    }
+
+    /// Swaps in a transformed version of the tree, keeping the callbacks and recording the history.
+    fn replace_with(&mut self, tree: Self){
+        self.record();
+        self.uni = tree.uni;
+        self.root = tree.root;
+        self.value = tree.value;
+        self.memo = tree.memo;
+    }
+
     /// Gets the main connective.
     pub fn main_connective(&self) -> Option<Operator>{
         match self.root{
@@ -1273,19 +2152,57 @@ impl Default for ExpressionTree{
     /// Default value is just a constant false node.
     fn default() -> Self {
         Self { 
-            uni: Universe::new(), 
+            uni: Arc::new(Universe::new()), 
             root: Node::Constant(Negation::default(), false),
-            value: Cell::new(None),
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Vec::new(),
+            history: None,
+            context: None,
         }
     }
 }
 
+/// Trees are equal if they're literally the same (see `lit_eq()`).
+/// The truth values of their sentences (and the cached value) are ignored.
+impl PartialEq for ExpressionTree{
+    fn eq(&self, other: &Self) -> bool {
+        self.lit_eq(other)
+    }
+}
+
+impl Eq for ExpressionTree{}
+
+/// Only hashes the root node, so it agrees with `PartialEq`.
+impl std::hash::Hash for ExpressionTree{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.root.hash(state);
+    }
+}
+
+/// Trees are ordered by their root nodes (see `Node` for the order).
+impl PartialOrd for ExpressionTree{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExpressionTree{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.root.cmp(&other.root)
+    }
+}
+
 impl From<Node> for ExpressionTree{
     fn from(n: Node) -> Self{
         Self { 
-            uni: Self::create_uni(&n, Universe::new()), 
+            uni: Arc::new(Self::create_uni(&n, Universe::new())), 
             root: n,
-            value: Cell::new(None),
+            value: OnceLock::new(),
+            memo: OnceLock::new(),
+            observers: Vec::new(),
+            history: None,
+            context: None,
         }
     }
 }
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::expression_tree::grounding::{for_each_instance, Grounding};
+use crate::expression_tree::node::Node;
+use crate::prelude::{Assignment, ExpressionVar, Operator, Sentence};
+
+/// Literal for the constant false (true is `FALSE ^ 1`).
+const FALSE: u32 = 0;
+
+/// An and-inverter graph (AIG): every expression is built out of two-input ands, with inversions marked on the edges.
+///
+/// Literals follow the AIGER convention: variable `v` is the literal `2v` and its inversion `2v + 1`.
+/// Variable 0 is the constant false, the inputs (the ground sentences) come next, then the and gates.
+/// Gates are structurally hashed, so the same and of the same literals is only ever stored once.
+/// ```
+/// use clawgic::prelude::*;
+/// let aig = ExpressionTree::new("(A&B)v(C&(A&B))").unwrap().to_aig();
+/// //A&B only shows up once
+/// assert_eq!(aig.and_count(), 3);
+/// assert_eq!(aig.to_aiger(), "aag 6 3 0 1 3\n2\n4\n6\n13\n8 4 2\n10 8 6\n12 11 9\ni0 A\ni1 B\ni2 C\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Aig{
+    /// Every input, in order (input `i` is variable `i + 1`).
+    inputs: Vec<Sentence>,
+    /// Both (ordered) inputs of every and gate, in order (gate `k` is variable `inputs.len() + k + 1`).
+    ands: Vec<(u32, u32)>,
+    /// Where each and gate already is (so nothing gets stored twice).
+    strash: HashMap<(u32, u32), u32>,
+    /// Literal of every output, in order.
+    outputs: Vec<u32>,
+}
+
+impl Aig{
+    /// Constructs an `Aig` with every atom of the grounding as an input and no outputs.
+    pub(crate) fn new(grounding: &Grounding) -> Self{
+        Self { inputs: grounding.atoms().clone(), ands: Vec::new(), strash: HashMap::new(), outputs: Vec::new() }
+    }
+
+    /// Adds the node as an output. Every sentence in it must have been added to the grounding first.
+    pub(crate) fn add_output(&mut self, node: &Node, grounding: &Grounding){
+        let lit = self.build(node, grounding, &mut HashMap::new());
+        self.outputs.push(lit);
+    }
+
+    /// Gets every input, in order.
+    pub fn inputs(&self) -> &Vec<Sentence>{
+        &self.inputs
+    }
+
+    /// Number of and gates.
+    pub fn and_count(&self) -> usize{
+        self.ands.len()
+    }
+
+    /// Number of outputs.
+    pub fn output_count(&self) -> usize{
+        self.outputs.len()
+    }
+
+    /// Evaluates every output. Inputs the assignment leaves out count as false.
+    pub fn evaluate(&self, assignment: &Assignment) -> Vec<bool>{
+        let mut values = Vec::with_capacity(1 + self.inputs.len() + self.ands.len());
+        values.push(false);
+        values.extend(self.inputs.iter().map(|s| assignment.get(s) == Some(true)));
+        for (a, b) in self.ands.iter(){
+            let value = Self::value(&values, *a) && Self::value(&values, *b);
+            values.push(value);
+        }
+
+        self.outputs.iter().map(|o| Self::value(&values, *o)).collect()
+    }
+
+    /// Writes the graph in the ASCII AIGER format (`aag`), with a symbol for every input.
+    pub fn to_aiger(&self) -> String{
+        let mut aiger = self.header("aag");
+        for i in 0..self.inputs.len(){
+            aiger.push_str(&format!("{}\n", 2 * (i + 1)));
+        }
+        for o in self.outputs.iter(){
+            aiger.push_str(&format!("{o}\n"));
+        }
+        for (k, (a, b)) in self.ands.iter().enumerate(){
+            aiger.push_str(&format!("{} {b} {a}\n", self.gate_lit(k)));
+        }
+        aiger.push_str(&self.symbols());
+
+        aiger
+    }
+
+    /// Writes the graph in the binary AIGER format (`aig`), with a symbol for every input.
+    pub fn to_aiger_binary(&self) -> Vec<u8>{
+        let mut aiger = self.header("aig").into_bytes();
+        for o in self.outputs.iter(){
+            aiger.extend(format!("{o}\n").bytes());
+        }
+        for (k, (a, b)) in self.ands.iter().enumerate(){
+            //gates store the differences lhs - rhs0 and rhs0 - rhs1 (with rhs0 >= rhs1)
+            let lhs = self.gate_lit(k);
+            push_varint(&mut aiger, lhs - b);
+            push_varint(&mut aiger, b - a);
+        }
+        aiger.extend(self.symbols().bytes());
+
+        aiger
+    }
+
+    /// Header line of both AIGER formats.
+    fn header(&self, format: &str) -> String{
+        format!("{format} {} {} 0 {} {}\n", self.inputs.len() + self.ands.len(), self.inputs.len(), self.outputs.len(), self.ands.len())
+    }
+
+    /// Symbol table of both AIGER formats.
+    fn symbols(&self) -> String{
+        self.inputs.iter().enumerate().map(|(i, s)| format!("i{i} {}\n", s.to_string())).collect()
+    }
+
+    /// Literal of the k'th and gate.
+    fn gate_lit(&self, k: usize) -> u32{
+        2 * (self.inputs.len() + k + 1) as u32
+    }
+
+    /// Value of the literal, given the value of every variable so far.
+    fn value(values: &[bool], lit: u32) -> bool{
+        values[(lit >> 1) as usize] != (lit & 1 == 1)
+    }
+
+    /// Gets the literal for the and of the two literals, adding a gate if there isn't one already.
+    fn and(&mut self, a: u32, b: u32) -> u32{
+        let (a, b) = if a <= b {(a, b)} else {(b, a)};
+        if a == FALSE || a ^ 1 == b{
+            return FALSE;
+        }
+        if a == FALSE ^ 1 || a == b{
+            return b;
+        }
+        if let Some(lit) = self.strash.get(&(a, b)){
+            return *lit;
+        }
+        let lit = self.gate_lit(self.ands.len());
+        self.ands.push((a, b));
+        self.strash.insert((a, b), lit);
+        lit
+    }
+
+    /// Gets the literal for the binary operator on the two literals.
+    fn binary(&mut self, op: Operator, a: u32, b: u32) -> u32{
+        match op{
+            Operator::AND => self.and(a, b),
+            Operator::OR => self.and(a ^ 1, b ^ 1) ^ 1,
+            Operator::CON => self.and(a, b ^ 1) ^ 1,
+            Operator::BICON => {
+                let only_a = self.and(a, b ^ 1);
+                let only_b = self.and(a ^ 1, b);
+                self.and(only_a ^ 1, only_b ^ 1)
+            },
+            Operator::NOT | Operator::UNI | Operator::EXI => unreachable!(),
+        }
+    }
+
+    /// Recursive helper for `Aig::add_output()`.
+    fn build(&mut self, node: &Node, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>) -> u32{
+        let (neg, lit) = match node{
+            Node::Operator { neg, op, left, right } => {
+                let left = self.build(left, grounding, varsubs);
+                let right = self.build(right, grounding, varsubs);
+                (neg, self.binary(*op, left, right))
+            },
+            Node::Quantifier { neg, op, vars, subexpr } => {
+                let (join, mut lit) = if op.is_uni() {(Operator::AND, FALSE ^ 1)} else {(Operator::OR, FALSE)};
+                if !grounding.domain().is_empty(){
+                    for_each_instance(vars, grounding.domain(), varsubs, |varsubs| {
+                        let instance = self.build(subexpr, grounding, varsubs);
+                        lit = self.binary(join, lit, instance);
+                    });
+                }
+                (neg, lit)
+            },
+            Node::Sentence { neg, sen } => match grounding.atom_index(&sen.substitute(varsubs)){
+                Some(i) => (neg, 2 * (i + 1) as u32),
+                None => (neg, FALSE),
+            },
+            Node::Constant(neg, b) => (neg, FALSE ^ (*b as u32)),
+        };
+        if neg.is_denied() {lit ^ 1} else {lit}
+    }
+}
+
+/// Writes the number as an AIGER varint: 7 bits at a time (lowest first), with the top bit set on every byte but the last.
+fn push_varint(bytes: &mut Vec<u8>, mut x: u32){
+    while x >= 0x80{
+        bytes.push((x & 0x7f) as u8 | 0x80);
+        x >>= 7;
+    }
+    bytes.push(x as u8);
+}
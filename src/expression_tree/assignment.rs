@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::Index;
+
+use crate::prelude::Sentence;
+use crate::ClawgicError;
+
+/// Truth values for a set of sentences (i.e. one row of a truth table).
+///
+/// Iterates in sentence order (by predicate name, then variables) no matter what order things were set in.
+/// ```
+/// use clawgic::prelude::*;
+/// let assignment = Assignment::new()
+///     .set("B", false)?
+///     .set("A", true)?
+///     .set("P(a)", true)?;
+/// assert_eq!(assignment.to_string(), "A=T, B=F, P(a)=T");
+/// let mut tree = ExpressionTree::new("A&~B")?;
+/// tree.set_tvals(&assignment);
+/// assert!(tree.evaluate()?);
+/// # Ok::<(), ClawgicError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Assignment{
+    /// The truth value of every sentence.
+    tvals: BTreeMap<Sentence, bool>,
+}
+
+impl Assignment{
+    /// Constructs an empty `Assignment`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Sets the truth value of the sentence written as a string (i.e. "A", "P(a, b)", "Rab"); returns self.
+    /// 
+    /// Fails (the same way as `Sentence::try_from()`) if the string isn't a single sentence.
+    pub fn set(self, sentence: &str, value: bool) -> Result<Self, ClawgicError>{
+        Ok(self.with(Sentence::try_from(sentence)?, value))
+    }
+
+    /// Sets the truth value of the sentence; returns self.
+    pub fn with(mut self, sentence: Sentence, value: bool) -> Self{
+        self.tvals.insert(sentence, value);
+        self
+    }
+
+    /// Sets the truth value of the sentence. Returns the old truth value if there was one.
+    pub fn insert(&mut self, sentence: Sentence, value: bool) -> Option<bool>{
+        self.tvals.insert(sentence, value)
+    }
+
+    /// Removes the sentence. Returns its truth value if it was there.
+    pub fn remove(&mut self, sentence: &Sentence) -> Option<bool>{
+        self.tvals.remove(sentence)
+    }
+
+    /// Gets the truth value of the sentence.
+    pub fn get(&self, sentence: &Sentence) -> Option<bool>{
+        self.tvals.get(sentence).copied()
+    }
+
+    /// Whether the sentence has a truth value.
+    pub fn contains(&self, sentence: &Sentence) -> bool{
+        self.tvals.contains_key(sentence)
+    }
+
+    /// Number of sentences.
+    pub fn len(&self) -> usize{
+        self.tvals.len()
+    }
+
+    /// Whether there are no sentences.
+    pub fn is_empty(&self) -> bool{
+        self.tvals.is_empty()
+    }
+
+    /// creates an iterator over every sentence and its truth value (in sentence order).
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, Sentence, bool>{
+        self.tvals.iter()
+    }
+
+    /// creates an iterator over every sentence (in order).
+    pub fn sentences(&self) -> std::collections::btree_map::Keys<'_, Sentence, bool>{
+        self.tvals.keys()
+    }
+}
+
+impl Display for Assignment{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for (sen, b) in self.tvals.iter(){
+            if !first{
+                write!(f, ", ")?;
+            }
+            first = false;
+            write!(f, "{}={}", sen.to_string(), if *b {"T"} else {"F"})?;
+        }
+        Ok(())
+    }
+}
+
+impl Index<&Sentence> for Assignment{
+    type Output = bool;
+
+    fn index(&self, index: &Sentence) -> &Self::Output {
+        &self.tvals[index]
+    }
+}
+
+impl FromIterator<(Sentence, bool)> for Assignment{
+    fn from_iter<T: IntoIterator<Item = (Sentence, bool)>>(iter: T) -> Self {
+        Self { tvals: iter.into_iter().collect() }
+    }
+}
+
+impl IntoIterator for Assignment{
+    type Item = (Sentence, bool);
+    type IntoIter = std::collections::btree_map::IntoIter<Sentence, bool>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tvals.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Assignment{
+    type Item = (&'a Sentence, &'a bool);
+    type IntoIter = std::collections::btree_map::Iter<'a, Sentence, bool>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tvals.iter()
+    }
+}
+
+/// Fails if any of the strings isn't a single sentence.
+impl TryFrom<&[(&str, bool)]> for Assignment{
+    type Error = ClawgicError;
+
+    fn try_from(value: &[(&str, bool)]) -> Result<Self, Self::Error> {
+        value.iter().try_fold(Self::new(), |a, (s, b)| a.set(s, *b))
+    }
+}
+
+/// Fails if any of the strings isn't a single sentence.
+impl<const N: usize> TryFrom<[(&str, bool) ; N]> for Assignment{
+    type Error = ClawgicError;
+
+    fn try_from(value: [(&str, bool) ; N]) -> Result<Self, Self::Error> {
+        Self::try_from(&value[..])
+    }
+}
+
+impl From<std::collections::HashMap<Sentence, bool>> for Assignment{
+    fn from(value: std::collections::HashMap<Sentence, bool>) -> Self {
+        value.into_iter().collect()
+    }
+}
@@ -0,0 +1,556 @@
+use std::collections::HashMap;
+
+use crate::budget::{Budget, Meter};
+use crate::expression_tree::grounding::{for_each_instance, Grounding};
+use crate::expression_tree::node::Node;
+use crate::prelude::{Assignment, ExpressionTree, ExpressionVar, Operator, Sentence, Zdd};
+use crate::utils::scale_count;
+use crate::ClawgicError;
+
+/// Index of the false terminal.
+const FALSE: usize = 0;
+/// Index of the true terminal.
+const TRUE: usize = 1;
+
+/// Decision node of a `Bdd`: goes to `high` if the atom is true and `low` if it's false.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct BddNode{
+    atom: usize,
+    low: usize,
+    high: usize,
+}
+
+/// How big a `Bdd` was at some point (see `Bdd::checkpoint()`).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Checkpoint{
+    atoms: usize,
+    nodes: usize,
+    root: usize,
+}
+
+/// A reduced ordered binary decision diagram (BDD) of an expression.
+///
+/// Equivalent expressions over the same atoms and order always give the same diagram, and satisfiability,
+/// tautology checks and model counting are cheap once it's built.
+/// How big the diagram gets depends heavily on the order of the atoms, so `ExpressionTree::to_bdd()` starts
+/// from `Bdd::best_order()`, and `sift()` can improve the order afterwards.
+/// ```
+/// use clawgic::prelude::*;
+/// let expr = ExpressionTree::new("((A&D)v(B&E))v(C&F)").unwrap();
+/// let sen = |s: &str| Sentence::try_from(s).unwrap();
+///
+/// let mut bdd = expr.to_bdd_with_order(&["A", "B", "C", "D", "E", "F"].map(sen));
+/// assert_eq!(bdd.size(), 14);
+/// bdd.sift();
+/// assert_eq!(bdd.size(), 6);
+/// assert_eq!(bdd.model_count(), expr.satisfy_count()[0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bdd{
+    /// Every ground sentence, by atom index.
+    atoms: Vec<Sentence>,
+    /// Atom index at each level (top first).
+    order: Vec<usize>,
+    /// Level of each atom.
+    levels: Vec<usize>,
+    /// Every node (including ones that are no longer reachable). The first two are the terminals.
+    nodes: Vec<BddNode>,
+    /// Where each node already is in `nodes` (so nothing gets stored twice).
+    unique: HashMap<BddNode, usize>,
+    /// Index of the root node.
+    root: usize,
+}
+
+impl Bdd{
+    /// Builds the diagram of the node with the given order (as atom indices, top first).
+    /// Every sentence in the node must have been added to the grounding first.
+    pub(crate) fn new(node: &Node, grounding: &Grounding, order: Vec<usize>) -> Self{
+        let mut bdd = Self::empty(grounding.atoms().clone(), order);
+        bdd.root = bdd.add(node, grounding);
+        bdd
+    }
+
+    /// Constructs a diagram with no nodes other than the terminals (its root is false).
+    pub(crate) fn empty(atoms: Vec<Sentence>, order: Vec<usize>) -> Self{
+        let mut levels = vec![0 ; atoms.len()];
+        for (level, atom) in order.iter().enumerate(){
+            levels[*atom] = level;
+        }
+        let terminal = |i| BddNode { atom: usize::MAX, low: i, high: i };
+        Self { atoms, order, levels, nodes: vec![terminal(FALSE), terminal(TRUE)], unique: HashMap::new(), root: FALSE }
+    }
+
+    /// Constructs a diagram over no atoms whose root is true (something to conjoin formulas onto).
+    pub(crate) fn tautology() -> Self{
+        let mut bdd = Self::empty(Vec::new(), Vec::new());
+        bdd.root = TRUE;
+        bdd
+    }
+
+    /// Adds more atoms below all of the ones already there (so every node stays the way it is).
+    pub(crate) fn add_atoms(&mut self, atoms: &[Sentence]){
+        for sen in atoms{
+            self.levels.push(self.order.len());
+            self.order.push(self.atoms.len());
+            self.atoms.push(sen.clone());
+        }
+    }
+
+    /// Gets what `roll_back()` needs to undo everything added from now on.
+    pub(crate) fn checkpoint(&self) -> Checkpoint{
+        Checkpoint { atoms: self.atoms.len(), nodes: self.nodes.len(), root: self.root }
+    }
+
+    /// Throws away every atom and node added since the checkpoint and puts the root back.
+    pub(crate) fn roll_back(&mut self, checkpoint: Checkpoint){
+        for node in self.nodes.drain(checkpoint.nodes..){
+            self.unique.remove(&node);
+        }
+        self.atoms.truncate(checkpoint.atoms);
+        self.levels.truncate(checkpoint.atoms);
+        self.order.retain(|a| *a < checkpoint.atoms);
+        self.root = checkpoint.root;
+    }
+
+    /// Adds the diagram of the node (sharing whatever is already there) and returns the index of its root.
+    pub(crate) fn add(&mut self, node: &Node, grounding: &Grounding) -> usize{
+        self.add_within(node, grounding, &mut Budget::new().meter()).unwrap()
+    }
+
+    /// `add()`, but gives up with `Interrupted` once the budget runs out (every operation and every new node is a step).
+    pub(crate) fn add_within(&mut self, node: &Node, grounding: &Grounding, meter: &mut Meter) -> Result<usize, ClawgicError>{
+        self.build(node, grounding, &mut HashMap::new(), &mut HashMap::new(), meter)
+    }
+
+    /// Conjoins the node onto the root (see `add_within()`).
+    pub(crate) fn conjoin_within(&mut self, node: &Node, grounding: &Grounding, meter: &mut Meter) -> Result<(), ClawgicError>{
+        let mut memo = HashMap::new();
+        let added = self.build(node, grounding, &mut HashMap::new(), &mut memo, meter)?;
+        self.root = self.apply_within(Operator::AND, self.root, added, &mut memo, meter)?;
+        Ok(())
+    }
+
+    /// Whether the node is true in every model of the root (see `add_within()`).
+    pub(crate) fn entails_within(&mut self, node: &Node, grounding: &Grounding, meter: &mut Meter) -> Result<bool, ClawgicError>{
+        let mut memo = HashMap::new();
+        let added = self.build(node, grounding, &mut HashMap::new(), &mut memo, meter)?;
+        Ok(self.apply_within(Operator::CON, self.root, added, &mut memo, meter)? == TRUE)
+    }
+
+    /// Works out a good static order for the atoms of the expression, for when nothing better is known.
+    ///
+    /// Starts from the order the atoms show up in and then repeatedly moves every atom towards the middle
+    /// of the subexpressions it's in (the FORCE heuristic), so atoms that get combined with each other end up close together.
+    pub fn best_order(expression: &ExpressionTree) -> Vec<Sentence>{
+        let grounding = expression.grounding();
+        Self::force_order(&expression.root, &grounding).into_iter().map(|i| grounding.atoms()[i].clone()).collect()
+    }
+
+    /// Atom indices (top first) for `best_order()`.
+    pub(crate) fn force_order(node: &Node, grounding: &Grounding) -> Vec<usize>{
+        let n = grounding.atoms().len();
+        let mut edges = Vec::new();
+        hyperedges(node, grounding, &mut HashMap::new(), &mut edges);
+        let span = |position: &[f64]| -> f64 {
+            edges.iter().map(|e: &Vec<usize>| {
+                let (lo, hi) = e.iter().fold((f64::MAX, f64::MIN), |(lo, hi), a| (lo.min(position[*a]), hi.max(position[*a])));
+                hi - lo
+            }).sum()
+        };
+
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut position: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let mut best = span(&position);
+        for _ in 0..n.max(1){
+            //move every atom to the average middle of the edges it's in
+            let centers: Vec<f64> = edges.iter().map(|e| e.iter().map(|a| position[*a]).sum::<f64>() / e.len() as f64).collect();
+            let mut totals = vec![(0.0, 0usize) ; n];
+            for (e, center) in edges.iter().zip(centers.iter()){
+                for a in e.iter(){
+                    totals[*a].0 += center;
+                    totals[*a].1 += 1;
+                }
+            }
+            let target: Vec<f64> = (0..n).map(|a| if totals[a].1 == 0 {position[a]} else {totals[a].0 / totals[a].1 as f64}).collect();
+            let mut next = order.clone();
+            next.sort_by(|a, b| target[*a].total_cmp(&target[*b]).then(position[*a].total_cmp(&position[*b])));
+            let mut next_position = vec![0.0 ; n];
+            for (level, atom) in next.iter().enumerate(){
+                next_position[*atom] = level as f64;
+            }
+            let next_span = span(&next_position);
+            if next_span >= best{
+                break;
+            }
+            best = next_span;
+            order = next;
+            position = next_position;
+        }
+
+        order
+    }
+
+    /// Gets every ground sentence the diagram is over, in no particular order.
+    pub fn atoms(&self) -> &Vec<Sentence>{
+        &self.atoms
+    }
+
+    /// Gets the order of the atoms (top first).
+    pub fn order(&self) -> Vec<&Sentence>{
+        self.order.iter().map(|i| &self.atoms[*i]).collect()
+    }
+
+    /// Number of decision nodes reachable from the root (so not counting the terminals).
+    pub fn size(&self) -> usize{
+        self.size_of(self.root)
+    }
+
+    /// `size()`, but for the node with the given index.
+    pub(crate) fn size_of(&self, root: usize) -> usize{
+        let mut seen = vec![false ; self.nodes.len()];
+        let mut stack = vec![root];
+        let mut size = 0;
+        while let Some(i) = stack.pop(){
+            if i <= TRUE || seen[i]{
+                continue;
+            }
+            seen[i] = true;
+            size += 1;
+            stack.push(self.nodes[i].low);
+            stack.push(self.nodes[i].high);
+        }
+
+        size
+    }
+
+    /// Whether any assignment satisfies the diagram.
+    pub fn is_satisfiable(&self) -> bool{
+        self.root != FALSE
+    }
+
+    /// Whether every assignment satisfies the diagram.
+    pub fn is_tautology(&self) -> bool{
+        self.root == TRUE
+    }
+
+    /// Whether the assignment satisfies the diagram. Atoms the assignment leaves out count as false.
+    pub fn evaluate(&self, assignment: &Assignment) -> bool{
+        let mut i = self.root;
+        while i > TRUE{
+            let node = self.nodes[i];
+            i = if assignment.get(&self.atoms[node.atom]) == Some(true) {node.high} else {node.low};
+        }
+        i == TRUE
+    }
+
+    /// Goes through every model of the diagram (as truth values by atom index), one at a time.
+    pub(crate) fn models(&self) -> Models<'_>{
+        Models { bdd: self, assignment: vec![false ; self.atoms.len()], stack: vec![(0, self.root, None)] }
+    }
+
+    /// Number of assignments of the atoms that satisfy the diagram.
+    ///
+    /// Saturates at `u128::MAX`.
+    pub fn model_count(&self) -> u128{
+        let mut counts = HashMap::new();
+        scale_count(self.count(self.root, &mut counts), self.level(self.root))
+    }
+
+    /// Converts the diagram into a `Zdd` (the family of the true atoms of every model) with the same order.
+    pub fn to_zdd(&self) -> Zdd{
+        Zdd::from(self)
+    }
+
+    /// Rebuilds the diagram with the given order (top first).
+    ///
+    /// Sentences in `order` that aren't atoms are ignored, and atoms left out go at the bottom in their current order.
+    pub fn reorder(&mut self, order: &[Sentence]) -> &mut Self{
+        let mut new_order: Vec<usize> = Vec::with_capacity(self.atoms.len());
+        for sen in order.iter(){
+            if let Some(i) = self.atoms.iter().position(|a| a == sen) && !new_order.contains(&i){
+                new_order.push(i);
+            }
+        }
+        for atom in self.order.iter(){
+            if !new_order.contains(atom){
+                new_order.push(*atom);
+            }
+        }
+        *self = self.rebuilt(new_order);
+        self
+    }
+
+    /// Improves the order by sifting: each atom (the ones with the most nodes first) gets tried at every level
+    /// and left wherever the diagram was smallest. Never makes the diagram bigger.
+    pub fn sift(&mut self) -> &mut Self{
+        let mut per_atom = vec![0usize ; self.atoms.len()];
+        let mut seen = vec![false ; self.nodes.len()];
+        let mut stack = vec![self.root];
+        while let Some(i) = stack.pop(){
+            if i <= TRUE || seen[i]{
+                continue;
+            }
+            seen[i] = true;
+            per_atom[self.nodes[i].atom] += 1;
+            stack.push(self.nodes[i].low);
+            stack.push(self.nodes[i].high);
+        }
+        let mut atoms: Vec<usize> = (0..self.atoms.len()).collect();
+        atoms.sort_by_key(|a| std::cmp::Reverse(per_atom[*a]));
+
+        let mut best_size = self.size();
+        for atom in atoms{
+            let mut order = self.order.clone();
+            let from = self.levels[atom];
+            order.remove(from);
+            let mut best = None;
+            for to in 0..=order.len(){
+                if to == from{
+                    continue;
+                }
+                let mut candidate = order.clone();
+                candidate.insert(to, atom);
+                let bdd = self.rebuilt(candidate);
+                if bdd.size() < best_size{
+                    best_size = bdd.size();
+                    best = Some(bdd);
+                }
+            }
+            if let Some(bdd) = best{
+                *self = bdd;
+            }
+        }
+
+        self
+    }
+
+    /// Builds the same function with a different order (as atom indices, top first).
+    fn rebuilt(&self, order: Vec<usize>) -> Self{
+        let mut bdd = Self::empty(self.atoms.clone(), order);
+        let mut translated = HashMap::new();
+        let mut memo = HashMap::new();
+        bdd.root = bdd.translate(self, self.root, &mut translated, &mut memo);
+        bdd
+    }
+
+    /// Recursive helper for `Bdd::rebuilt()`. Copies a node of `other` into this diagram (with this diagram's order).
+    fn translate(&mut self, other: &Self, i: usize, translated: &mut HashMap<usize, usize>, memo: &mut HashMap<(Operator, usize, usize), usize>) -> usize{
+        if i <= TRUE{
+            return i;
+        }
+        if let Some(t) = translated.get(&i){
+            return *t;
+        }
+        let node = other.nodes[i];
+        let low = self.translate(other, node.low, translated, memo);
+        let high = self.translate(other, node.high, translated, memo);
+        let var = self.mk(node.atom, FALSE, TRUE);
+        let not_var = self.mk(node.atom, TRUE, FALSE);
+        let when_true = self.apply(Operator::AND, var, high, memo);
+        let when_false = self.apply(Operator::AND, not_var, low, memo);
+        let result = self.apply(Operator::OR, when_true, when_false, memo);
+        translated.insert(i, result);
+
+        result
+    }
+
+    /// Gets the index of the root node.
+    pub(crate) fn root(&self) -> usize{
+        self.root
+    }
+
+    /// Sets the root node (by index).
+    pub(crate) fn set_root(&mut self, root: usize){
+        self.root = root;
+    }
+
+    /// Gets the atom indices in order (top first).
+    pub(crate) fn atom_order(&self) -> &Vec<usize>{
+        &self.order
+    }
+
+    /// Gets the (atom, low, high) of a decision node, or `None` for a terminal.
+    pub(crate) fn branches(&self, i: usize) -> Option<(usize, usize, usize)>{
+        match i <= TRUE{
+            true => None,
+            false => Some((self.nodes[i].atom, self.nodes[i].low, self.nodes[i].high)),
+        }
+    }
+
+    /// Level of the node (terminals are below every atom).
+    pub(crate) fn level(&self, i: usize) -> usize{
+        if i <= TRUE {self.atoms.len()} else {self.levels[self.nodes[i].atom]}
+    }
+
+    /// Gets the node testing the atom (unless both branches are the same).
+    pub(crate) fn mk(&mut self, atom: usize, low: usize, high: usize) -> usize{
+        if low == high{
+            return low;
+        }
+        let node = BddNode { atom, low, high };
+        if let Some(i) = self.unique.get(&node){
+            return *i;
+        }
+        self.nodes.push(node);
+        self.unique.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Combines two nodes with the binary operator.
+    fn apply(&mut self, op: Operator, a: usize, b: usize, memo: &mut HashMap<(Operator, usize, usize), usize>) -> usize{
+        if a <= TRUE && b <= TRUE{
+            return if op.execute_binary(a == TRUE, b == TRUE) {TRUE} else {FALSE};
+        }
+        match (op, a, b){
+            (Operator::AND, FALSE, _) | (Operator::AND, _, FALSE) => return FALSE,
+            (Operator::AND, TRUE, x) | (Operator::AND, x, TRUE) => return x,
+            (Operator::OR, TRUE, _) | (Operator::OR, _, TRUE) => return TRUE,
+            (Operator::OR, FALSE, x) | (Operator::OR, x, FALSE) => return x,
+            _ => (),
+        }
+        if let Some(i) = memo.get(&(op, a, b)){
+            return *i;
+        }
+
+        let level = self.level(a).min(self.level(b));
+        let atom = self.order[level];
+        let cofactors = |bdd: &Self, i: usize| if bdd.level(i) == level {(bdd.nodes[i].low, bdd.nodes[i].high)} else {(i, i)};
+        let (a_low, a_high) = cofactors(self, a);
+        let (b_low, b_high) = cofactors(self, b);
+        let low = self.apply(op, a_low, b_low, memo);
+        let high = self.apply(op, a_high, b_high, memo);
+        let result = self.mk(atom, low, high);
+        memo.insert((op, a, b), result);
+
+        result
+    }
+
+    /// `apply()`, but counts against the budget.
+    fn apply_within(&mut self, op: Operator, a: usize, b: usize, memo: &mut HashMap<(Operator, usize, usize), usize>, meter: &mut Meter) -> Result<usize, ClawgicError>{
+        let before = self.nodes.len();
+        let result = self.apply(op, a, b, memo);
+        meter.step((self.nodes.len() - before) as u64 + 1)?;
+        Ok(result)
+    }
+
+    /// Recursive helper for `Bdd::add_within()`.
+    fn build(&mut self, node: &Node, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>, memo: &mut HashMap<(Operator, usize, usize), usize>, meter: &mut Meter) -> Result<usize, ClawgicError>{
+        let (neg, result) = match node{
+            Node::Operator { neg, op, left, right } => {
+                let left = self.build(left, grounding, varsubs, memo, meter)?;
+                let right = self.build(right, grounding, varsubs, memo, meter)?;
+                (neg, self.apply_within(*op, left, right, memo, meter)?)
+            },
+            Node::Quantifier { neg, op, vars, subexpr } => {
+                let (join, start) = if op.is_uni() {(Operator::AND, TRUE)} else {(Operator::OR, FALSE)};
+                let mut result = Ok(start);
+                if !grounding.domain().is_empty(){
+                    for_each_instance(vars, grounding.domain(), varsubs, |varsubs| {
+                        if let Ok(so_far) = result{
+                            result = self.build(subexpr, grounding, varsubs, memo, meter)
+                                .and_then(|instance| self.apply_within(join, so_far, instance, memo, meter));
+                        }
+                    });
+                }
+                (neg, result?)
+            },
+            Node::Sentence { neg, sen } => match grounding.atom_index(&sen.substitute(varsubs)){
+                Some(i) => (neg, self.mk(i, FALSE, TRUE)),
+                None => (neg, FALSE),
+            },
+            Node::Constant(neg, b) => (neg, if *b {TRUE} else {FALSE}),
+        };
+        match neg.is_denied(){
+            //a <-> false is ~a
+            true => self.apply_within(Operator::BICON, result, FALSE, memo, meter),
+            false => Ok(result),
+        }
+    }
+
+    /// Recursive helper for `Bdd::model_count()`. Counts over the atoms at or below the node's level.
+    fn count(&self, i: usize, counts: &mut HashMap<usize, u128>) -> u128{
+        if i <= TRUE{
+            return i as u128;
+        }
+        if let Some(c) = counts.get(&i){
+            return *c;
+        }
+        let node = self.nodes[i];
+        let level = self.level(i);
+        let low = scale_count(self.count(node.low, counts), self.level(node.low) - level - 1);
+        let high = scale_count(self.count(node.high, counts), self.level(node.high) - level - 1);
+        let count = low.saturating_add(high);
+        counts.insert(i, count);
+
+        count
+    }
+}
+
+/// A branch of a `Bdd` that still has to be followed: the level it starts at, the node there,
+/// and the atom just above it along with its truth value (if there is one).
+type Branch = (usize, usize, Option<(usize, bool)>);
+
+/// Iterator over the models of a `Bdd` (see `Bdd::models()`).
+pub(crate) struct Models<'a>{
+    bdd: &'a Bdd,
+    /// Truth values (by atom index) along the path being followed.
+    assignment: Vec<bool>,
+    /// Branches that still have to be followed.
+    stack: Vec<Branch>,
+}
+
+impl Iterator for Models<'_>{
+    type Item = Vec<bool>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((level, i, above)) = self.stack.pop(){
+            if let Some((atom, b)) = above{
+                self.assignment[atom] = b;
+            }
+            if i == FALSE{
+                continue;
+            }
+            if level == self.bdd.order.len(){
+                return Some(self.assignment.clone());
+            }
+            //an atom the node skips over can be either
+            let atom = self.bdd.order[level];
+            let (low, high) = match self.bdd.level(i) == level{
+                true => (self.bdd.nodes[i].low, self.bdd.nodes[i].high),
+                false => (i, i),
+            };
+            self.stack.push((level + 1, high, Some((atom, true))));
+            self.stack.push((level + 1, low, Some((atom, false))));
+        }
+
+        None
+    }
+}
+
+/// Collects the atoms of every binary operator in the node (with quantifiers expanded) that has at least two of them.
+/// Returns the atoms of the node itself.
+fn hyperedges(node: &Node, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>, edges: &mut Vec<Vec<usize>>) -> Vec<usize>{
+    match node{
+        Node::Operator { left, right, .. } => {
+            let mut atoms = hyperedges(left, grounding, varsubs, edges);
+            atoms.extend(hyperedges(right, grounding, varsubs, edges));
+            atoms.sort_unstable();
+            atoms.dedup();
+            if atoms.len() > 1{
+                edges.push(atoms.clone());
+            }
+            atoms
+        },
+        Node::Quantifier { vars, subexpr, .. } => {
+            let mut atoms = Vec::new();
+            if !grounding.domain().is_empty(){
+                for_each_instance(vars, grounding.domain(), varsubs, |varsubs| atoms.extend(hyperedges(subexpr, grounding, varsubs, edges)));
+            }
+            atoms.sort_unstable();
+            atoms.dedup();
+            atoms
+        },
+        Node::Sentence { sen, .. } => grounding.atom_index(&sen.substitute(varsubs)).into_iter().collect(),
+        Node::Constant(..) => Vec::new(),
+    }
+}
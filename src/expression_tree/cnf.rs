@@ -0,0 +1,488 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use std::ops::Not;
+
+use crate::expression_tree::grounding::{for_each_instance, Grounding};
+use crate::expression_tree::minimize;
+use crate::expression_tree::node::Node;
+use crate::expression_tree::node::negation::Negation;
+use crate::prelude::{Assignment, ExpressionTree, ExpressionVar, Operator, Sentence};
+
+/// A sentence or its denial.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal{
+    sen: Sentence,
+    /// Whether the sentence is plain (not denied).
+    positive: bool,
+}
+
+impl Literal{
+    /// Constructs the literal for the sentence (denied if `positive` is false).
+    pub fn new(sen: Sentence, positive: bool) -> Self{
+        Self { sen, positive }
+    }
+
+    /// Gets the sentence.
+    pub fn sentence(&self) -> &Sentence{
+        &self.sen
+    }
+
+    /// Whether the sentence is plain (not denied).
+    pub fn is_positive(&self) -> bool{
+        self.positive
+    }
+
+    /// Gets the literal with the opposite sign.
+    pub fn negated(&self) -> Self{
+        Self { sen: self.sen.clone(), positive: !self.positive }
+    }
+
+    /// Gets the truth value of the literal under the assignment (if its sentence has one).
+    pub fn evaluate(&self, assignment: &Assignment) -> Option<bool>{
+        assignment.get(&self.sen).map(|b| b == self.positive)
+    }
+
+    /// Makes the node for the literal.
+    fn to_node(&self) -> Node{
+        Node::Sentence { neg: Negation::new(!self.positive as u32), sen: self.sen.clone() }
+    }
+}
+
+impl Not for Literal{
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        self.positive = !self.positive;
+        self
+    }
+}
+
+impl From<Sentence> for Literal{
+    fn from(sen: Sentence) -> Self {
+        Self::new(sen, true)
+    }
+}
+
+impl Display for Literal{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", if self.positive {""} else {"~"}, self.sen.to_string())
+    }
+}
+
+/// A disjunction of literals, kept as a (sorted) set. The empty clause is false.
+/// ```
+/// use clawgic::prelude::*;
+/// let lit = |s: &str, b: bool| Literal::new(Sentence::try_from(s).unwrap(), b);
+/// let first: Clause = [lit("A", true), lit("B", true)].into_iter().collect();
+/// let second: Clause = [lit("A", false), lit("C", true)].into_iter().collect();
+/// let resolvent = first.resolve(&second, lit("A", true).sentence()).unwrap();
+/// assert_eq!(resolvent.to_string(), "B v C");
+/// assert!(resolvent.subsumes(&[lit("B", true), lit("C", true), lit("D", false)].into_iter().collect()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Clause{
+    literals: BTreeSet<Literal>,
+}
+
+impl Clause{
+    /// Constructs the empty clause.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Adds the literal; returns a mutable reference.
+    pub fn add(&mut self, literal: Literal) -> &mut Self{
+        self.literals.insert(literal);
+        self
+    }
+
+    /// creates an iterator over every literal (in order).
+    pub fn literals(&self) -> std::collections::btree_set::Iter<'_, Literal>{
+        self.literals.iter()
+    }
+
+    /// Number of literals.
+    pub fn len(&self) -> usize{
+        self.literals.len()
+    }
+
+    /// Whether there are no literals (the clause is false).
+    pub fn is_empty(&self) -> bool{
+        self.literals.is_empty()
+    }
+
+    /// Gets the only literal, if there is exactly one.
+    pub fn unit(&self) -> Option<&Literal>{
+        if self.literals.len() == 1 {self.literals.first()} else {None}
+    }
+
+    /// Whether the clause has the literal.
+    pub fn contains(&self, literal: &Literal) -> bool{
+        self.literals.contains(literal)
+    }
+
+    /// Whether the clause has some sentence both plain and denied (so it's always true).
+    pub fn is_tautology(&self) -> bool{
+        self.literals.iter().any(|l| l.positive && self.literals.contains(&l.negated()))
+    }
+
+    /// Whether every literal of this clause is in the other one (so this clause being true makes the other one true).
+    pub fn subsumes(&self, other: &Self) -> bool{
+        self.literals.is_subset(&other.literals)
+    }
+
+    /// Resolves the two clauses on the sentence: if one has it plain and the other has it denied,
+    /// returns every other literal of both.
+    pub fn resolve(&self, other: &Self, sentence: &Sentence) -> Option<Self>{
+        let plain = Literal::new(sentence.clone(), true);
+        let denied = plain.negated();
+        let complementary = (self.contains(&plain) && other.contains(&denied)) || (self.contains(&denied) && other.contains(&plain));
+        if !complementary{
+            return None;
+        }
+
+        Some(self.literals.iter().chain(other.literals.iter())
+            .filter(|l| l.sen != *sentence)
+            .cloned()
+            .collect())
+    }
+
+    /// Gets every resolvent of the two clauses that isn't a tautology.
+    pub fn resolvents(&self, other: &Self) -> Vec<Self>{
+        self.literals.iter()
+            .filter_map(|l| self.resolve(other, &l.sen))
+            .filter(|r| !r.is_tautology())
+            .collect()
+    }
+
+    /// Whether some literal is true under the assignment.
+    pub fn is_satisfied_by(&self, assignment: &Assignment) -> bool{
+        self.literals.iter().any(|l| l.evaluate(assignment) == Some(true))
+    }
+
+    /// Makes the node for the clause.
+    fn to_node(&self) -> Node{
+        minimize::join(self.literals.iter().map(Literal::to_node), Operator::OR).unwrap_or(Node::Constant(Negation::default(), false))
+    }
+}
+
+impl FromIterator<Literal> for Clause{
+    fn from_iter<T: IntoIterator<Item = Literal>>(iter: T) -> Self {
+        Self { literals: iter.into_iter().collect() }
+    }
+}
+
+impl<'a> IntoIterator for &'a Clause{
+    type Item = &'a Literal;
+    type IntoIter = std::collections::btree_set::Iter<'a, Literal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.literals.iter()
+    }
+}
+
+impl Display for Clause{
+    /// Literals joined by ` v ` (or `FALSE` for the empty clause).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty(){
+            return write!(f, "FALSE");
+        }
+        let literals: Vec<String> = self.literals.iter().map(|l| l.to_string()).collect();
+        write!(f, "{}", literals.join(" v "))
+    }
+}
+
+/// An expression in conjunctive normal form: a conjunction of `Clause`s. The empty `Cnf` is true.
+///
+/// Made from any expression with `ExpressionTree::to_cnf()` (quantifiers are expanded the same way
+/// `ExpressionTree::satisfy_count()` does it), and turned back into one with `ExpressionTree::from()`.
+/// ```
+/// use clawgic::prelude::*;
+/// let cnf = ExpressionTree::new("A<->B").unwrap().to_cnf();
+/// assert_eq!(cnf.to_string(), "(~A v B) & (A v ~B)");
+/// assert!(ExpressionTree::from(&cnf).log_eq(&ExpressionTree::new("A<->B").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Cnf{
+    clauses: Vec<Clause>,
+}
+
+impl Cnf{
+    /// Constructs a `Cnf` with no clauses.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Adds the clause; returns a mutable reference.
+    pub fn add_clause(&mut self, clause: Clause) -> &mut Self{
+        self.clauses.push(clause);
+        self
+    }
+
+    /// Gets every clause, in order.
+    pub fn clauses(&self) -> &Vec<Clause>{
+        &self.clauses
+    }
+
+    /// creates an iterator over every clause (in order).
+    pub fn iter(&self) -> std::slice::Iter<'_, Clause>{
+        self.clauses.iter()
+    }
+
+    /// Number of clauses.
+    pub fn len(&self) -> usize{
+        self.clauses.len()
+    }
+
+    /// Whether there are no clauses (the `Cnf` is true).
+    pub fn is_empty(&self) -> bool{
+        self.clauses.is_empty()
+    }
+
+    /// Whether one of the clauses is empty (the `Cnf` is false).
+    pub fn has_empty_clause(&self) -> bool{
+        self.clauses.iter().any(Clause::is_empty)
+    }
+
+    /// Gets every sentence in any clause (in order).
+    pub fn sentences(&self) -> Vec<Sentence>{
+        let found: BTreeSet<&Sentence> = self.clauses.iter().flat_map(|c| c.literals().map(Literal::sentence)).collect();
+        found.into_iter().cloned().collect()
+    }
+
+    /// Whether every clause is satisfied by the assignment.
+    pub fn is_satisfied_by(&self, assignment: &Assignment) -> bool{
+        self.clauses.iter().all(|c| c.is_satisfied_by(assignment))
+    }
+
+    /// Removes every tautology, duplicate and subsumed clause (keeping the first of any duplicates); returns a mutable reference.
+    pub fn simplify(&mut self) -> &mut Self{
+        let clauses = std::mem::take(&mut self.clauses);
+        for clause in clauses{
+            if clause.is_tautology() || self.clauses.iter().any(|c| c.subsumes(&clause)){
+                continue;
+            }
+            self.clauses.retain(|c| !clause.subsumes(c));
+            self.clauses.push(clause);
+        }
+        self
+    }
+
+    /// Repeatedly sets the literal of every unit clause (a clause with only one literal that isn't false yet)
+    /// until there are none left, starting from the assumptions. Stops early if a clause becomes false.
+    ///
+    /// Every forced literal remembers which clause forced it and which literals made the rest of that clause false,
+    /// so the result is also the implication graph.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let cnf = ExpressionTree::new("(A->B)&((B->C)&(~CvD))").unwrap().to_cnf();
+    /// let propagation = cnf.unit_propagate(&Assignment::new().set("A", true).unwrap());
+    /// let forced: Vec<String> = propagation.literals().map(|l| l.to_string()).collect();
+    /// assert_eq!(forced, vec!["B", "C", "D"]);
+    /// let edges: Vec<String> = propagation.edges().map(|(from, to, _)| format!("{from} -> {to}")).collect();
+    /// assert_eq!(edges, vec!["A -> B", "B -> C", "C -> D"]);
+    /// ```
+    pub fn unit_propagate(&self, assumptions: &Assignment) -> Propagation{
+        let mut propagation = Propagation { forced: Vec::new(), conflict: None, assignment: assumptions.clone() };
+        let mut changed = true;
+        while changed{
+            changed = false;
+            for (i, clause) in self.clauses.iter().enumerate(){
+                let mut open = clause.literals().filter(|l| l.evaluate(&propagation.assignment) != Some(false));
+                let (first, second) = (open.next(), open.next());
+                let literal = match (first, second){
+                    (None, _) => {
+                        propagation.conflict = Some(i);
+                        return propagation;
+                    },
+                    (Some(l), None) if l.evaluate(&propagation.assignment).is_none() => l,
+                    _ => continue,
+                };
+                propagation.assignment.insert(literal.sentence().clone(), literal.is_positive());
+                propagation.forced.push(Implication {
+                    literal: literal.clone(),
+                    clause: i,
+                    antecedents: clause.literals().filter(|l| *l != literal).map(Literal::negated).collect(),
+                });
+                changed = true;
+            }
+        }
+
+        propagation
+    }
+
+    /// Converts the node into simplified clauses. Every sentence in it must have been added to the grounding first.
+    pub(crate) fn from_node(node: &Node, grounding: &Grounding) -> Self{
+        let mut cnf = Self { clauses: clauses(node, true, grounding, &mut HashMap::new()) };
+        cnf.simplify();
+        cnf
+    }
+}
+
+impl<'a> IntoIterator for &'a Cnf{
+    type Item = &'a Clause;
+    type IntoIter = std::slice::Iter<'a, Clause>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.clauses.iter()
+    }
+}
+
+impl FromIterator<Clause> for Cnf{
+    fn from_iter<T: IntoIterator<Item = Clause>>(iter: T) -> Self {
+        Self { clauses: iter.into_iter().collect() }
+    }
+}
+
+impl Display for Cnf{
+    /// Clauses joined by ` & ` (or `TRUE` if there are none), with parentheses around any with several literals.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty(){
+            return write!(f, "TRUE");
+        }
+        let clauses: Vec<String> = self.clauses.iter()
+            .map(|c| if c.len() > 1 && self.clauses.len() > 1 {format!("({c})")} else {c.to_string()})
+            .collect();
+        write!(f, "{}", clauses.join(" & "))
+    }
+}
+
+impl From<&Cnf> for ExpressionTree{
+    fn from(cnf: &Cnf) -> Self {
+        let root = minimize::join(cnf.clauses.iter().map(Clause::to_node), Operator::AND).unwrap_or(Node::Constant(Negation::default(), true));
+        ExpressionTree::from(root)
+    }
+}
+
+impl From<&ExpressionTree> for Cnf{
+    fn from(tree: &ExpressionTree) -> Self {
+        tree.to_cnf()
+    }
+}
+
+/// A literal that `Cnf::unit_propagate()` forced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Implication{
+    literal: Literal,
+    /// Index of the clause that forced it.
+    clause: usize,
+    /// The (true) literals that made every other literal of the clause false.
+    antecedents: Vec<Literal>,
+}
+
+impl Implication{
+    /// Gets the literal that was forced.
+    pub fn literal(&self) -> &Literal{
+        &self.literal
+    }
+
+    /// Gets the index of the clause that forced it.
+    pub fn clause(&self) -> usize{
+        self.clause
+    }
+
+    /// Gets the (true) literals that made every other literal of the clause false.
+    pub fn antecedents(&self) -> &Vec<Literal>{
+        &self.antecedents
+    }
+}
+
+/// Everything `Cnf::unit_propagate()` found out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Propagation{
+    /// Every forced literal, in the order it was forced.
+    forced: Vec<Implication>,
+    /// Index of the clause that became false, if one did.
+    conflict: Option<usize>,
+    /// The assumptions along with every forced literal.
+    assignment: Assignment,
+}
+
+impl Propagation{
+    /// Gets every forced literal (and why), in the order they were forced.
+    pub fn forced(&self) -> &Vec<Implication>{
+        &self.forced
+    }
+
+    /// creates an iterator over every forced literal (in order).
+    pub fn literals(&self) -> impl Iterator<Item = &Literal>{
+        self.forced.iter().map(Implication::literal)
+    }
+
+    /// creates an iterator over every edge of the implication graph: each antecedent, the literal it helped force,
+    /// and the index of the clause that did it.
+    pub fn edges(&self) -> impl Iterator<Item = (&Literal, &Literal, usize)>{
+        self.forced.iter().flat_map(|imp| imp.antecedents.iter().map(move |a| (a, &imp.literal, imp.clause)))
+    }
+
+    /// Gets the index of the clause that became false, if one did (the assumptions can't all be true).
+    pub fn conflict(&self) -> Option<usize>{
+        self.conflict
+    }
+
+    /// Whether a clause became false.
+    pub fn is_conflict(&self) -> bool{
+        self.conflict.is_some()
+    }
+
+    /// Gets the assumptions along with every forced literal.
+    pub fn assignment(&self) -> &Assignment{
+        &self.assignment
+    }
+}
+
+/// Gets clauses equivalent to the node (or its denial, if `positive` is false) by distributing disjunctions over conjunctions.
+fn clauses(node: &Node, positive: bool, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>) -> Vec<Clause>{
+    let positive = positive != node.negation().is_denied();
+    match node{
+        Node::Operator { op, left, right, .. } => {
+            let mut side = |n: &Node, p: bool| clauses(n, p, grounding, varsubs);
+            match (op, positive){
+                (Operator::AND, true) => [side(left, true), side(right, true)].concat(),
+                (Operator::AND, false) => distribute(side(left, false), side(right, false)),
+                (Operator::OR, true) => distribute(side(left, true), side(right, true)),
+                (Operator::OR, false) => [side(left, false), side(right, false)].concat(),
+                (Operator::CON, true) => distribute(side(left, false), side(right, true)),
+                (Operator::CON, false) => [side(left, true), side(right, false)].concat(),
+                //(~L v R) & (L v ~R), or (L v R) & (~L v ~R) when it's denied
+                (Operator::BICON, _) => {
+                    let first = distribute(side(left, false), side(right, positive));
+                    let second = distribute(side(left, true), side(right, !positive));
+                    [first, second].concat()
+                },
+                (Operator::NOT | Operator::UNI | Operator::EXI, _) => unreachable!(),
+            }
+        },
+        Node::Quantifier { op, vars, subexpr, .. } => {
+            //a universal is a conjunction of every instance, unless it's denied (then it's a disjunction of denied instances)
+            let conjunction = op.is_uni() == positive;
+            let domain = grounding.domain();
+            if domain.is_empty(){
+                return if conjunction {Vec::new()} else {vec![Clause::new()]};
+            }
+            let mut instances = Vec::new();
+            for_each_instance(vars, domain, varsubs, |varsubs| instances.push(clauses(subexpr, positive, grounding, varsubs)));
+            if conjunction{
+                instances.concat()
+            }else{
+                instances.into_iter().reduce(distribute).unwrap_or_default()
+            }
+        },
+        Node::Sentence { sen, .. } => vec![Clause::from_iter([Literal::new(sen.substitute(varsubs), positive)])],
+        Node::Constant(_, b) => if *b == positive {Vec::new()} else {vec![Clause::new()]},
+    }
+}
+
+/// Gets clauses for the disjunction of two sets of clauses (every clause of one or'd with every clause of the other).
+fn distribute(left: Vec<Clause>, right: Vec<Clause>) -> Vec<Clause>{
+    let mut result = Vec::with_capacity(left.len() * right.len());
+    for l in left.iter(){
+        for r in right.iter(){
+            let joined: Clause = l.literals().chain(r.literals()).cloned().collect();
+            if !joined.is_tautology(){
+                result.push(joined);
+            }
+        }
+    }
+    result
+}
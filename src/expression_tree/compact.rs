@@ -0,0 +1,176 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::expression_tree::node::Node;
+use crate::expression_tree::node::negation::Negation;
+use crate::prelude::{ExpressionVar, Predicate, Sentence};
+
+/// Rebuilds the node so that every repeated subtree is one shared allocation (a DAG instead of a tree).
+pub(crate) fn share(node: &Node, table: &mut HashMap<Node, Arc<Node>>) -> Arc<Node>{
+    let rebuilt = match node{
+        Node::Operator { neg, op, left, right } => Node::Operator {
+            neg: *neg,
+            op: *op,
+            left: share(left, table),
+            right: share(right, table),
+        },
+        Node::Quantifier { neg, op, vars, subexpr } => Node::Quantifier {
+            neg: *neg,
+            op: *op,
+            vars: vars.clone(),
+            subexpr: share(subexpr, table),
+        },
+        Node::Sentence { .. } | Node::Constant(..) => node.clone(),
+    };
+    if let Some(shared) = table.get(&rebuilt){
+        return shared.clone();
+    }
+    let shared = Arc::new(rebuilt.clone());
+    table.insert(rebuilt, shared.clone());
+
+    shared
+}
+
+/// Pulls repeated subexpressions out of the node into definitions.
+///
+/// Repeatedly picks the compound subexpression (ignoring leading tildes) that saves the most nodes by being
+/// replaced with a fresh sentence everywhere, until none of them saves anything. New sentences are named
+/// after `prefix` and skip any name in `taken`.
+///
+/// Returns the rewritten node and every (sentence, definition) pair, ordered so that definitions only use sentences defined before them.
+pub(crate) fn definitions(root: &Node, prefix: &str, taken: &HashSet<String>) -> (Node, Vec<(Sentence, Node)>){
+    let mut root = root.clone();
+    let mut defs: Vec<(Sentence, Node)> = Vec::new();
+    let mut next = 0;
+    loop{
+        let mut counts: HashMap<Node, (usize, usize)> = HashMap::new();
+        count(&root, &mut Vec::new(), &mut counts);
+        for (_, def) in defs.iter(){
+            //the definition itself doesn't count as a copy
+            count(def, &mut Vec::new(), &mut counts);
+            if let Some((k, _)) = counts.get_mut(def){
+                *k -= 1;
+            }
+        }
+        //replacing k copies of a subexpression of size s with a sentence saves k * s nodes, but costs k for the sentences
+        //and s + 2 for the definition (the sentence and a biconditional)
+        let best = counts.into_iter()
+            .filter(|(_, (k, s))| k * s > k + s + 2)
+            .max_by_key(|(node, (k, s))| (k * s - k - s - 2, *s, std::cmp::Reverse(node.clone())));
+        let Some((target, _)) = best else {break};
+
+        let name = loop{
+            let name = format!("{prefix}{next}");
+            next += 1;
+            if !taken.contains(&name){
+                break name;
+            }
+        };
+        let sen = Predicate::new(&name, 0).unwrap().inst(&vec![]).unwrap();
+        replace(&mut root, &target, &sen, &mut Vec::new());
+        for (_, def) in defs.iter_mut(){
+            replace_children(def, &target, &sen, &mut Vec::new());
+        }
+        defs.push((sen, target));
+    }
+
+    (root, dependency_order(defs))
+}
+
+/// The node without its leading tildes.
+fn strip(node: &Node) -> Node{
+    let mut stripped = node.clone();
+    match &mut stripped{
+        Node::Operator { neg, .. } | Node::Quantifier { neg, .. } | Node::Sentence { neg, .. } | Node::Constant(neg, _) => *neg = Negation::default(),
+    }
+    stripped
+}
+
+/// Counts every compound subexpression (without leading tildes) that doesn't use a variable bound by a quantifier
+/// above it, along with its size. Returns the size of the node and every variable its sentences use.
+fn count(node: &Node, scope: &mut Vec<ExpressionVar>, counts: &mut HashMap<Node, (usize, usize)>) -> (usize, BTreeSet<ExpressionVar>){
+    let (size, vars) = match node{
+        Node::Operator { left, right, .. } => {
+            let (left_size, mut vars) = count(left, scope, counts);
+            let (right_size, right_vars) = count(right, scope, counts);
+            vars.extend(right_vars);
+            (1 + left_size + right_size, vars)
+        },
+        Node::Quantifier { vars: bound, subexpr, .. } => {
+            scope.extend(bound.iter().cloned());
+            let (size, vars) = count(subexpr, scope, counts);
+            scope.truncate(scope.len() - bound.len());
+            (1 + size, vars)
+        },
+        Node::Sentence { sen, .. } => return (1, sen.vars().iter().cloned().collect()),
+        Node::Constant(..) => return (1, BTreeSet::new()),
+    };
+    if !scope.iter().any(|v| vars.contains(v)){
+        counts.entry(strip(node)).or_insert((0, size)).0 += 1;
+    }
+
+    (size, vars)
+}
+
+/// Replaces every copy of the target (ignoring leading tildes) that doesn't use a variable bound above it with the sentence.
+fn replace(node: &mut Node, target: &Node, sen: &Sentence, scope: &mut Vec<ExpressionVar>){
+    if !node.is_sentence() && !node.is_constant() && strip(node) == *target && !uses_any(target, scope){
+        *node = Node::Sentence { neg: node.negation(), sen: sen.clone() };
+        return;
+    }
+    replace_children(node, target, sen, scope);
+}
+
+/// `replace()`, but only below the node.
+fn replace_children(node: &mut Node, target: &Node, sen: &Sentence, scope: &mut Vec<ExpressionVar>){
+    match node{
+        Node::Operator { left, right, .. } => {
+            replace(Arc::make_mut(left), target, sen, scope);
+            replace(Arc::make_mut(right), target, sen, scope);
+        },
+        Node::Quantifier { vars, subexpr, .. } => {
+            scope.extend(vars.iter().cloned());
+            replace(Arc::make_mut(subexpr), target, sen, scope);
+            scope.truncate(scope.len() - vars.len());
+        },
+        Node::Sentence { .. } | Node::Constant(..) => (),
+    }
+}
+
+/// Whether any sentence in the node uses one of the variables.
+fn uses_any(node: &Node, vars: &[ExpressionVar]) -> bool{
+    match node{
+        Node::Operator { left, right, .. } => uses_any(left, vars) || uses_any(right, vars),
+        Node::Quantifier { subexpr, .. } => uses_any(subexpr, vars),
+        Node::Sentence { sen, .. } => sen.vars().iter().any(|v| vars.contains(v)),
+        Node::Constant(..) => false,
+    }
+}
+
+/// Whether the sentence shows up anywhere in the node.
+fn mentions(node: &Node, sen: &Sentence) -> bool{
+    match node{
+        Node::Operator { left, right, .. } => mentions(left, sen) || mentions(right, sen),
+        Node::Quantifier { subexpr, .. } => mentions(subexpr, sen),
+        Node::Sentence { sen: s, .. } => s == sen,
+        Node::Constant(..) => false,
+    }
+}
+
+/// Orders the definitions so each one only uses sentences defined before it.
+fn dependency_order(defs: Vec<(Sentence, Node)>) -> Vec<(Sentence, Node)>{
+    let mut placed = vec![false ; defs.len()];
+    let mut order = Vec::with_capacity(defs.len());
+    while order.len() < defs.len(){
+        for i in 0..defs.len(){
+            let ready = !placed[i] && (0..defs.len()).all(|j| placed[j] || j == i || !mentions(&defs[i].1, &defs[j].0));
+            if ready{
+                placed[i] = true;
+                order.push(i);
+            }
+        }
+    }
+    let mut defs: Vec<Option<(Sentence, Node)>> = defs.into_iter().map(Some).collect();
+
+    order.into_iter().map(|i| defs[i].take().unwrap()).collect()
+}
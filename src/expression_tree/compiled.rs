@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use crate::expression_tree::grounding::Grounding;
+use crate::expression_tree::node::Node;
+use crate::prelude::{ExpressionVar, Operator};
+
+/// One instruction of a `Compiled` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instr{
+    /// Push the column of the i'th atom.
+    Atom(usize),
+    /// Push a column of all trues or all falses.
+    Const(bool),
+    /// Flip the top of the stack.
+    Not,
+    /// Pop two columns and push the result of the operator on them.
+    Binary(Operator),
+}
+
+/// An expression flattened into a little stack machine (in postfix order) over the atoms of a `Grounding`,
+/// with every quantifier already expanded over the domain.
+///
+/// Evaluates 64 rows of the truth table at once: each `u64` is a column where bit `j` is the value in row `64 * block + j`,
+/// and the i'th atom is true in row `r` iff bit `i` of `r` is set (the same order `grounding::next_assignment()` counts in).
+#[derive(Debug, Clone)]
+pub(crate) struct Compiled{
+    /// The instructions in postfix order.
+    instrs: Vec<Instr>,
+    /// Scratch space for evaluation.
+    stack: Vec<u64>,
+}
+
+impl Compiled{
+    /// Compiles the node. Every sentence in it must have been added to the grounding first.
+    pub(crate) fn new(node: &Node, grounding: &Grounding) -> Self{
+        let mut instrs = Vec::new();
+        compile(node, grounding, &mut HashMap::new(), &mut instrs);
+        Self { instrs, stack: Vec::new() }
+    }
+
+    /// Number of 64 row blocks in a truth table with `atoms` atoms.
+    ///
+    /// Stops at `u128::MAX` past 134 atoms (which is far more blocks than could ever be gotten through anyway).
+    pub(crate) fn block_count(atoms: usize) -> u128{
+        if atoms <= 6 {1} else {1u128.checked_shl((atoms - 6) as u32).unwrap_or(u128::MAX)}
+    }
+
+    /// Mask of the rows of a block that actually exist in a truth table with `atoms` atoms.
+    pub(crate) fn row_mask(atoms: usize) -> u64{
+        if atoms >= 6 {u64::MAX} else {(1 << (1 << atoms)) - 1}
+    }
+
+    /// Gets the assignment of the given row (bit `lane` of `block`) in a truth table with `atoms` atoms.
+    pub(crate) fn row(block: u128, lane: u32, atoms: usize) -> Vec<bool>{
+        (0..atoms).map(|i| if i < 6 {(lane >> i) & 1 == 1} else {atom_column(i, block) != 0}).collect()
+    }
+
+    /// Evaluates 64 rows of the truth table at once (see the struct docs for the layout).
+    pub(crate) fn evaluate_block(&mut self, block: u128) -> u64{
+        self.stack.clear();
+        for instr in self.instrs.iter(){
+            let column = match instr{
+                Instr::Atom(i) => atom_column(*i, block),
+                Instr::Const(b) => if *b {u64::MAX} else {0},
+                Instr::Not => !self.stack.pop().unwrap(),
+                Instr::Binary(op) => {
+                    let right = self.stack.pop().unwrap();
+                    let left = self.stack.pop().unwrap();
+                    match op{
+                        Operator::AND => left & right,
+                        Operator::OR => left | right,
+                        Operator::CON => !left | right,
+                        Operator::BICON => !(left ^ right),
+                        Operator::NOT | Operator::UNI | Operator::EXI => unreachable!(),
+                    }
+                }
+            };
+            self.stack.push(column);
+        }
+
+        self.stack.pop().unwrap()
+    }
+}
+
+/// Gets the column of the i'th atom in the given block.
+pub(crate) fn atom_column(i: usize, block: u128) -> u64{
+    //patterns for the atoms that change within a block
+    const LOW: [u64 ; 6] = [
+        0xAAAA_AAAA_AAAA_AAAA,
+        0xCCCC_CCCC_CCCC_CCCC,
+        0xF0F0_F0F0_F0F0_F0F0,
+        0xFF00_FF00_FF00_FF00,
+        0xFFFF_0000_FFFF_0000,
+        0xFFFF_FFFF_0000_0000,
+    ];
+    if i < 6{
+        LOW[i]
+    }else if i - 6 < 128 && (block >> (i - 6)) & 1 == 1{
+        u64::MAX
+    }else{
+        0
+    }
+}
+
+/// Recursive helper function for `Compiled::new()`.
+fn compile(node: &Node, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>, instrs: &mut Vec<Instr>){
+    let neg = match node{
+        Node::Operator { neg, op, left, right } => {
+            compile(left, grounding, varsubs, instrs);
+            compile(right, grounding, varsubs, instrs);
+            instrs.push(Instr::Binary(*op));
+            neg
+        },
+        Node::Quantifier { neg, op, vars, subexpr } => {
+            let domain = grounding.domain();
+            let join = if op.is_uni() {Operator::AND} else {Operator::OR};
+            if domain.is_empty(){
+                instrs.push(Instr::Const(op.is_uni()));
+            }else{
+                //go through every way of filling in the bound vars
+                let mut indices = vec![0 ; vars.len()];
+                let mut first = true;
+                loop{
+                    for (v, i) in vars.iter().zip(indices.iter()){
+                        varsubs.insert(v.clone(), domain[*i].clone());
+                    }
+                    compile(subexpr, grounding, varsubs, instrs);
+                    if !first{
+                        instrs.push(Instr::Binary(join));
+                    }
+                    first = false;
+
+                    let mut i = 0;
+                    while i < indices.len(){
+                        indices[i] += 1;
+                        if indices[i] < domain.len(){
+                            break;
+                        }
+                        indices[i] = 0;
+                        i += 1;
+                    }
+                    if i == indices.len(){
+                        break;
+                    }
+                }
+                for v in vars.iter(){
+                    varsubs.remove(v);
+                }
+            }
+            neg
+        },
+        Node::Sentence { neg, sen } => {
+            match grounding.atom_index(&sen.substitute(varsubs)){
+                Some(i) => instrs.push(Instr::Atom(i)),
+                None => instrs.push(Instr::Const(false)),
+            }
+            neg
+        },
+        Node::Constant(neg, b) => {
+            instrs.push(Instr::Const(*b));
+            neg
+        },
+    };
+    if neg.is_denied(){
+        instrs.push(Instr::Not);
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+use crate::expression_tree::grounding::{for_each_instance, Grounding};
+use crate::expression_tree::node::Node;
+use crate::prelude::{Assignment, ExpressionVar, Operator, Sentence};
+use crate::utils::scale_count;
+
+/// A ground expression in negation normal form over atom indices. Only used while compiling.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Formula{
+    Const(bool),
+    /// The i'th atom, or its negation if the bool is false.
+    Lit(usize, bool),
+    And(Vec<Formula>),
+    Or(Vec<Formula>),
+}
+
+impl Formula{
+    /// Conjunction of the formulas, with constants folded away and nested conjunctions flattened.
+    fn and(parts: Vec<Formula>) -> Formula{
+        Self::join(parts, true)
+    }
+
+    /// Disjunction of the formulas, with constants folded away and nested disjunctions flattened.
+    fn or(parts: Vec<Formula>) -> Formula{
+        Self::join(parts, false)
+    }
+
+    /// Helper for `and()` and `or()`.
+    fn join(parts: Vec<Formula>, is_and: bool) -> Formula{
+        let mut flat = Vec::with_capacity(parts.len());
+        for part in parts{
+            match part{
+                Formula::Const(b) if b == is_and => (),
+                Formula::Const(b) => return Formula::Const(b),
+                Formula::And(inner) if is_and => flat.extend(inner),
+                Formula::Or(inner) if !is_and => flat.extend(inner),
+                other => flat.push(other),
+            }
+        }
+        match flat.len(){
+            0 => Formula::Const(is_and),
+            1 => flat.pop().unwrap(),
+            _ if is_and => Formula::And(flat),
+            _ => Formula::Or(flat),
+        }
+    }
+
+    /// The formula with the atom set to the value.
+    fn assign(&self, atom: usize, value: bool) -> Formula{
+        match self{
+            Formula::Lit(i, b) if *i == atom => Formula::Const(*b == value),
+            Formula::Const(_) | Formula::Lit(..) => self.clone(),
+            Formula::And(parts) => Self::and(parts.iter().map(|p| p.assign(atom, value)).collect()),
+            Formula::Or(parts) => Self::or(parts.iter().map(|p| p.assign(atom, value)).collect()),
+        }
+    }
+
+    /// Adds how many times each atom shows up in the formula.
+    fn count_atoms(&self, counts: &mut HashMap<usize, usize>){
+        match self{
+            Formula::Const(_) => (),
+            Formula::Lit(i, _) => *counts.entry(*i).or_insert(0) += 1,
+            Formula::And(parts) | Formula::Or(parts) => parts.iter().for_each(|p| p.count_atoms(counts)),
+        }
+    }
+}
+
+/// Node of a compiled `Ddnnf`. Children always come before their parents.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DdnnfNode{
+    True,
+    False,
+    /// The i'th atom, or its negation if the bool is false.
+    Lit(usize, bool),
+    /// Conjunction whose children share no atoms (decomposable).
+    And(Vec<usize>),
+    /// Disjunction whose children can't be true at the same time (deterministic).
+    Or(Vec<usize>),
+}
+
+/// An expression compiled into deterministic decomposable negation normal form (d-DNNF).
+///
+/// Compiling is expensive (exponential in the worst case), but once it's done model counting, satisfiability,
+/// clause entailment and conditioning all take time linear in the size of the compiled form.
+/// Quantifiers are expanded the same way `ExpressionTree::satisfy_count()` does it.
+/// ```
+/// use clawgic::prelude::*;
+/// let ddnnf = ExpressionTree::new("(A->B)&(B->C)").unwrap().compile_ddnnf();
+/// assert_eq!(ddnnf.model_count(), 4);
+/// let sen = |s: &str| Sentence::try_from(s).unwrap();
+/// assert!(ddnnf.entails_clause(&[(sen("A"), false), (sen("C"), true)]));
+/// assert!(!ddnnf.entails_clause(&[(sen("C"), true)]));
+///
+/// let conditioned = ddnnf.condition(&Assignment::new().set("A", true).unwrap());
+/// assert_eq!(conditioned.model_count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ddnnf{
+    /// Every ground sentence, by atom index.
+    atoms: Vec<Sentence>,
+    /// Which atoms have been conditioned on (and to what).
+    fixed: Vec<Option<bool>>,
+    /// Every node, children first.
+    nodes: Vec<DdnnfNode>,
+    /// Index of the root node.
+    root: usize,
+}
+
+impl Ddnnf{
+    /// Compiles the node. Every sentence in it must have been added to the grounding first.
+    pub(crate) fn new(node: &Node, grounding: &Grounding) -> Self{
+        let formula = to_formula(node, true, grounding, &mut HashMap::new());
+        let mut compiler = Compiler { nodes: Vec::new(), unique: HashMap::new(), cache: HashMap::new() };
+        let root = compiler.compile(formula);
+        Self {
+            atoms: grounding.atoms().clone(),
+            fixed: vec![None ; grounding.atoms().len()],
+            nodes: compiler.nodes,
+            root,
+        }
+    }
+
+    /// Gets every ground sentence the compiled form is over (including ones that have been conditioned on).
+    pub fn atoms(&self) -> &Vec<Sentence>{
+        &self.atoms
+    }
+
+    /// Number of nodes in the compiled form.
+    pub fn size(&self) -> usize{
+        self.nodes.len()
+    }
+
+    /// Whether any assignment satisfies the compiled form.
+    pub fn is_satisfiable(&self) -> bool{
+        let mut sat = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter(){
+            sat.push(match node{
+                DdnnfNode::True | DdnnfNode::Lit(..) => true,
+                DdnnfNode::False => false,
+                DdnnfNode::And(children) => children.iter().all(|c| sat[*c]),
+                DdnnfNode::Or(children) => children.iter().any(|c| sat[*c]),
+            });
+        }
+        sat[self.root]
+    }
+
+    /// Number of assignments of the atoms (other than the ones that have been conditioned on) that satisfy the compiled form.
+    ///
+    /// Saturates at `u128::MAX`.
+    pub fn model_count(&self) -> u128{
+        //count of each node over the atoms below it, plus which atoms those are (sorted)
+        let mut counts: Vec<(u128, Vec<usize>)> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter(){
+            counts.push(match node{
+                DdnnfNode::True => (1, Vec::new()),
+                DdnnfNode::False => (0, Vec::new()),
+                DdnnfNode::Lit(i, _) => (1, vec![*i]),
+                DdnnfNode::And(children) => {
+                    let mut count = 1u128;
+                    let mut atoms = Vec::new();
+                    for c in children.iter(){
+                        count = count.saturating_mul(counts[*c].0);
+                        atoms.extend(counts[*c].1.iter().copied());
+                    }
+                    atoms.sort_unstable();
+                    (count, atoms)
+                },
+                DdnnfNode::Or(children) => {
+                    let mut atoms: Vec<usize> = children.iter().flat_map(|c| counts[*c].1.iter().copied()).collect();
+                    atoms.sort_unstable();
+                    atoms.dedup();
+                    //smooth each child over the atoms it doesn't mention
+                    let count = children.iter().fold(0u128, |total, c| {
+                        total.saturating_add(scale_count(counts[*c].0, atoms.len() - counts[*c].1.len()))
+                    });
+                    (count, atoms)
+                },
+            });
+        }
+
+        let free = self.fixed.iter().filter(|f| f.is_none()).count();
+        let (count, atoms) = &counts[self.root];
+        scale_count(*count, free - atoms.len())
+    }
+
+    /// Whether every model of the compiled form satisfies the clause (a disjunction of sentences, each one
+    /// negated if its bool is false).
+    ///
+    /// Sentences that aren't atoms of the compiled form can't help satisfy the clause.
+    pub fn entails_clause(&self, clause: &[(Sentence, bool)]) -> bool{
+        let mut negated = Assignment::new();
+        for (sen, b) in clause.iter(){
+            if negated.insert(sen.clone(), !b) == Some(*b){
+                //the clause has both a sentence and its negation, so it's always true
+                return true;
+            }
+        }
+        !self.condition(&negated).is_satisfiable()
+    }
+
+    /// Conditions the compiled form on the assignment: every atom it mentions gets fixed to its value.
+    /// The result is still a d-DNNF.
+    ///
+    /// Sentences that aren't atoms are ignored. Conditioning an atom that is already fixed to the
+    /// other value gives something unsatisfiable.
+    pub fn condition(&self, assignment: &Assignment) -> Ddnnf{
+        let mut fixed = self.fixed.clone();
+        let mut contradiction = false;
+        for (i, sen) in self.atoms.iter().enumerate(){
+            if let Some(b) = assignment.get(sen){
+                contradiction |= fixed[i] == Some(!b);
+                fixed[i] = Some(b);
+            }
+        }
+
+        let mut compiler = Compiler { nodes: Vec::new(), unique: HashMap::new(), cache: HashMap::new() };
+        if contradiction{
+            let root = compiler.add(DdnnfNode::False);
+            return Self { atoms: self.atoms.clone(), fixed, nodes: compiler.nodes, root };
+        }
+        let mut map = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter(){
+            map.push(match node{
+                DdnnfNode::True => compiler.add(DdnnfNode::True),
+                DdnnfNode::False => compiler.add(DdnnfNode::False),
+                DdnnfNode::Lit(i, b) => match fixed[*i]{
+                    Some(v) if v == *b => compiler.add(DdnnfNode::True),
+                    Some(_) => compiler.add(DdnnfNode::False),
+                    None => compiler.add(DdnnfNode::Lit(*i, *b)),
+                },
+                DdnnfNode::And(children) => compiler.and(children.iter().map(|c| map[*c]).collect()),
+                DdnnfNode::Or(children) => compiler.or(children.iter().map(|c| map[*c]).collect()),
+            });
+        }
+
+        Self { atoms: self.atoms.clone(), fixed, nodes: compiler.nodes, root: map[self.root] }
+    }
+}
+
+/// Builds the nodes of a `Ddnnf`.
+struct Compiler{
+    nodes: Vec<DdnnfNode>,
+    /// Where each node already is in `nodes` (so nothing gets stored twice).
+    unique: HashMap<DdnnfNode, usize>,
+    /// What each formula compiled to.
+    cache: HashMap<Formula, usize>,
+}
+
+impl Compiler{
+    /// Adds the node (unless it's already there) and returns its index.
+    fn add(&mut self, node: DdnnfNode) -> usize{
+        if let Some(i) = self.unique.get(&node){
+            return *i;
+        }
+        self.nodes.push(node.clone());
+        self.unique.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Adds the conjunction of the nodes, folding away constants.
+    fn and(&mut self, children: Vec<usize>) -> usize{
+        if children.iter().any(|c| self.nodes[*c] == DdnnfNode::False){
+            return self.add(DdnnfNode::False);
+        }
+        let mut children: Vec<usize> = children.into_iter().filter(|c| self.nodes[*c] != DdnnfNode::True).collect();
+        match children.len(){
+            0 => self.add(DdnnfNode::True),
+            1 => children[0],
+            _ => {
+                children.sort_unstable();
+                self.add(DdnnfNode::And(children))
+            },
+        }
+    }
+
+    /// Adds the disjunction of the nodes, folding away constants.
+    fn or(&mut self, children: Vec<usize>) -> usize{
+        let mut children: Vec<usize> = children.into_iter().filter(|c| self.nodes[*c] != DdnnfNode::False).collect();
+        match children.len(){
+            0 => self.add(DdnnfNode::False),
+            1 => children[0],
+            _ => {
+                children.sort_unstable();
+                self.add(DdnnfNode::Or(children))
+            },
+        }
+    }
+
+    /// Compiles the formula. Splits conjunctions into parts that share no atoms,
+    /// and otherwise branches on the atom that shows up the most.
+    fn compile(&mut self, formula: Formula) -> usize{
+        match &formula{
+            Formula::Const(true) => return self.add(DdnnfNode::True),
+            Formula::Const(false) => return self.add(DdnnfNode::False),
+            Formula::Lit(i, b) => return self.add(DdnnfNode::Lit(*i, *b)),
+            _ => (),
+        }
+        if let Some(i) = self.cache.get(&formula){
+            return *i;
+        }
+
+        let compiled = match components(&formula){
+            Some(parts) => {
+                let children = parts.into_iter().map(|p| self.compile(p)).collect();
+                self.and(children)
+            },
+            None => {
+                let mut counts = HashMap::new();
+                formula.count_atoms(&mut counts);
+                let atom = counts.into_iter().max_by_key(|(i, c)| (*c, std::cmp::Reverse(*i))).unwrap().0;
+                let mut branches = Vec::with_capacity(2);
+                for value in [true, false]{
+                    let lit = self.add(DdnnfNode::Lit(atom, value));
+                    let rest = self.compile(formula.assign(atom, value));
+                    branches.push(self.and(vec![lit, rest]));
+                }
+                self.or(branches)
+            },
+        };
+        self.cache.insert(formula, compiled);
+
+        compiled
+    }
+}
+
+/// Splits a conjunction into groups of conjuncts that share no atoms with each other.
+/// Returns `None` if there is only one group.
+fn components(formula: &Formula) -> Option<Vec<Formula>>{
+    let Formula::And(parts) = formula else {return None};
+    let mut groups: Vec<(Vec<Formula>, Vec<usize>)> = Vec::new();
+    for part in parts.iter(){
+        let mut counts = HashMap::new();
+        part.count_atoms(&mut counts);
+        let mut atoms: Vec<usize> = counts.into_keys().collect();
+        let mut members = vec![part.clone()];
+        //merge every group that shares an atom with this part
+        let mut g = 0;
+        while g < groups.len(){
+            if groups[g].1.iter().any(|a| atoms.contains(a)){
+                let (other_members, other_atoms) = groups.swap_remove(g);
+                members.extend(other_members);
+                atoms.extend(other_atoms);
+            }else{
+                g += 1;
+            }
+        }
+        atoms.sort_unstable();
+        atoms.dedup();
+        groups.push((members, atoms));
+    }
+
+    match groups.len(){
+        1 => None,
+        _ => Some(groups.into_iter().map(|(members, _)| Formula::and(members)).collect()),
+    }
+}
+
+/// Turns the node into a `Formula` (negated if `positive` is false), expanding quantifiers over the domain of the grounding.
+fn to_formula(node: &Node, positive: bool, grounding: &Grounding, varsubs: &mut HashMap<ExpressionVar, ExpressionVar>) -> Formula{
+    let positive = positive != node.negation().is_denied();
+    match node{
+        Node::Operator { op, left, right, .. } => {
+            let mut side = |n: &Node, p: bool| to_formula(n, p, grounding, varsubs);
+            match (op, positive){
+                (Operator::AND, true) => Formula::and(vec![side(left, true), side(right, true)]),
+                (Operator::AND, false) => Formula::or(vec![side(left, false), side(right, false)]),
+                (Operator::OR, true) => Formula::or(vec![side(left, true), side(right, true)]),
+                (Operator::OR, false) => Formula::and(vec![side(left, false), side(right, false)]),
+                (Operator::CON, true) => Formula::or(vec![side(left, false), side(right, true)]),
+                (Operator::CON, false) => Formula::and(vec![side(left, true), side(right, false)]),
+                (Operator::BICON, _) => {
+                    let both = Formula::and(vec![side(left, true), side(right, positive)]);
+                    let neither = Formula::and(vec![side(left, false), side(right, !positive)]);
+                    Formula::or(vec![both, neither])
+                },
+                (Operator::NOT | Operator::UNI | Operator::EXI, _) => unreachable!(),
+            }
+        },
+        Node::Quantifier { op, vars, subexpr, .. } => {
+            let domain = grounding.domain();
+            //a universal is a conjunction of every instance, unless it's negated (then it's a disjunction of negated instances)
+            let conjunction = op.is_uni() == positive;
+            if domain.is_empty(){
+                return Formula::Const(op.is_uni() == positive);
+            }
+            let mut instances = Vec::new();
+            for_each_instance(vars, domain, varsubs, |varsubs| instances.push(to_formula(subexpr, positive, grounding, varsubs)));
+            if conjunction {Formula::and(instances)} else {Formula::or(instances)}
+        },
+        Node::Sentence { sen, .. } => match grounding.atom_index(&sen.substitute(varsubs)){
+            Some(i) => Formula::Lit(i, positive),
+            None => Formula::Const(!positive),
+        },
+        Node::Constant(_, b) => Formula::Const(*b == positive),
+    }
+}
@@ -0,0 +1,186 @@
+use std::ops::Range;
+
+use crate::expression_tree::ExpressionTree;
+use crate::expression_tree::lexer::Lexer;
+use crate::expression_tree::token::Token;
+use crate::operator_notation::OperatorNotation;
+use crate::parser_config::ParserConfig;
+use crate::prelude::Predicate;
+use crate::utils;
+use crate::ClawgicError;
+
+/// An error in an expression, along with where it is (see `ExpressionTree::diagnose()`).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostic{
+    error: ClawgicError,
+    /// Byte offsets into the expression.
+    span: Range<usize>,
+}
+
+impl Diagnostic{
+    /// Constructs a `Diagnostic`.
+    pub fn new(error: ClawgicError, span: Range<usize>) -> Self{
+        Self { error, span }
+    }
+
+    /// Gets what's wrong.
+    pub fn error(&self) -> &ClawgicError{
+        &self.error
+    }
+
+    /// Gets where it's wrong, as a range of byte offsets into the expression.
+    /// Empty for something that's missing (i.e. the end of the expression for a missing operand).
+    pub fn span(&self) -> Range<usize>{
+        self.span.clone()
+    }
+
+    /// Consumes the `Diagnostic`, giving back the error.
+    pub fn into_error(self) -> ClawgicError{
+        self.error
+    }
+}
+
+impl std::fmt::Display for Diagnostic{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at {}..{})", self.error, self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for Diagnostic{}
+
+/// Operators waiting for their right operand inside one pair of parentheses (or quantifier).
+struct Group{
+    /// Where the open parenthesis is, or `None` for the whole expression or the scope of a quantifier.
+    open: Option<Range<usize>>,
+    /// Precedence of every operator that hasn't been finished yet (the same ones the shunting yard would be holding).
+    precedences: Vec<u8>,
+}
+
+impl Group{
+    fn new(open: Option<Range<usize>>) -> Self{
+        Self { open, precedences: Vec::new() }
+    }
+
+    /// Finishes every operator that binds tighter than one with the given precedence.
+    /// Returns false if there's an operator with the same precedence (which would be ambiguous).
+    fn finish(&mut self, precedence: u8) -> bool{
+        while self.precedences.last().is_some_and(|p| *p > precedence){
+            self.precedences.pop();
+        }
+        self.precedences.last() != Some(&precedence)
+    }
+}
+
+/// Finds every error in the expression (instead of stopping at the first one). Returns nothing if it parses.
+///
+/// Keeps going after an error by skipping the bad token, or by pretending the missing operand/operator was there.
+pub(crate) fn diagnose(expression: &str, notation: &OperatorNotation, config: &ParserConfig) -> Vec<Diagnostic>{
+    let first = match ExpressionTree::new_with_config(expression, Some(notation), config){
+        Ok(_) => return Vec::new(),
+        Err(e) => e,
+    };
+
+    //subscript digits are longer than the digits the lexer sees, so every span has to be moved back
+    let normalized = utils::normalize_subscripts(expression);
+    let mut offsets = Vec::with_capacity(normalized.len() + 1);
+    for (i, c) in expression.char_indices(){
+        let len = utils::normalize_subscripts(c.encode_utf8(&mut [0 ; 4])).len();
+        offsets.extend(std::iter::repeat_n(i, len));
+    }
+    offsets.push(expression.len());
+    let mut diagnostics = Vec::new();
+    let mut report = |error: ClawgicError, span: Range<usize>| diagnostics.push(Diagnostic::new(error, offsets[span.start]..offsets[span.end]));
+
+    let mut lexer = Lexer::new(&normalized, notation, config);
+    //whether the next token should start an operand (`None` right after a bad token, since who knows)
+    let mut expecting_operand = Some(true);
+    let mut groups = vec![Group::new(None)];
+    let mut empty = true;
+    while let Some(token) = lexer.next(){
+        let span = lexer.span();
+        let token = match token{
+            Ok(token) => token,
+            Err(e) => {
+                report(e, span);
+                expecting_operand = None;
+                empty = false;
+                continue;
+            },
+        };
+        empty = false;
+        if token.starts_operand() && expecting_operand == Some(false){
+            report(ClawgicError::NotEnoughOperators, span.start..span.start);
+        }
+        match token{
+            Token::Sentence(_, name, args) => {
+                let sentence = ExpressionTree::expression_vars(args, config)
+                    .and_then(|vars| Predicate::new_with_config(name, vars.len(), config)?.inst(&vars));
+                if let Err(e) = sentence{
+                    report(e, span);
+                }
+                expecting_operand = Some(false);
+            },
+            Token::Constant(..) => expecting_operand = Some(false),
+            Token::Tilde(_) => expecting_operand = Some(true),
+            Token::Quantifier(_, op, args) => {
+                if let Err(e) = ExpressionTree::expression_vars(args, config){
+                    report(e, span.clone());
+                }
+                if !groups.last_mut().unwrap().finish(config.precedence(op)){
+                    report(ClawgicError::AmbiguousExpression, span);
+                }
+                groups.push(Group::new(None));
+                expecting_operand = Some(true);
+            },
+            Token::OpenParenthesis => {
+                groups.push(Group::new(Some(span)));
+                expecting_operand = Some(true);
+            },
+            Token::ClosedParenthesis => {
+                while groups.len() > 1 && groups.last().unwrap().open.is_none(){
+                    groups.pop();
+                }
+                if groups.len() == 1{
+                    report(ClawgicError::InvalidParentheses, span);
+                    continue;
+                }
+                if expecting_operand == Some(true){
+                    report(ClawgicError::TooManyOperators, span.start..span.start);
+                }
+                groups.pop();
+                expecting_operand = Some(false);
+            },
+            Token::Operator(_, op) => {
+                if expecting_operand == Some(true){
+                    //act like it isn't there
+                    report(ClawgicError::TooManyOperators, span);
+                    continue;
+                }
+                let group = groups.last_mut().unwrap();
+                if group.finish(config.precedence(op)){
+                    group.precedences.push(config.precedence(op));
+                }else{
+                    report(ClawgicError::AmbiguousExpression, span);
+                }
+                expecting_operand = Some(true);
+            },
+            Token::ImplicitAnd => expecting_operand = Some(true),
+        }
+    }
+
+    let end = normalized.len();
+    if empty{
+        report(ClawgicError::EmptyExpression, 0..end);
+    }else if expecting_operand == Some(true){
+        report(ClawgicError::TooManyOperators, end..end);
+    }
+    for open in groups.into_iter().filter_map(|g| g.open){
+        report(ClawgicError::InvalidParentheses, open);
+    }
+    //anything that only shows up once the tree is built
+    if diagnostics.is_empty(){
+        diagnostics.push(Diagnostic::new(first, 0..expression.len()));
+    }
+
+    diagnostics
+}
@@ -1,6 +1,6 @@
-use std::{fmt::Display, ops::{Index, RangeBounds}};
+use std::{fmt::Display, ops::{Bound, Index, RangeBounds}};
 
-use crate::{ClawgicError};
+use crate::{ClawgicError, parser_config::ParserConfig, prelude::ExpressionTree, utils};
 
 /// Variable constant for an ExpressionTree. Not necessary for constructing a tree, but very helpful.
 /// 
@@ -12,18 +12,18 @@ pub struct ExpressionVar{
 
 impl ExpressionVar{
     ///Constructs and returns an ExpressionVar iff a valid name is given.
+    /// 
+    /// Valid names are one lowercase letter followed by any number of digits.
+    /// (i.e. "a", "b0", "c123"). Subscript digits are turned into normal ones ("x₁" is "x1").
     pub fn new(name: &str) -> Result<ExpressionVar, ClawgicError>{
-        let name = name.trim().to_string();
-        let mut chars = name.chars();
-        let first = chars.next();
-        if first.is_none_or(|c| !c.is_lowercase()){
-            return Err(ClawgicError::InvalidVariableName(name.to_string()));
-        }
+        Self::new_with_config(name, &ParserConfig::default())
+    }
 
-        for c in chars{
-            if !c.is_numeric(){
-                return Err(ClawgicError::InvalidVariableName(name.to_string()));
-            }
+    ///Constructs and returns an ExpressionVar iff the name is valid under the given `ParserConfig`.
+    pub fn new_with_config(name: &str, config: &ParserConfig) -> Result<ExpressionVar, ClawgicError>{
+        let name = utils::normalize_subscripts(name.trim());
+        if !config.is_valid_var_name(&name){
+            return Err(ClawgicError::InvalidVariableName(name));
         }
 
         Ok(Self {name})
@@ -41,16 +41,16 @@ impl ExpressionVar{
 /// ```
 /// use clawgic::prelude::*;
 /// //relative indexing
-/// let a = ExpressionVars::new("A", 1..=3, true).unwrap();
-/// assert_eq!(a[1].name(), "A1");
-/// assert_eq!(a[2].name(), "A2");
-/// assert_eq!(a[3].name(), "A3");
+/// let a = ExpressionVars::new("a", 1..=3, true).unwrap();
+/// assert_eq!(a[1].name(), "a1");
+/// assert_eq!(a[2].name(), "a2");
+/// assert_eq!(a[3].name(), "a3");
 /// 
 /// //absolute indexing
-/// let a = ExpressionVars::new("A", 1..=3, false).unwrap();
-/// assert_eq!(a[0].name(), "A1");
-/// assert_eq!(a[1].name(), "A2");
-/// assert_eq!(a[2].name(), "A3");
+/// let a = ExpressionVars::new("a", 1..=3, false).unwrap();
+/// assert_eq!(a[0].name(), "a1");
+/// assert_eq!(a[1].name(), "a2");
+/// assert_eq!(a[2].name(), "a3");
 /// ```
 #[derive(Clone, Debug)]
 pub struct ExpressionVars{
@@ -87,20 +87,276 @@ impl ExpressionVars{
         })
     }
 
+    ///Constructs an ExpressionVars out of the given names (in order). Always indexed absolutely.
+    /// 
+    ///Every name has to be valid by default (see `ExpressionVar::new()`), so "alice" isn't allowed;
+    ///use `from_names_with_config()` for multi-letter names.
+    pub fn from_names(names: &[&str]) -> Result<Self, ClawgicError>{
+        Self::from_names_with_config(names, &ParserConfig::default())
+    }
+
+    ///Constructs an ExpressionVars out of the given names (in order) iff they're all valid under the given `ParserConfig`.
+    ///Always indexed absolutely.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// use clawgic::parser_config::ParserConfig;
+    /// let mut config = ParserConfig::default();
+    /// config.set_long_names(true);
+    /// let people = ExpressionVars::from_names_with_config(&["alice", "bob"], &config).unwrap();
+    /// assert_eq!(people[0].name(), "alice");
+    /// ```
+    pub fn from_names_with_config(names: &[&str], config: &ParserConfig) -> Result<Self, ClawgicError>{
+        Ok(Self{
+            vars: names.iter().map(|name| ExpressionVar::new_with_config(name, config)).collect::<Result<_, _>>()?,
+            bounds: None,
+        })
+    }
+
     ///Gets lowest index.
     pub fn start(&self) -> usize{
         self.bounds.unwrap_or((0,0)).0
     }
 
     ///Gets highest index.
+    /// 
+    ///An empty list has no highest index, so this gives 0 (the same as `start()`); check `is_empty()` first.
     pub fn end(&self) -> usize{
-        self.bounds.unwrap_or((0, self.vars.len() - 1)).1
+        self.bounds.unwrap_or((0, self.vars.len().saturating_sub(1))).1
     }
 
     ///creates an iterator of all ExpressionVars.
     pub fn iter(&self) -> std::slice::Iter<'_, ExpressionVar>{
         self.vars.iter()
     }
+
+    ///Number of variables.
+    pub fn len(&self) -> usize{
+        self.vars.len()
+    }
+
+    ///Whether there are no variables.
+    pub fn is_empty(&self) -> bool{
+        self.vars.is_empty()
+    }
+
+    ///Conjunction of the expression made for each variable. `TRUE` if there are no variables.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let people = ExpressionVars::from_names(&["a", "b", "c"]).unwrap();
+    /// //P(x): x got picked
+    /// let picked = |x: &ExpressionVar| Predicate::new("P", 1).unwrap().inst(&vec![x.clone()]).unwrap();
+    /// let some = people.disjunction(|x| picked(x).expr());
+    /// let at_most_one = people.pairwise(|x, y| !(&picked(x) & &picked(y)));
+    /// assert_eq!(some.and(at_most_one).satisfy_all().len(), 3);
+    /// assert!(people.conjunction(|x| picked(x).expr()).log_eq(&ExpressionTree::new("(Pa&Pb)&Pc").unwrap()));
+    /// ```
+    pub fn conjunction<F>(&self, f: F) -> ExpressionTree
+    where F: FnMut(&ExpressionVar) -> ExpressionTree{
+        self.vars.iter().map(f).reduce(ExpressionTree::and).unwrap_or_else(ExpressionTree::TRUE)
+    }
+
+    ///Disjunction of the expression made for each variable. `FALSE` if there are no variables.
+    pub fn disjunction<F>(&self, f: F) -> ExpressionTree
+    where F: FnMut(&ExpressionVar) -> ExpressionTree{
+        self.vars.iter().map(f).reduce(ExpressionTree::or).unwrap_or_else(ExpressionTree::FALSE)
+    }
+
+    ///Conjunction of the expression made for every pair of different variables (each pair once, in order).
+    ///`TRUE` if there are less than two variables.
+    pub fn pairwise<F>(&self, mut f: F) -> ExpressionTree
+    where F: FnMut(&ExpressionVar, &ExpressionVar) -> ExpressionTree{
+        let pairs = self.vars.iter().enumerate().flat_map(|(i, a)| self.vars[i + 1..].iter().map(move |b| (a, b)));
+        pairs.map(|(a, b)| f(a, b)).reduce(ExpressionTree::and).unwrap_or_else(ExpressionTree::TRUE)
+    }
+}
+
+/// Gets the (inclusive) start and end of a range of grid rows or columns. Fails if the range is unbounded or empty.
+fn grid_bounds<R: RangeBounds<usize>>(range: R) -> Result<(usize, usize), ClawgicError>{
+    let start = match range.start_bound(){
+        Bound::Included(s) => *s,
+        Bound::Excluded(s) => *s + 1,
+        Bound::Unbounded => return Err(ClawgicError::InvalidVarBounds),
+    };
+    let end = match range.end_bound(){
+        Bound::Included(e) => *e,
+        Bound::Excluded(e) => e.checked_sub(1).ok_or(ClawgicError::InvalidVarBounds)?,
+        Bound::Unbounded => return Err(ClawgicError::InvalidVarBounds),
+    };
+    if end < start{
+        return Err(ClawgicError::InvalidVarBounds);
+    }
+
+    Ok((start, end))
+}
+
+///2D grid of enumerated ExpressionVar's. Nice for encoding puzzles on a board (i.e. sudoku or n-queens).
+/// 
+/// Every variable is named with the prefix followed by its row and then its column.
+/// The row and column are padded with zeros to the width of the biggest row and column
+/// so that names can't collide (i.e. (1, 10) is "c0110" and (11, 0) is "c1100").
+/// Variable names are lowercase, so the prefix is lowercased first ("C" gives "c11", "c12", ...).
+/// 
+/// Always indexed relatively.
+/// ```
+/// use clawgic::prelude::*;
+/// let c = ExpressionVarGrid::new("C", 1..=9, 1..=9).unwrap();
+/// assert_eq!(c[(1, 1)].name(), "c11");
+/// assert_eq!(c[(3, 7)].name(), "c37");
+/// assert_eq!(c.row(2).count(), 9);
+/// assert_eq!(c.column(5).next().unwrap().name(), "c15");
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpressionVarGrid{
+    /// All the variables in row-major order.
+    vars: Vec<ExpressionVar>,
+    /// (start, end) of the rows.
+    rows: (usize, usize),
+    /// (start, end) of the columns.
+    cols: (usize, usize),
+}
+
+impl ExpressionVarGrid{
+    ///Constructs a new ExpressionVarGrid with the given prefix, rows, and columns.
+    ///
+    ///Fails with `InvalidVarBounds` if either range is unbounded or empty.
+    pub fn new<R, C>(name: &str, rows: R, cols: C) -> Result<Self, ClawgicError>
+    where R: RangeBounds<usize>, C: RangeBounds<usize>{
+        let name = name.trim().to_lowercase();
+        let rows = grid_bounds(rows)?;
+        let cols = grid_bounds(cols)?;
+        let row_width = rows.1.to_string().len();
+        let col_width = cols.1.to_string().len();
+        let mut vars = Vec::with_capacity((rows.1 - rows.0 + 1) * (cols.1 - cols.0 + 1));
+        for r in rows.0..=rows.1{
+            for c in cols.0..=cols.1{
+                vars.push(ExpressionVar::new(&format!("{name}{r:0row_width$}{c:0col_width$}"))?);
+            }
+        }
+
+        Ok(Self { vars, rows, cols })
+    }
+
+    ///Gets the lowest row index.
+    pub fn row_start(&self) -> usize{
+        self.rows.0
+    }
+
+    ///Gets the highest row index.
+    pub fn row_end(&self) -> usize{
+        self.rows.1
+    }
+
+    ///Gets the lowest column index.
+    pub fn col_start(&self) -> usize{
+        self.cols.0
+    }
+
+    ///Gets the highest column index.
+    pub fn col_end(&self) -> usize{
+        self.cols.1
+    }
+
+    ///Number of rows.
+    pub fn height(&self) -> usize{
+        self.rows.1 - self.rows.0 + 1
+    }
+
+    ///Number of columns.
+    pub fn width(&self) -> usize{
+        self.cols.1 - self.cols.0 + 1
+    }
+
+    ///Gets the variable at (row, col) if it's in the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<&ExpressionVar>{
+        if !(self.rows.0..=self.rows.1).contains(&row) || !(self.cols.0..=self.cols.1).contains(&col){
+            return None;
+        }
+        self.vars.get((row - self.rows.0) * self.width() + (col - self.cols.0))
+    }
+
+    ///creates an iterator of all ExpressionVars in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, ExpressionVar>{
+        self.vars.iter()
+    }
+
+    ///creates an iterator over the given row (left to right).
+    /// 
+    /// panics if the row isn't in the grid.
+    pub fn row(&self, row: usize) -> std::slice::Iter<'_, ExpressionVar>{
+        assert!((self.rows.0..=self.rows.1).contains(&row), "row {row} is out of bounds");
+        let start = (row - self.rows.0) * self.width();
+        self.vars[start..start + self.width()].iter()
+    }
+
+    ///creates an iterator over the given column (top to bottom).
+    /// 
+    /// panics if the column isn't in the grid.
+    pub fn column(&self, col: usize) -> std::iter::StepBy<std::iter::Skip<std::slice::Iter<'_, ExpressionVar>>>{
+        assert!((self.cols.0..=self.cols.1).contains(&col), "column {col} is out of bounds");
+        self.vars.iter().skip(col - self.cols.0).step_by(self.width())
+    }
+
+    ///creates an iterator over every row.
+    pub fn rows(&self) -> std::slice::Chunks<'_, ExpressionVar>{
+        self.vars.chunks(self.width())
+    }
+
+    ///creates an iterator over every column.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&ExpressionVar>>{
+        (self.cols.0..=self.cols.1).map(|c| self.column(c).collect())
+    }
+
+    ///creates an iterator over every diagonal going from the top left to the bottom right
+    /// (i.e. every set of cells where row - col is the same).
+    /// 
+    /// Starts with the diagonal at the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&ExpressionVar>>{
+        let (height, width) = (self.height(), self.width());
+        (0..height + width - 1).map(move |d| {
+            //start of diagonal d in (relative) grid coordinates
+            let (mut r, mut c) = if d < height {(height - 1 - d, 0)} else {(0, d - height + 1)};
+            let mut diagonal = Vec::new();
+            while r < height && c < width{
+                diagonal.push(&self.vars[r * width + c]);
+                r += 1;
+                c += 1;
+            }
+            diagonal
+        })
+    }
+
+    ///creates an iterator over every diagonal going from the top right to the bottom left
+    /// (i.e. every set of cells where row + col is the same).
+    /// 
+    /// Starts with the diagonal at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&ExpressionVar>>{
+        let (height, width) = (self.height(), self.width());
+        (0..height + width - 1).map(move |d| {
+            (0..height)
+                .filter(|r| d >= *r && d - r < width)
+                .map(|r| &self.vars[r * width + (d - r)])
+                .collect()
+        })
+    }
+}
+
+impl Index<(usize, usize)> for ExpressionVarGrid{
+    type Output = ExpressionVar;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        match self.get(row, col){
+            Some(v) => v,
+            None => panic!("({row}, {col}) is out of bounds"),
+        }
+    }
+}
+
+impl IntoIterator for ExpressionVarGrid{
+    type Item = ExpressionVar;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.vars.into_iter()
+    }
 }
 
 impl Index<usize> for ExpressionVars{
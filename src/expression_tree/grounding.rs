@@ -0,0 +1,254 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::expression_tree::node::Node;
+use crate::expression_tree::universe::Universe;
+use crate::prelude::{Assignment, ExpressionVar, Sentence};
+use crate::ClawgicError;
+
+/// Turns (possibly quantified) expressions into a fixed list of ground atomic sentences
+/// so they can be checked by enumerating truth assignments.
+///
+/// Quantifiers range over the `domain`: every individual that shows up free in the
+/// expressions (plus any given explicitly) and one fresh individual standing in for "anything else",
+/// so that (for example) `#xPx` doesn't force `Pa` to be true.
+#[derive(Debug, Clone)]
+pub(crate) struct Grounding{
+    /// Individuals that quantifiers range over.
+    domain: Vec<ExpressionVar>,
+    /// Every ground sentence seen so far, in the order they were found.
+    atoms: Vec<Sentence>,
+    /// Where each atom is in `atoms`.
+    index: HashMap<Sentence, usize>,
+    /// Reusable universe holding the domain and every atom.
+    uni: Universe,
+}
+
+impl Grounding{
+    /// Constructs an empty `Grounding` over the given domain.
+    pub(crate) fn new(domain: Vec<ExpressionVar>) -> Self{
+        let mut uni = Universe::new();
+        uni.insert_variables(domain.iter().cloned());
+        Self { domain, atoms: Vec::new(), index: HashMap::new(), uni }
+    }
+
+    /// Works out the domain for the given nodes.
+    ///
+    /// Names that are bound by a quantifier anywhere are left out (otherwise evaluation would
+    /// complain about them being bound twice).
+    pub(crate) fn domain_of<'a, It, V>(nodes: It, individuals: V) -> Vec<ExpressionVar>
+    where It: Iterator<Item = &'a Node>, V: Iterator<Item = &'a ExpressionVar>{
+        let mut free = BTreeSet::new();
+        let mut bound = BTreeSet::new();
+        for node in nodes{
+            collect_vars(node, &mut Vec::new(), &mut free, &mut bound);
+        }
+        free.extend(individuals.cloned());
+        let fresh = fresh_var(&free, &bound);
+        let mut domain: Vec<ExpressionVar> = free.into_iter().filter(|v| !bound.contains(v)).collect();
+        domain.push(fresh);
+
+        domain
+    }
+
+    /// Gets the domain.
+    pub(crate) fn domain(&self) -> &Vec<ExpressionVar>{
+        &self.domain
+    }
+
+    /// Gets every atom found so far.
+    pub(crate) fn atoms(&self) -> &Vec<Sentence>{
+        &self.atoms
+    }
+
+    /// Gets the position of the atom in `atoms()`.
+    pub(crate) fn atom_index(&self, sentence: &Sentence) -> Option<usize>{
+        self.index.get(sentence).copied()
+    }
+
+    /// Adds every ground sentence in the node that isn't already known.
+    /// Returns how many new atoms were added (they are always at the end of `atoms()`).
+    pub(crate) fn add_atoms(&mut self, node: &Node) -> usize{
+        let before = self.atoms.len();
+        let mut found = Vec::new();
+        ground_sentences(node, &self.domain, &mut Vec::new(), &mut found);
+        for sen in found{
+            self.add_atom(sen);
+        }
+
+        self.atoms.len() - before
+    }
+
+    /// Adds the sentence as an atom if it isn't one already. Returns its position in `atoms()`.
+    pub(crate) fn add_atom(&mut self, sentence: Sentence) -> usize{
+        if let Some(i) = self.index.get(&sentence){
+            return *i;
+        }
+        self.uni.insert_sentence(sentence.clone(), false);
+        self.index.insert(sentence.clone(), self.atoms.len());
+        self.atoms.push(sentence);
+        self.atoms.len() - 1
+    }
+
+    /// Evaluates the node where the i'th atom has the i'th truth value in `assignment`.
+    /// Every sentence in the node must have been added with `add_atoms()` first.
+    pub(crate) fn evaluate(&mut self, node: &Node, assignment: &[bool]) -> Result<bool, ClawgicError>{
+        for (sen, b) in self.atoms.iter().zip(assignment.iter()){
+            if let Some(tval) = self.uni.get_tval_mut(sen){
+                *tval = *b;
+            }
+        }
+        node.evaluate(&self.uni, &mut HashMap::new())
+    }
+
+    /// Turns a list of truth values back into an `Assignment`.
+    pub(crate) fn to_assignment(&self, assignment: &[bool]) -> Assignment{
+        self.atoms.iter().cloned().zip(assignment.iter().copied()).collect()
+    }
+}
+
+/// Moves to the next assignment (counting in binary with the first atom as the lowest bit).
+/// Returns false once every assignment has been visited.
+pub(crate) fn next_assignment(assignment: &mut [bool]) -> bool{
+    for b in assignment.iter_mut(){
+        *b = !*b;
+        if *b{
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Calls `visit` once for every way of filling in the vars with individuals from the (non-empty) domain,
+/// with `varsubs` holding the substitution. The vars are taken back out of `varsubs` afterwards.
+pub(crate) fn for_each_instance<F>(vars: &[ExpressionVar], domain: &[ExpressionVar], varsubs: &mut HashMap<ExpressionVar, ExpressionVar>, mut visit: F)
+where F: FnMut(&mut HashMap<ExpressionVar, ExpressionVar>){
+    let mut indices = vec![0 ; vars.len()];
+    loop{
+        for (v, i) in vars.iter().zip(indices.iter()){
+            varsubs.insert(v.clone(), domain[*i].clone());
+        }
+        visit(varsubs);
+
+        let mut i = 0;
+        while i < indices.len(){
+            indices[i] += 1;
+            if indices[i] < domain.len(){
+                break;
+            }
+            indices[i] = 0;
+            i += 1;
+        }
+        if i == indices.len(){
+            break;
+        }
+    }
+    for v in vars.iter(){
+        varsubs.remove(v);
+    }
+}
+
+/// Finds every sentence in the node that doesn't use a variable bound by a quantifier.
+pub(crate) fn free_sentences(node: &Node, scope: &mut Vec<ExpressionVar>, found: &mut BTreeSet<Sentence>){
+    match node{
+        Node::Operator { left, right, .. } => {
+            free_sentences(left, scope, found);
+            free_sentences(right, scope, found);
+        },
+        Node::Quantifier { vars, subexpr, .. } => {
+            scope.extend(vars.iter().cloned());
+            free_sentences(subexpr, scope, found);
+            scope.truncate(scope.len() - vars.len());
+        },
+        Node::Sentence { sen, .. } => {
+            if !sen.vars().iter().any(|v| scope.contains(v)){
+                found.insert(sen.clone());
+            }
+        },
+        Node::Constant(..) => (),
+    }
+}
+
+/// Finds every free and bound variable in the node.
+fn collect_vars(node: &Node, scope: &mut Vec<ExpressionVar>, free: &mut BTreeSet<ExpressionVar>, bound: &mut BTreeSet<ExpressionVar>){
+    match node{
+        Node::Operator { left, right, .. } => {
+            collect_vars(left, scope, free, bound);
+            collect_vars(right, scope, free, bound);
+        },
+        Node::Quantifier { vars, subexpr, .. } => {
+            bound.extend(vars.iter().cloned());
+            scope.extend(vars.iter().cloned());
+            collect_vars(subexpr, scope, free, bound);
+            scope.truncate(scope.len() - vars.len());
+        },
+        Node::Sentence { sen, .. } => {
+            free.extend(sen.vars().iter().filter(|v| !scope.contains(v)).cloned());
+        },
+        Node::Constant(..) => (),
+    }
+}
+
+/// Finds every ground instance of every sentence in the node.
+fn ground_sentences(node: &Node, domain: &[ExpressionVar], scope: &mut Vec<ExpressionVar>, found: &mut Vec<Sentence>){
+    match node{
+        Node::Operator { left, right, .. } => {
+            ground_sentences(left, domain, scope, found);
+            ground_sentences(right, domain, scope, found);
+        },
+        Node::Quantifier { vars, subexpr, .. } => {
+            scope.extend(vars.iter().cloned());
+            ground_sentences(subexpr, domain, scope, found);
+            scope.truncate(scope.len() - vars.len());
+        },
+        Node::Sentence { sen, .. } => {
+            let mut bound: Vec<&ExpressionVar> = sen.vars().iter().filter(|v| scope.contains(v)).collect();
+            bound.sort();
+            bound.dedup();
+            if bound.is_empty(){
+                found.push(sen.clone());
+                return;
+            }
+            if domain.is_empty(){
+                return;
+            }
+
+            //go through every way of filling in the bound vars
+            let mut indices = vec![0 ; bound.len()];
+            loop{
+                let subs = bound.iter().zip(indices.iter()).map(|(v, i)| ((*v).clone(), domain[*i].clone())).collect();
+                found.push(sen.substitute(&subs));
+
+                let mut i = 0;
+                while i < indices.len(){
+                    indices[i] += 1;
+                    if indices[i] < domain.len(){
+                        break;
+                    }
+                    indices[i] = 0;
+                    i += 1;
+                }
+                if i == indices.len(){
+                    break;
+                }
+            }
+        },
+        Node::Constant(..) => (),
+    }
+}
+
+/// Makes up a variable name that isn't in either set.
+fn fresh_var(free: &BTreeSet<ExpressionVar>, bound: &BTreeSet<ExpressionVar>) -> ExpressionVar{
+    let mut suffix = String::new();
+    let mut n = 0;
+    loop{
+        for c in 'a'..='z'{
+            let var = ExpressionVar::new(&format!("{c}{suffix}")).unwrap();
+            if !free.contains(&var) && !bound.contains(&var){
+                return var;
+            }
+        }
+        suffix = n.to_string();
+        n += 1;
+    }
+}
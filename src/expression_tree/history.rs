@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::expression_tree::node::Node;
+use crate::expression_tree::universe::Universe;
+
+/// What a tree looked like before one of its transformations.
+///
+/// Cheap to keep around since every subtree (and the universe) is shared with the tree itself.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot{
+    pub(crate) root: Node,
+    pub(crate) uni: Arc<Universe>,
+}
+
+/// Undo and redo stacks for a tree in history mode (see `ExpressionTree::enable_history()`).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct History{
+    /// Snapshots to go back to, most recent last.
+    undo: Vec<Snapshot>,
+    /// Snapshots that were undone, most recent last.
+    redo: Vec<Snapshot>,
+}
+
+impl History{
+    /// Records the state of a tree before a new transformation. Anything that was undone can't be redone anymore.
+    pub(crate) fn push(&mut self, snapshot: Snapshot){
+        self.undo.push(snapshot);
+        self.redo.clear();
+    }
+
+    /// Swaps the current state of a tree for the last one on the undo stack.
+    pub(crate) fn undo(&mut self, current: Snapshot) -> Option<Snapshot>{
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Swaps the current state of a tree for the last one on the redo stack.
+    pub(crate) fn redo(&mut self, current: Snapshot) -> Option<Snapshot>{
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    /// Whether there is anything to undo.
+    pub(crate) fn can_undo(&self) -> bool{
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is anything to redo.
+    pub(crate) fn can_redo(&self) -> bool{
+        !self.redo.is_empty()
+    }
+}
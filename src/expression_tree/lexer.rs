@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use crate::expression_tree::node::negation::Negation;
+use crate::expression_tree::node::operator::Operator;
+use crate::expression_tree::token::{Args, Token};
+use crate::operator_notation::OperatorNotation;
+use crate::parser_config::ParserConfig;
+use crate::ClawgicError;
+
+/// Splits an infix logical expression into `Token`s that borrow from it.
+///
+/// Works on byte offsets into the expression, so it never allocates (except for errors) and reads each character once.
+/// Operators are matched against every notation in a single pass, longest first.
+/// Puts an `ImplicitAnd` between every two operands that are next to each other if `ParserConfig::implicit_and()` is on.
+///
+/// Always moves forward after an error, so it can keep going to find the rest of the errors (see `span()`).
+pub(crate) struct Lexer<'a>{
+    input: &'a str,
+    /// Byte offset of the next character.
+    pos: usize,
+    /// Byte offset of the start of the token being read.
+    start: usize,
+    /// Where the last token (or error) was in the expression.
+    span: Range<usize>,
+    /// End of the run of uppercase letters currently being split into sentences (with implicit conjunction).
+    run_end: usize,
+    notation: &'a OperatorNotation,
+    config: &'a ParserConfig,
+    /// Token (and its span) to give out right after an `ImplicitAnd`.
+    pending: Option<(Token<'a>, Range<usize>)>,
+    /// Whether the last token ended an operand.
+    after_operand: bool,
+}
+
+impl<'a> Lexer<'a>{
+    /// Constructs a `Lexer` at the start of the expression.
+    pub(crate) fn new(input: &'a str, notation: &'a OperatorNotation, config: &'a ParserConfig) -> Self{
+        Self { input, pos: 0, start: 0, span: 0..0, run_end: 0, notation, config, pending: None, after_operand: false }
+    }
+
+    /// Where the last token (or error) given out was, as a range of byte offsets into the expression.
+    /// An `ImplicitAnd` is empty, right before the operand that comes after it.
+    pub(crate) fn span(&self) -> Range<usize>{
+        self.span.clone()
+    }
+
+    /// Everything that hasn't been read yet.
+    fn rest(&self) -> &'a str{
+        &self.input[self.pos..]
+    }
+
+    /// The next character (without reading it).
+    fn peek(&self) -> Option<char>{
+        self.rest().chars().next()
+    }
+
+    /// Reads characters while they match, returning everything that was read.
+    fn take_while(&mut self, matches: impl Fn(char) -> bool) -> &'a str{
+        let start = self.pos;
+        let len = self.rest().find(|c: char| !matches(c)).unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.input[start..self.pos]
+    }
+
+    /// Skips whitespace, but only with short names (long names need whitespace to tell where they end).
+    fn skip_short_whitespace(&mut self){
+        if !self.config.long_names(){
+            self.take_while(char::is_whitespace);
+        }
+    }
+
+    /// Reads the next token (without any implicit conjunction).
+    fn scan(&mut self) -> Option<Result<Token<'a>, ClawgicError>>{
+        if self.pos < self.run_end{
+            self.start = self.pos;
+            return Some(self.run_letter());
+        }
+        self.take_while(char::is_whitespace);
+        self.start = self.pos;
+        let c = self.peek()?;
+
+        Some(if self.config.long_names() && c.is_uppercase(){
+            self.long_name()
+        }else if c.is_alphanumeric() && c != 'v'{
+            self.short_name(c)
+        }else{
+            match self.notation.match_operator(self.rest()){
+                Ok((op, len)) => {
+                    self.pos += len;
+                    self.operator(op, false)
+                },
+                Err(0) => {
+                    self.pos += c.len_utf8();
+                    self.symbol(c)
+                },
+                Err(len) => {
+                    let symbol = self.rest()[..len].to_string();
+                    self.pos += len;
+                    Err(ClawgicError::UnknownSymbol(symbol))
+                },
+            }
+        })
+    }
+
+    /// Reads a (long) predicate name, a constant, or one of the letter constants.
+    fn long_name(&mut self) -> Result<Token<'a>, ClawgicError>{
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        let has_args = self.peek() == Some('(');
+        if name == "TRUE" || name == "FALSE"{
+            Ok(Token::Constant(Negation::default(), name == "TRUE"))
+        }else if self.config.letter_constants() && (name == "T" || name == "F") && !has_args{
+            Ok(Token::Constant(Negation::default(), name == "T"))
+        }else{
+            let args = if has_args {self.args()?} else {Args::NONE};
+            Ok(Token::Sentence(Negation::default(), name, args))
+        }
+    }
+
+    /// Reads a run of uppercase letters (or `1`/`0`). With implicit conjunction, every letter is its own sentence.
+    fn short_name(&mut self, c: char) -> Result<Token<'a>, ClawgicError>{
+        let start = self.pos;
+        let run = self.take_while(char::is_uppercase);
+        if run.is_empty(){
+            if c != '1' && c != '0'{
+                return Err(ClawgicError::InvalidPredicateName(c.to_string()));
+            }
+            self.pos += 1;
+            return Ok(Token::Constant(Negation::default(), c == '1'));
+        }
+        if run == "TRUE" || run == "FALSE"{
+            return Ok(Token::Constant(Negation::default(), run == "TRUE"));
+        }
+        if run.chars().nth(1).is_some() && !self.config.implicit_and(){
+            return Err(ClawgicError::InvalidPredicateName(run.to_string()));
+        }
+        self.run_end = self.pos;
+        self.pos = start;
+
+        self.run_letter()
+    }
+
+    /// Reads the next letter of a run of uppercase letters. Only the last one gets digits and variables.
+    fn run_letter(&mut self) -> Result<Token<'a>, ClawgicError>{
+        let start = self.pos;
+        self.pos += self.peek().map_or(0, char::len_utf8);
+        if self.pos < self.run_end{
+            return Ok(self.short_sentence(&self.input[start..self.pos], Args::NONE));
+        }
+        self.take_while(char::is_numeric);
+        let name = &self.input[start..self.pos];
+        self.skip_short_whitespace();
+        //with implicit conjunction, a parenthesis after a sentence starts a group instead of arguments
+        let args = if self.config.implicit_and() && self.peek() == Some('('){
+            Args::NONE
+        }else{
+            self.args()?
+        };
+
+        Ok(self.short_sentence(name, args))
+    }
+
+    /// Makes the token for a (short) sentence. `T` and `F` are constants instead if `ParserConfig::letter_constants()` is on.
+    fn short_sentence(&self, name: &'a str, args: Args<'a>) -> Token<'a>{
+        if self.config.letter_constants() && args.is_empty() && (name == "T" || name == "F"){
+            return Token::Constant(Negation::default(), name == "T");
+        }
+
+        Token::Sentence(Negation::default(), name, args)
+    }
+
+    /// Makes the token for an operator that was just read (reading the variables of a quantifier).
+    fn operator(&mut self, op: Operator, denied: bool) -> Result<Token<'a>, ClawgicError>{
+        if op.is_not(){
+            Ok(Token::Tilde(Negation::new(1)))
+        }else if op.is_quantifier(){
+            self.skip_short_whitespace();
+            let args = self.args()?;
+            if args.is_empty(){
+                return Err(ClawgicError::NoVarQuantifier);
+            }
+            Ok(Token::Quantifier(Negation::default(), op, args))
+        }else{
+            Ok(Token::Operator(Negation::new(denied as u32), op))
+        }
+    }
+
+    /// Makes the token for a symbol that isn't in the notation (which was just read).
+    fn symbol(&mut self, c: char) -> Result<Token<'a>, ClawgicError>{
+        if let Some((op, denied)) = OperatorNotation::get_unicode_operator(c){
+            self.operator(op, denied)
+        }else if c == '⊤' || c == '⊥'{
+            Ok(Token::Constant(Negation::default(), c == '⊤'))
+        }else if c == '('{
+            Ok(Token::OpenParenthesis)
+        }else if c == ')'{
+            Ok(Token::ClosedParenthesis)
+        }else{
+            Err(ClawgicError::UnknownSymbol(c.to_string()))
+        }
+    }
+
+    /// Reads the variables right after a predicate or quantifier, either in the form `(x1, y2, ...)` or `x1y2...`.
+    fn args(&mut self) -> Result<Args<'a>, ClawgicError>{
+        let args = if self.peek() == Some('('){
+            let inner = &self.rest()[1..];
+            let Some(end) = inner.find(')') else{
+                //nothing after this can be read properly
+                self.pos = self.input.len();
+                return Err(ClawgicError::InvalidExpression);
+            };
+            self.pos += end + 2;
+            Args::List(&inner[..end])
+        }else{
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c.is_lowercase() && c != 'v'){
+                self.pos += self.peek().map_or(0, char::len_utf8);
+                self.take_while(char::is_numeric);
+            }
+            Args::Run(&self.input[start..self.pos])
+        };
+        if let Some(var) = args.vars().find(|v| !self.config.is_valid_var_name(v)){
+            return Err(ClawgicError::InvalidVariableName(var.to_string()));
+        }
+
+        Ok(args)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a>{
+    type Item = Result<Token<'a>, ClawgicError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (token, span) = match self.pending.take(){
+            Some(pending) => pending,
+            None => match self.scan()?{
+                Ok(token) => (token, self.start..self.pos),
+                Err(e) => {
+                    if self.pos == self.start{
+                        self.pos += self.peek().map_or(0, char::len_utf8);
+                    }
+                    self.span = self.start..self.pos;
+                    return Some(Err(e));
+                },
+            },
+        };
+        if self.config.implicit_and() && self.after_operand && token.starts_operand(){
+            self.span = span.start..span.start;
+            self.pending = Some((token, span));
+            self.after_operand = false;
+            return Some(Ok(Token::ImplicitAnd));
+        }
+        self.after_operand = token.ends_operand();
+        self.span = span;
+
+        Some(Ok(token))
+    }
+}
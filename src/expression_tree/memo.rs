@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::expression_tree::node::Node;
+use crate::expression_tree::universe::Universe;
+use crate::prelude::{Predicate, Sentence};
+use crate::ClawgicError;
+
+const UNKNOWN: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+
+/// Remembers the value of every node in a tree so that changing one sentence only
+/// re-evaluates the nodes that actually contain it.
+///
+/// Nodes are numbered in preorder. Everything inside a quantifier is evaluated once per individual,
+/// so only the quantifier node itself gets remembered.
+#[derive(Debug)]
+pub(crate) struct Memo{
+    /// Remembered value of each node (`UNKNOWN`, `FALSE` or `TRUE`). Atomic so trees stay `Sync`.
+    values: Vec<AtomicU8>,
+    /// Number of nodes in the subtree of each node (used to find right children).
+    sizes: Vec<usize>,
+    /// Every node that has the (free) sentence somewhere below it.
+    sentence_deps: HashMap<Sentence, Vec<usize>>,
+    /// Every node that has a quantified sentence with the predicate somewhere below it.
+    predicate_deps: HashMap<Predicate, Vec<usize>>,
+}
+
+impl Memo{
+    /// Constructs a `Memo` for the tree with the given root. Nothing is remembered yet.
+    pub(crate) fn new(root: &Node) -> Self{
+        let mut memo = Self { values: Vec::new(), sizes: Vec::new(), sentence_deps: HashMap::new(), predicate_deps: HashMap::new() };
+        memo.build(root, &mut Vec::new(), false);
+        for deps in memo.sentence_deps.values_mut().chain(memo.predicate_deps.values_mut()){
+            deps.sort_unstable();
+            deps.dedup();
+        }
+
+        memo
+    }
+
+    /// Numbers the nodes and works out which nodes depend on which sentences.
+    fn build(&mut self, node: &Node, ancestors: &mut Vec<usize>, quantified: bool){
+        let index = self.values.len();
+        self.values.push(AtomicU8::new(UNKNOWN));
+        self.sizes.push(1);
+        ancestors.push(index);
+        match node{
+            Node::Operator { left, right, .. } => {
+                self.build(left, ancestors, quantified);
+                self.build(right, ancestors, quantified);
+            },
+            Node::Quantifier { subexpr, .. } => self.build(subexpr, ancestors, true),
+            Node::Sentence { sen, .. } => {
+                //a sentence inside a quantifier could end up being any sentence with the same predicate
+                let deps = if quantified{
+                    self.predicate_deps.entry(sen.predicate().clone()).or_default()
+                }else{
+                    self.sentence_deps.entry(sen.clone()).or_default()
+                };
+                deps.extend(ancestors.iter().copied());
+            },
+            Node::Constant(..) => (),
+        }
+        ancestors.pop();
+        self.sizes[index] = self.values.len() - index;
+    }
+
+    /// Forgets the value of every node that depends on the sentence.
+    pub(crate) fn invalidate(&mut self, sentence: &Sentence){
+        let deps = self.sentence_deps.get(sentence).into_iter().chain(self.predicate_deps.get(sentence.predicate()));
+        for i in deps.flatten(){
+            *self.values[*i].get_mut() = UNKNOWN;
+        }
+    }
+
+    /// `invalidate()`, but for a memo that's shared.
+    pub(crate) fn forget(&self, sentence: &Sentence){
+        let deps = self.sentence_deps.get(sentence).into_iter().chain(self.predicate_deps.get(sentence.predicate()));
+        for i in deps.flatten(){
+            self.values[*i].store(UNKNOWN, Ordering::Relaxed);
+        }
+    }
+
+    /// Forgets the value of every node.
+    pub(crate) fn forget_all(&self){
+        for value in self.values.iter(){
+            value.store(UNKNOWN, Ordering::Relaxed);
+        }
+    }
+
+    /// Flips the remembered value of the root (for when the whole tree gets denied).
+    pub(crate) fn negate_root(&mut self){
+        let root = self.values[0].get_mut();
+        *root = match *root{
+            TRUE => FALSE,
+            FALSE => TRUE,
+            _ => UNKNOWN,
+        };
+    }
+
+    /// Evaluates the tree, reusing every value that is still remembered.
+    pub(crate) fn evaluate(&self, root: &Node, uni: &Universe) -> Result<bool, ClawgicError>{
+        self.evaluate_rec(root, 0, uni)
+    }
+
+    /// Recursive helper for `Memo::evaluate()`.
+    fn evaluate_rec(&self, node: &Node, index: usize, uni: &Universe) -> Result<bool, ClawgicError>{
+        match self.values[index].load(Ordering::Relaxed){
+            TRUE => return Ok(true),
+            FALSE => return Ok(false),
+            _ => (),
+        }
+
+        let result = match node{
+            Node::Operator { neg, op, left, right } => {
+                let left_result = self.evaluate_rec(left, index + 1, uni)?;
+                let result = match op.short_circuit(left_result){
+                    Some(b) => b,
+                    None => op.execute_binary(left_result, self.evaluate_rec(right, index + 1 + self.sizes[index + 1], uni)?),
+                };
+                result != neg.is_denied()
+            },
+            _ => node.evaluate(uni, &mut HashMap::new())?,
+        };
+        self.values[index].store(if result {TRUE} else {FALSE}, Ordering::Relaxed);
+
+        Ok(result)
+    }
+}
+
+impl Clone for Memo{
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.iter().map(|v| AtomicU8::new(v.load(Ordering::Relaxed))).collect(),
+            sizes: self.sizes.clone(),
+            sentence_deps: self.sentence_deps.clone(),
+            predicate_deps: self.predicate_deps.clone(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::budget::Meter;
+use crate::expression_tree::node::Node;
+use crate::expression_tree::node::negation::Negation;
+use crate::prelude::{Operator, Sentence};
+use crate::ClawgicError;
+
+/// A product of literals over the atoms of a truth table.
+///
+/// Atom `i` is left out of the product if bit `i` of `mask` is set.
+/// Otherwise it shows up plain if bit `i` of `bits` is set and denied if it isn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct Implicant{
+    bits: u64,
+    mask: u64,
+}
+
+impl Implicant{
+    /// Whether the row is covered by the product.
+    fn covers(&self, row: u64) -> bool{
+        row & !self.mask == self.bits
+    }
+
+    /// Number of literals in the product.
+    fn literals(&self, atoms: usize) -> u32{
+        atoms as u32 - self.mask.count_ones()
+    }
+}
+
+/// Finds a small sum of products that is true on every row of `on`, false on every row not in `on` or `dont_care`,
+/// and anything on the rows of `dont_care` (Quine-McCluskey, then essential primes and a greedy cover).
+/// Every implicant looked at (while merging or covering) is a step.
+pub(crate) fn minimize(atoms: usize, on: &[u64], dont_care: &[u64], meter: &mut Meter) -> Result<Vec<Implicant>, ClawgicError>{
+    let primes = prime_implicants(atoms, on.iter().chain(dont_care.iter()).copied(), meter)?;
+    cover(atoms, &primes, on, meter)
+}
+
+/// Turns a sum of products back into a node.
+pub(crate) fn to_node(implicants: &[Implicant], atoms: &[Sentence]) -> Node{
+    let terms = implicants.iter().map(|imp| {
+        let literals = atoms.iter().enumerate()
+            .filter(|(i, _)| (imp.mask >> i) & 1 == 0)
+            .map(|(i, sen)| Node::Sentence { neg: Negation::new(if (imp.bits >> i) & 1 == 1 {0} else {1}), sen: sen.clone() });
+        join(literals, Operator::AND).unwrap_or(Node::Constant(Negation::default(), true))
+    });
+
+    join(terms, Operator::OR).unwrap_or(Node::Constant(Negation::default(), false))
+}
+
+/// Builds the canonical sum of minterms (or product of maxterms) over the atoms.
+///
+/// Atom `i` is true in term `t` iff bit `atoms.len() - 1 - i` of `t` is set (the first atom is the most significant bit).
+pub(crate) fn canonical(terms: &[u64], atoms: &[Sentence], minterms: bool) -> Node{
+    let (inner, outer) = if minterms {(Operator::AND, Operator::OR)} else {(Operator::OR, Operator::AND)};
+    let terms = terms.iter().map(|t| {
+        let literals = atoms.iter().enumerate().map(|(i, sen)| {
+            let set = t.checked_shr((atoms.len() - 1 - i) as u32).unwrap_or(0) & 1 == 1;
+            //minterms have the atom plain where it's true, maxterms where it's false
+            Node::Sentence { neg: Negation::new((set != minterms) as u32), sen: sen.clone() }
+        });
+        join(literals, inner).unwrap_or(Node::Constant(Negation::default(), minterms))
+    });
+
+    join(terms, outer).unwrap_or(Node::Constant(Negation::default(), !minterms))
+}
+
+/// Joins the nodes together (from left to right) with the given operator.
+pub(crate) fn join<It: Iterator<Item = Node>>(mut nodes: It, op: Operator) -> Option<Node>{
+    let first = nodes.next()?;
+    Some(nodes.fold(first, |left, right| Node::Operator {
+        neg: Negation::default(),
+        op,
+        left: Arc::new(left),
+        right: Arc::new(right),
+    }))
+}
+
+/// Finds every prime implicant of the given rows by repeatedly merging products that differ in one atom.
+fn prime_implicants<It: Iterator<Item = u64>>(atoms: usize, rows: It, meter: &mut Meter) -> Result<Vec<Implicant>, ClawgicError>{
+    let mut current: HashSet<Implicant> = rows.map(|bits| Implicant { bits, mask: 0 }).collect();
+    let mut primes = Vec::new();
+    while !current.is_empty(){
+        let mut next = HashSet::new();
+        let mut merged = HashSet::new();
+        for imp in current.iter(){
+            meter.step(1)?;
+            for i in 0..atoms{
+                let bit = 1 << i;
+                if imp.mask & bit != 0 || imp.bits & bit != 0{
+                    continue;
+                }
+                let partner = Implicant { bits: imp.bits | bit, mask: imp.mask };
+                if current.contains(&partner){
+                    next.insert(Implicant { bits: imp.bits, mask: imp.mask | bit });
+                    merged.insert(*imp);
+                    merged.insert(partner);
+                }
+            }
+        }
+        primes.extend(current.into_iter().filter(|imp| !merged.contains(imp)));
+        current = next;
+    }
+
+    primes.sort();
+    Ok(primes)
+}
+
+/// Picks prime implicants that cover every row of `on`.
+/// Takes the essential ones first, then keeps taking whichever covers the most rows that are left.
+fn cover(atoms: usize, primes: &[Implicant], on: &[u64], meter: &mut Meter) -> Result<Vec<Implicant>, ClawgicError>{
+    let mut chosen: Vec<Implicant> = Vec::new();
+    for row in on.iter(){
+        let mut covering = primes.iter().filter(|p| p.covers(*row));
+        if let (Some(p), None) = (covering.next(), covering.next()) && !chosen.contains(p){
+            chosen.push(*p);
+        }
+    }
+
+    let mut left: Vec<u64> = on.iter().copied().filter(|row| !chosen.iter().any(|p| p.covers(*row))).collect();
+    while !left.is_empty(){
+        meter.step(primes.len() as u64)?;
+        let best = primes.iter()
+            .max_by_key(|p| (left.iter().filter(|row| p.covers(**row)).count(), std::cmp::Reverse(p.literals(atoms))))
+            .copied()
+            .unwrap();
+        left.retain(|row| !best.covers(*row));
+        chosen.push(best);
+    }
+
+    chosen.sort();
+    Ok(chosen)
+}
@@ -2,10 +2,10 @@ pub mod operator;
 pub mod negation;
 pub mod sentence;
 
-use std::{collections::HashMap, mem::swap};
+use std::{collections::HashMap, mem::swap, sync::Arc};
 
 use operator::Operator;
-use crate::{expression_tree::{ClawgicError, node::negation::Negation, universe::Universe}, operator_notation::OperatorNotation, prelude::{ExpressionVar, Sentence}, utils};
+use crate::{expression_tree::{ClawgicError, node::negation::Negation, universe::Universe}, operator_notation::OperatorNotation, prelude::{Assignment, ExpressionVar, Sentence}, utils};
 
 /// Nodes for regular logical expression tree.
 /// 
@@ -13,7 +13,13 @@ use crate::{expression_tree::{ClawgicError, node::negation::Negation, universe::
 /// 
 /// Since there is only one unary operator in SL (~ - denial operator), it doesn't
 /// get its own enum type and instead is imbedded as a boolean value in operators and variables.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+/// 
+/// Nodes are totally ordered (so collections of them can be sorted deterministically):
+/// operators come before quantifiers, which come before sentences, which come before constants.
+/// Nodes of the same kind are compared field by field in the order they're declared
+/// (i.e. operators by negation count, then `Operator`, then left operand, then right operand),
+/// sentences by predicate name, then arity, then variable names, and false comes before true.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Node{
     /// Binary operator node.
     Operator{
@@ -22,9 +28,9 @@ pub enum Node{
         /// the type of operator. (exclusively a binary operator)
         op: Operator,
         /// left operand.
-        left: Box<Node>,
+        left: Arc<Node>,
         /// right operand.
-        right: Box<Node>,
+        right: Arc<Node>,
     },
     /// Quantifier node.
     Quantifier{
@@ -35,7 +41,7 @@ pub enum Node{
         /// variables bound by the quantifier.
         vars: Vec<ExpressionVar>,
         /// subexpression contained within quantifier.
-        subexpr: Box<Node>,
+        subexpr: Arc<Node>,
     },
     /// Sentence node.
     Sentence{
@@ -73,6 +79,48 @@ impl Node{
         }
     }
 
+    /// Whether it is a literal (a sentence or constant with at most one tilde).
+    pub fn is_literal(&self) -> bool{
+        (self.is_sentence() || self.is_constant()) && self.negation().count() <= 1
+    }
+
+    /// Whether it is in negation normal form: only conjunctions, disjunctions, and quantifiers, with tildes only on literals.
+    pub fn is_nnf(&self) -> bool{
+        match self{
+            Self::Operator { neg, op, left, right } => neg.count() == 0 && (op.is_and() || op.is_or()) && left.is_nnf() && right.is_nnf(),
+            Self::Quantifier { neg, subexpr, .. } => neg.count() == 0 && subexpr.is_nnf(),
+            Self::Sentence { .. } | Self::Constant(..) => self.is_literal(),
+        }
+    }
+
+    /// Whether it is in conjunctive normal form (a conjunction of disjunctions of literals).
+    pub fn is_cnf(&self) -> bool{
+        self.is_chain_of(Operator::AND, |clause| clause.is_chain_of(Operator::OR, Self::is_literal))
+    }
+
+    /// Whether it is in disjunctive normal form (a disjunction of conjunctions of literals).
+    pub fn is_dnf(&self) -> bool{
+        self.is_chain_of(Operator::OR, |term| term.is_chain_of(Operator::AND, Self::is_literal))
+    }
+
+    /// Whether it is some number of the (undenied) operator joining operands that pass the check (or just one such operand).
+    fn is_chain_of(&self, op: Operator, operand: fn(&Self) -> bool) -> bool{
+        match self{
+            Self::Operator { neg, op: o, left, right } if neg.count() == 0 && *o == op => left.is_chain_of(op, operand) && right.is_chain_of(op, operand),
+            _ => operand(self),
+        }
+    }
+
+    /// Gets the leading tildes of the node.
+    pub fn negation(&self) -> Negation{
+        match self{
+            Self::Operator { neg, .. } => *neg,
+            Self::Quantifier { neg, .. } => *neg,
+            Self::Sentence { neg, .. } => *neg,
+            Self::Constant(neg, _) => *neg,
+        }
+    }
+
     /// Attempts to get the boolean value of the node.
     /// 
     /// A constant node will just return it's value
@@ -216,8 +264,8 @@ impl Node{
                 if op.is_and() || op.is_or(){
                     *op = if op.is_and() {Operator::OR} else {Operator::AND};
                     denied.deny();
-                    left.deny();
-                    right.deny();
+                    Arc::make_mut(left).deny();
+                    Arc::make_mut(right).deny();
                     return Some(self);
                 }
             },
@@ -238,8 +286,8 @@ impl Node{
                 if op.is_and() || op.is_or(){
                     *op = if op.is_and() {Operator::OR} else {Operator::AND};
                     denied.negate();
-                    left.negate();
-                    right.negate();
+                    Arc::make_mut(left).negate();
+                    Arc::make_mut(right).negate();
                     return Some(self);
                 }
             },
@@ -256,8 +304,8 @@ impl Node{
         let Node::Operator { neg: _, op, left, right } = self
             else {return None};
         if op.is_con(){
-            left.deny();
-            right.deny();
+            Arc::make_mut(left).deny();
+            Arc::make_mut(right).deny();
             swap(left, right);
             return Some(self);
         }
@@ -274,8 +322,8 @@ impl Node{
         let Node::Operator { neg: _, op, left, right } = self
             else {return None};
         if op.is_con(){
-            left.negate();
-            right.negate();
+            Arc::make_mut(left).negate();
+            Arc::make_mut(right).negate();
             swap(left, right);
             return Some(self);
         }
@@ -290,7 +338,7 @@ impl Node{
             Node::Operator { neg: _, op, left, right: _ } => {
                 if op.is_con() || op.is_or(){
                     *op =  if op.is_con() {Operator::OR} else {Operator::CON};
-                    left.deny();
+                    Arc::make_mut(left).deny();
                     return Some(self);
                 }
             },
@@ -309,7 +357,7 @@ impl Node{
             Node::Operator { neg: _, op, left, right: _ } => {
                 if op.is_con() || op.is_or(){
                     *op =  if op.is_con() {Operator::OR} else {Operator::CON};
-                    left.negate();
+                    Arc::make_mut(left).negate();
                     return Some(self);
                 }
             },
@@ -328,7 +376,7 @@ impl Node{
                 if op.is_con() || op.is_and(){
                     *op = if op.is_con() {Operator::AND} else {Operator::CON};
                     denied.deny();
-                    right.deny();
+                    Arc::make_mut(right).deny();
                     return Some(self);
                 }
             },
@@ -349,7 +397,7 @@ impl Node{
                 if op.is_con() || op.is_and(){
                     *op = if op.is_con() {Operator::AND} else {Operator::CON};
                     denied.negate();
-                    right.negate();
+                    Arc::make_mut(right).negate();
                     return Some(self);
                 }
             },
@@ -368,13 +416,13 @@ impl Node{
                     *op = Operator::AND;
                     let old_left = left.clone();
                     let old_right = right.clone();
-                    *left = Box::new(Node::Operator { neg: Negation::default(), op: Operator::CON, left: old_left.clone(), right: old_right.clone() });
-                    *right = Box::new(Node::Operator { neg: Negation::default(), op: Operator::CON, left: old_right, right: old_left });
+                    *left = Arc::new(Node::Operator { neg: Negation::default(), op: Operator::CON, left: old_left.clone(), right: old_right.clone() });
+                    *right = Arc::new(Node::Operator { neg: Negation::default(), op: Operator::CON, left: old_right, right: old_left });
 
                     return Some(self);
                 }else if op.is_and(){
-                    if let Node::Operator{neg: ld, op: l_op, left: ll, right: lr} = *left.clone(){
-                        if let Node::Operator { neg: rd, op: r_op, left: rl, right: rr } = *right.clone(){
+                    if let Node::Operator{neg: ld, op: l_op, left: ll, right: lr} = (**left).clone(){
+                        if let Node::Operator { neg: rd, op: r_op, left: rl, right: rr } = (**right).clone(){
                             if l_op.is_con() && r_op.is_con() && !ld.is_denied() && !rd.is_denied() && ll == rr && lr == rl{
                                 *op = Operator::BICON;
                                 *left = ll;
@@ -406,16 +454,16 @@ impl Node{
                     if denied.is_denied(){
                         denied.deny();
                         if old_left < old_right{
-                            old_left.deny();
+                            Arc::make_mut(&mut old_left).deny();
                         }
                         else{
-                            old_right.deny();
+                            Arc::make_mut(&mut old_right).deny();
                         }
                     }
-                    *left = Box::new(Node::Operator { neg: Negation::default(), op: Operator::AND, left: old_left.clone(), right: old_right.clone() });
-                    old_left.deny();
-                    old_right.deny();
-                    *right = Box::new(Node::Operator { neg: Negation::default(), op: Operator::AND, left: old_left, right: old_right });
+                    *left = Arc::new(Node::Operator { neg: Negation::default(), op: Operator::AND, left: old_left.clone(), right: old_right.clone() });
+                    Arc::make_mut(&mut old_left).deny();
+                    Arc::make_mut(&mut old_right).deny();
+                    *right = Arc::new(Node::Operator { neg: Negation::default(), op: Operator::AND, left: old_left, right: old_right });
                     return Some(self);
                 }
             },
@@ -430,7 +478,7 @@ impl Node{
             Node::Quantifier { neg, op, subexpr, .. } => {
                 neg.deny();
                 *op = if op.is_uni(){ Operator::EXI } else { Operator::UNI };
-                subexpr.deny();
+                Arc::make_mut(subexpr).deny();
                 return Some(self);
             },
             _ => (),
@@ -446,7 +494,7 @@ impl Node{
             Node::Quantifier { neg, op, subexpr, .. } => {
                 neg.negate();
                 *op = if op.is_uni(){ Operator::EXI } else { Operator::UNI };
-                subexpr.negate();
+                Arc::make_mut(subexpr).negate();
                 return Some(self);
             },
             _ => (),
@@ -454,6 +502,75 @@ impl Node{
         None
     }
 
+    /// Returns the truth value of the node if it is a constant (tildes included).
+    pub fn constant_value(&self) -> Option<bool>{
+        match self{
+            Self::Constant(neg, b) => Some(neg.is_denied() != *b),
+            _ => None,
+        }
+    }
+
+    /// Replaces every sentence with a known truth value with a constant and then
+    /// folds away all of the constants that it can; returns a mutable reference.
+    ///
+    /// Sentences that use a variable bound by an enclosing quantifier are left alone.
+    pub fn restrict(&mut self, tvals: &Assignment) -> &mut Self{
+        self.restrict_rec(tvals, &mut Vec::new());
+        self
+    }
+
+    /// Recursive helper function for `Node::restrict()`.
+    fn restrict_rec(&mut self, tvals: &Assignment, bound: &mut Vec<ExpressionVar>){
+        match self{
+            Self::Sentence { neg, sen } => {
+                if sen.vars().iter().any(|v| bound.contains(v)){
+                    return;
+                }
+                if let Some(b) = tvals.get(sen){
+                    *self = Self::Constant(*neg, b);
+                }
+            },
+            Self::Operator { neg, op, left, right } => {
+                Arc::make_mut(left).restrict_rec(tvals, bound);
+                Arc::make_mut(right).restrict_rec(tvals, bound);
+                let (constant, mut other, const_on_left) = match (left.constant_value(), right.constant_value()){
+                    (Some(l), Some(r)) => {
+                        *self = Self::Constant(*neg, op.execute_binary(l, r));
+                        return;
+                    },
+                    (Some(l), None) => (l, (**right).clone(), true),
+                    (None, Some(r)) => (r, (**left).clone(), false),
+                    (None, None) => return,
+                };
+
+                //figure out what the operator does to the non-constant side.
+                let (when_true, when_false) = if const_on_left{
+                    (op.execute_binary(constant, true), op.execute_binary(constant, false))
+                }else{
+                    (op.execute_binary(true, constant), op.execute_binary(false, constant))
+                };
+                if when_true == when_false{
+                    *self = Self::Constant(*neg, when_true);
+                    return;
+                }
+                if when_true == neg.is_denied(){
+                    other.deny();
+                }
+                *self = other;
+            },
+            Self::Quantifier { neg, vars, subexpr, .. } => {
+                let pushed = vars.len();
+                bound.extend(vars.iter().cloned());
+                Arc::make_mut(subexpr).restrict_rec(tvals, bound);
+                bound.truncate(bound.len() - pushed);
+                if let Some(b) = subexpr.constant_value(){
+                    *self = Self::Constant(*neg, b);
+                }
+            },
+            Self::Constant(..) => (),
+        }
+    }
+
     ///Returns a string representation of the current node based on the given notation.
     pub fn print(&self, notation: &OperatorNotation) -> String{
         let mut s = String::new();
@@ -464,22 +581,25 @@ impl Node{
             }
             Self::Sentence { neg, sen, .. } => {
                 s.push_str(&notation[Operator::NOT].repeat(neg.count() as usize));
-                s.push_str(&sen.to_string());
+                if notation.subscripts(){
+                    s.push_str(&utils::to_subscripts(&sen.to_string()));
+                }else{
+                    s.push_str(&sen.to_string());
+                }
             }
             Self::Constant(neg, b) => {
                 s.push_str(&notation[Operator::NOT].repeat(neg.count() as usize));
-                s +=
-                if *b{
-                    "TRUE"
-                }else{
-                    "FALSE"
-                };
+                s.push_str(notation.get_constant_notation(*b));
             }
             Self::Quantifier { neg, op, vars, .. } => {
                 s.push_str(&notation[Operator::NOT].repeat(neg.count() as usize));
                 s.push_str(&notation[*op]);
                 let var_string: String = utils::print_variables_verbose(vars);
-                s.push_str(&var_string);
+                if notation.subscripts(){
+                    s.push_str(&utils::to_subscripts(&var_string));
+                }else{
+                    s.push_str(&var_string);
+                }
             }
         }
         s
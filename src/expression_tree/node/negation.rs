@@ -1,5 +1,5 @@
 ///Struct representing the number of tildes attached to something.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Negation{
     count: u32,
 }
@@ -6,15 +6,15 @@
 /// `Operator` is all encompassing and can be used for extra things.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Operator{
-    /// Negation. ~
+    /// Negation. ~, ∼
     NOT,
-    /// Conjunction. &, ^
+    /// Conjunction. &, ^, ∧ (denied: ⊼, ↑)
     AND,
-    /// Disjunction. v
+    /// Disjunction. v, ∨ (denied: ⊽, ↓)
     OR,
-    /// Conditional. ->
+    /// Conditional. ->, →, ⊃
     CON,
-    /// Biconditional. <->
+    /// Biconditional. <->, ↔, ≡ (denied: ⊕, ⊻)
     BICON,
     /// Universal. @
     UNI,
@@ -94,9 +94,9 @@ impl Operator{
         self.is_uni() || self.is_exi()
     }
 
-    /// Returns the precedence of the operator.
+    /// Returns the default precedence of the operator (see `ParserConfig::set_precedence()` to change it).
     /// 
-    /// Lower number is higher precedence.
+    /// Higher number binds tighter.
     /// 
     /// Precedence is as follows:
     /// * AND (conjunction): 3
@@ -150,10 +150,10 @@ impl Operator{
     /// ```
     /// use clawgic::expression_tree::node::operator::Operator;
     /// let op = Operator::AND;
-    /// assert!(op.execute(true, true));
-    /// assert!(!op.execute(true, false));
-    /// assert!(!op.execute(false, true));
-    /// assert!(!op.execute(false, false));
+    /// assert!(op.execute_binary(true, true));
+    /// assert!(!op.execute_binary(true, false));
+    /// assert!(!op.execute_binary(false, true));
+    /// assert!(!op.execute_binary(false, false));
     /// ```
     pub fn execute_binary(&self, left: bool, right: bool) -> bool{
         match self{
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr, ShrAssign}};
 
-use crate::{ClawgicError, prelude::{ExpressionTree, ExpressionVar}, utils};
+use crate::{ClawgicError, expression_tree::node::Node, parser_config::ParserConfig, prelude::{ExpressionTree, ExpressionVar}, utils};
 
 /// Predicate from prediccate (first order) logic.
 /// Has a name and an arity (number of vars that it takes).
@@ -17,12 +17,21 @@ impl Predicate{
     /// 
     /// Valid names are one uppercase letter followed by any number of digits.
     /// (i.e. "A", "B0", "C123") 
+    ///
+    /// Fails with `InvalidPredicateName` if the name isn't valid.
     pub fn new(name: &str, arity: usize) -> Result<Self, ClawgicError>{
-        if !utils::is_valid_predicate_name(name){
-            return Err(ClawgicError::InvalidVariableName(name.to_string()))
+        Self::new_with_config(name, arity, &ParserConfig::default())
+    }
+
+    /// Constructs a new `Predicate` iff the provided name is valid under the given `ParserConfig`.
+    /// Fails with `InvalidPredicateName` otherwise.
+    pub fn new_with_config(name: &str, arity: usize, config: &ParserConfig) -> Result<Self, ClawgicError>{
+        let name = utils::normalize_subscripts(name.trim());
+        if !config.is_valid_predicate_name(&name){
+            return Err(ClawgicError::InvalidPredicateName(name))
         }
 
-        Ok(Self{name: name.to_string(), arity})
+        Ok(Self{name, arity})
     }
 
     ///Gets the name of the predicate.
@@ -115,6 +124,18 @@ impl Sentence{
     }
 }
 
+impl TryFrom<&str> for Sentence{
+    type Error = ClawgicError;
+
+    /// Parses a single (un-denied) sentence (i.e. "A", "P(a, b)", "Rab").
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match ExpressionTree::new(value)?.into_node(){
+            Node::Sentence { neg, sen } if !neg.is_denied() => Ok(sen),
+            _ => Err(ClawgicError::InvalidExpression),
+        }
+    }
+}
+
 impl ToString for Sentence{
     fn to_string(&self) -> String {
         let vars = utils::print_variables_verbose(&self.vars);
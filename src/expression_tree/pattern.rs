@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::expression_tree::node::Node;
+use crate::prelude::{ExpressionTree, ExpressionVar, OperatorNotation, ParserConfig, Predicate, Sentence};
+use crate::ClawgicError;
+
+/// What each metavariable of a `Pattern` stands for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bindings{
+    map: BTreeMap<String, Node>,
+}
+
+impl Bindings{
+    /// Constructs an empty `Bindings`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Binds the metavariable (without the `?`) and returns the `Bindings`. Nicer for chaining.
+    pub fn with(mut self, metavar: &str, node: Node) -> Self{
+        self.map.insert(metavar.to_string(), node);
+        self
+    }
+
+    /// Binds the metavariable (without the `?`). If it was already bound, returns the old node.
+    pub fn insert(&mut self, metavar: &str, node: Node) -> Option<Node>{
+        self.map.insert(metavar.to_string(), node)
+    }
+
+    /// Gets what the metavariable (without the `?`) is bound to.
+    pub fn get(&self, metavar: &str) -> Option<&Node>{
+        self.map.get(metavar)
+    }
+
+    /// Number of bound metavariables.
+    pub fn len(&self) -> usize{
+        self.map.len()
+    }
+
+    /// Whether nothing is bound.
+    pub fn is_empty(&self) -> bool{
+        self.map.is_empty()
+    }
+
+    /// Iterates over all (metavariable, node) pairs in alphabetical order.
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, String, Node>{
+        self.map.iter()
+    }
+}
+
+/// An expression with holes in it.
+///
+/// Metavariables are written as a `?` followed by a lowercase letter and any number of digits (i.e. `?p`, `?q1`)
+/// and can stand for any subexpression. A metavariable with tildes in front of it only matches
+/// expressions with at least that many tildes.
+///
+/// A metavariable inside a quantifier never stands for an expression that uses one of that quantifier's variables
+/// freely (so `@x?p` doesn't match `@xPx`), since the quantifier would capture it.
+/// ```
+/// use clawgic::prelude::*;
+/// let modus_ponens = Pattern::new("?p&(?p->?q)").unwrap();
+/// let bindings = modus_ponens.match_against(ExpressionTree::new("(AvB)&((AvB)->~C)").unwrap().node()).unwrap();
+/// assert_eq!(ExpressionTree::from(bindings.get("q").unwrap().clone()), ExpressionTree::new("~C").unwrap());
+/// assert!(modus_ponens.match_against(ExpressionTree::new("A&(B->C)").unwrap().node()).is_none());
+///
+/// let conclusion = Pattern::new("?q").unwrap().instantiate(&bindings).unwrap();
+/// assert_eq!(conclusion, ExpressionTree::new("~C").unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern{
+    /// The pattern with every metavariable replaced by a placeholder sentence.
+    tree: ExpressionTree,
+    /// Maps each placeholder sentence to the name of its metavariable.
+    metavars: HashMap<Sentence, String>,
+}
+
+impl Pattern{
+    /// Constructs a new `Pattern` given a string representation of an infix logical expression with metavariables.
+    pub fn new(pattern: &str) -> Result<Self, ClawgicError>{
+        Self::new_with_config(pattern, None, &ParserConfig::default())
+    }
+
+    /// Constructs a new `Pattern` with an (optional) `OperatorNotation` and a `ParserConfig` (see `ExpressionTree::new_with_config()`).
+    pub fn new_with_config(pattern: &str, notation: Option<&OperatorNotation>, config: &ParserConfig) -> Result<Self, ClawgicError>{
+        //placeholders use a letter that doesn't show up anywhere else, so they can't clash with real sentences
+        let letter = ('A'..='Z').rev()
+            .filter(|c| *c != 'T' && *c != 'F')
+            .find(|c| !pattern.contains(*c))
+            .ok_or(ClawgicError::InvalidExpression)?;
+
+        let mut names: Vec<String> = Vec::new();
+        let mut expression = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next(){
+            if c != '?' || !chars.peek().is_some_and(|c| c.is_ascii_lowercase()){
+                expression.push(c);
+                continue;
+            }
+            let mut name = chars.next().unwrap().to_string();
+            while let Some(d) = chars.next_if(|d| d.is_ascii_digit()){
+                name.push(d);
+            }
+            let index = match names.iter().position(|n| *n == name){
+                Some(i) => i,
+                None => {
+                    names.push(name);
+                    names.len() - 1
+                },
+            };
+            expression.push_str(&format!("{letter}{index}"));
+        }
+
+        let tree = ExpressionTree::new_with_config(&expression, notation, config)?;
+        let mut metavars = HashMap::new();
+        for (i, name) in names.into_iter().enumerate(){
+            let sen = Predicate::new(&format!("{letter}{i}"), 0)?.inst(&vec![])?;
+            metavars.insert(sen, name);
+        }
+
+        Ok(Self { tree, metavars })
+    }
+
+    /// Gets the pattern as a tree (with a placeholder sentence in place of each metavariable).
+    pub(crate) fn tree(&self) -> &ExpressionTree{
+        &self.tree
+    }
+
+    /// Gets the name (without the `?`) of every metavariable in the pattern, in alphabetical order.
+    pub fn metavars(&self) -> Vec<&str>{
+        let mut names: Vec<&str> = self.metavars.values().map(|n| n.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Tries to match the pattern against the node.
+    /// If it matches, returns what every metavariable stands for. Otherwise returns `None`.
+    ///
+    /// Every occurrence of a metavariable has to stand for (literally) the same expression.
+    pub fn match_against(&self, node: &Node) -> Option<Bindings>{
+        let mut bindings = Bindings::new();
+        match self.match_rec(self.tree.node(), node, &mut bindings){
+            true => Some(bindings),
+            false => None,
+        }
+    }
+
+    /// Recursive helper for `Pattern::match_against()`. Matches part of the pattern (that isn't inside a quantifier)
+    /// against the node, on top of whatever is already bound.
+    pub(crate) fn match_rec(&self, pattern: &Node, node: &Node, bindings: &mut Bindings) -> bool{
+        self.match_scoped(pattern, node, bindings, &mut Vec::new())
+    }
+
+    /// `match_rec()` inside quantifiers of the pattern that bind the variables in `scope`.
+    fn match_scoped(&self, pattern: &Node, node: &Node, bindings: &mut Bindings, scope: &mut Vec<ExpressionVar>) -> bool{
+        match (pattern, node){
+            (Node::Sentence { neg, sen }, _) if self.metavars.contains_key(sen) => {
+                if node.negation().count() < neg.count() || uses_free(node, scope, &mut Vec::new()){
+                    return false;
+                }
+                let mut bound = node.clone();
+                for _ in 0..neg.count(){
+                    bound.deny();
+                }
+                let name = &self.metavars[sen];
+                match bindings.get(name){
+                    Some(existing) => *existing == bound,
+                    None => {
+                        bindings.insert(name, bound);
+                        true
+                    },
+                }
+            },
+            (Node::Operator { neg: pneg, op: pop, left: pleft, right: pright }, Node::Operator { neg, op, left, right }) => {
+                pneg == neg && pop == op && self.match_scoped(pleft, left, bindings, scope) && self.match_scoped(pright, right, bindings, scope)
+            },
+            (Node::Quantifier { neg: pneg, op: pop, vars: pvars, subexpr: psubexpr }, Node::Quantifier { neg, op, vars, subexpr }) => {
+                if pneg != neg || pop != op || pvars != vars{
+                    return false;
+                }
+                scope.extend(vars.iter().cloned());
+                let matched = self.match_scoped(psubexpr, subexpr, bindings, scope);
+                scope.truncate(scope.len() - vars.len());
+                matched
+            },
+            _ => pattern == node,
+        }
+    }
+
+    /// Fills in every metavariable with what it's bound to.
+    ///
+    /// Fails with `UnboundMetavariable` if one of them isn't bound, and with `CapturedVariable` if one of them
+    /// is inside a quantifier that would capture a variable used freely by what it's bound to.
+    pub fn instantiate(&self, bindings: &Bindings) -> Result<ExpressionTree, ClawgicError>{
+        Ok(ExpressionTree::from(self.instantiate_rec(self.tree.node(), bindings)?))
+    }
+
+    /// Recursive helper for `Pattern::instantiate()`. Fills in part of the pattern (that isn't inside a quantifier).
+    pub(crate) fn instantiate_rec(&self, node: &Node, bindings: &Bindings) -> Result<Node, ClawgicError>{
+        self.instantiate_scoped(node, bindings, &mut Vec::new())
+    }
+
+    /// `instantiate_rec()` inside quantifiers of the pattern that bind the variables in `scope`.
+    fn instantiate_scoped(&self, node: &Node, bindings: &Bindings, scope: &mut Vec<ExpressionVar>) -> Result<Node, ClawgicError>{
+        match node{
+            Node::Operator { neg, op, left, right } => Ok(Node::Operator {
+                neg: *neg,
+                op: *op,
+                left: Arc::new(self.instantiate_scoped(left, bindings, scope)?),
+                right: Arc::new(self.instantiate_scoped(right, bindings, scope)?),
+            }),
+            Node::Quantifier { neg, op, vars, subexpr } => {
+                scope.extend(vars.iter().cloned());
+                let subexpr = self.instantiate_scoped(subexpr, bindings, scope);
+                scope.truncate(scope.len() - vars.len());
+                Ok(Node::Quantifier { neg: *neg, op: *op, vars: vars.clone(), subexpr: Arc::new(subexpr?) })
+            },
+            Node::Sentence { neg, sen } => match self.metavars.get(sen){
+                Some(name) => {
+                    let mut bound = bindings.get(name).ok_or_else(|| ClawgicError::UnboundMetavariable(name.clone()))?.clone();
+                    if uses_free(&bound, scope, &mut Vec::new()){
+                        return Err(ClawgicError::CapturedVariable(name.clone()));
+                    }
+                    for _ in 0..neg.count(){
+                        bound.negate();
+                    }
+                    Ok(bound)
+                },
+                None => Ok(node.clone()),
+            },
+            Node::Constant(..) => Ok(node.clone()),
+        }
+    }
+
+    /// Gets the infix representation of the pattern (with metavariables written as `?p`).
+    pub fn infix(&self, notation: Option<&OperatorNotation>) -> String{
+        let mut infix = self.tree.infix(notation);
+        //longest placeholders first so "Z1" doesn't eat the front of "Z10"
+        let mut metavars: Vec<(String, &String)> = self.metavars.iter().map(|(sen, name)| (sen.to_string(), name)).collect();
+        metavars.sort_by_key(|(placeholder, _)| std::cmp::Reverse(placeholder.len()));
+        for (placeholder, name) in metavars{
+            infix = infix.replace(&placeholder, &format!("?{name}"));
+        }
+
+        infix
+    }
+}
+
+/// Whether the node uses any of the variables freely (`inner` is every variable bound by a quantifier inside the node so far).
+fn uses_free(node: &Node, vars: &[ExpressionVar], inner: &mut Vec<ExpressionVar>) -> bool{
+    if vars.is_empty(){
+        return false;
+    }
+    match node{
+        Node::Operator { left, right, .. } => uses_free(left, vars, inner) || uses_free(right, vars, inner),
+        Node::Quantifier { vars: bound, subexpr, .. } => {
+            inner.extend(bound.iter().cloned());
+            let uses = uses_free(subexpr, vars, inner);
+            inner.truncate(inner.len() - bound.len());
+            uses
+        },
+        Node::Sentence { sen, .. } => sen.vars().iter().any(|v| vars.contains(v) && !inner.contains(v)),
+        Node::Constant(..) => false,
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::expression_tree::node::Node;
+use crate::expression_tree::universe::Universe;
+use crate::prelude::{ExpressionTree, ExpressionVar, Sentence};
+
+/// Maps sentences to the expressions that should replace them.
+///
+/// A `Substitution` is applied simultaneously: every sentence is replaced in a single pass,
+/// so the replacement expressions are never substituted into themselves.
+/// It borrows the target tree, so one `Substitution` can be applied to as many trees as you want.
+/// ```
+/// use clawgic::prelude::*;
+/// let a = Predicate::new("A", 0).unwrap().inst(&vec![]).unwrap();
+/// let b = Predicate::new("B", 0).unwrap().inst(&vec![]).unwrap();
+/// let sub = Substitution::new()
+///     .with(a.clone(), b.expr())
+///     .with(b, a.expr());
+/// let tree = ExpressionTree::new("A->B").unwrap();
+/// assert!(sub.apply(&tree).lit_eq(&ExpressionTree::new("B->A").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Substitution{
+    map: HashMap<Sentence, ExpressionTree>,
+}
+
+impl Substitution{
+    /// Constructs an empty `Substitution`.
+    pub fn new() -> Self{
+        Self { map: HashMap::new() }
+    }
+
+    /// Adds a replacement and returns the `Substitution`. Nicer for chaining.
+    pub fn with(mut self, sentence: Sentence, expression: ExpressionTree) -> Self{
+        self.map.insert(sentence, expression);
+        self
+    }
+
+    /// Adds a replacement. If the sentence already had one, returns the old replacement.
+    pub fn insert(&mut self, sentence: Sentence, expression: ExpressionTree) -> Option<ExpressionTree>{
+        self.map.insert(sentence, expression)
+    }
+
+    /// Removes the replacement for the sentence (if there is one) and returns it.
+    pub fn remove(&mut self, sentence: &Sentence) -> Option<ExpressionTree>{
+        self.map.remove(sentence)
+    }
+
+    /// Gets the replacement for the sentence.
+    pub fn get(&self, sentence: &Sentence) -> Option<&ExpressionTree>{
+        self.map.get(sentence)
+    }
+
+    /// Whether the sentence has a replacement.
+    pub fn contains(&self, sentence: &Sentence) -> bool{
+        self.map.contains_key(sentence)
+    }
+
+    /// Number of replacements.
+    pub fn len(&self) -> usize{
+        self.map.len()
+    }
+
+    /// Whether there are no replacements.
+    pub fn is_empty(&self) -> bool{
+        self.map.is_empty()
+    }
+
+    /// Iterates over all (sentence, replacement) pairs.
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, Sentence, ExpressionTree>{
+        self.map.iter()
+    }
+
+    /// Returns the result of applying the substitution to the tree.
+    ///
+    /// Sentences that use a variable bound by an enclosing quantifier are left alone.
+    /// Truth values known by the tree (or by the replacements) are carried over for every sentence that's left.
+    pub fn apply(&self, tree: &ExpressionTree) -> ExpressionTree{
+        let root = self.apply_node(tree.node());
+        let mut uni = ExpressionTree::create_uni(&root, Universe::new());
+        uni.inherit_tvals(tree.universe());
+        for replacement in self.map.values(){
+            uni.inherit_tvals(replacement.universe());
+        }
+
+        ExpressionTree::from_parts(uni, root)
+    }
+
+    /// Returns the result of applying the substitution to the node.
+    ///
+    /// Subtrees that don't change are shared with the original node instead of being copied.
+    pub fn apply_node(&self, node: &Node) -> Node{
+        self.apply_rec(node, &mut Vec::new()).unwrap_or_else(|| node.clone())
+    }
+
+    /// Recursive helper function for `Substitution::apply_node()`. Returns `None` if nothing in the node changes.
+    fn apply_rec(&self, node: &Node, bound: &mut Vec<ExpressionVar>) -> Option<Node>{
+        match node{
+            Node::Sentence { neg, sen } => {
+                if sen.vars().iter().any(|v| bound.contains(v)){
+                    return None;
+                }
+                let mut replacement = self.map.get(sen)?.node().clone();
+                if neg.is_denied(){
+                    replacement.deny();
+                }
+                Some(replacement)
+            },
+            Node::Operator { neg, op, left, right } => {
+                let (new_left, new_right) = (self.apply_rec(left, bound), self.apply_rec(right, bound));
+                if new_left.is_none() && new_right.is_none(){
+                    return None;
+                }
+                Some(Node::Operator {
+                    neg: *neg,
+                    op: *op,
+                    left: new_left.map(Arc::new).unwrap_or_else(|| left.clone()),
+                    right: new_right.map(Arc::new).unwrap_or_else(|| right.clone()),
+                })
+            },
+            Node::Quantifier { neg, op, vars, subexpr } => {
+                let pushed = vars.len();
+                bound.extend(vars.iter().cloned());
+                let new_subexpr = self.apply_rec(subexpr, bound);
+                bound.truncate(bound.len() - pushed);
+                Some(Node::Quantifier { neg: *neg, op: *op, vars: vars.clone(), subexpr: Arc::new(new_subexpr?) })
+            },
+            Node::Constant(..) => None,
+        }
+    }
+
+    /// Returns the substitution that has the same effect as applying `self` and then `other`.
+    ///
+    /// `other` gets applied to all of `self`'s replacements, and any of `other`'s replacements
+    /// for sentences that `self` doesn't touch are kept as they are.
+    pub fn compose(&self, other: &Substitution) -> Substitution{
+        let mut map: HashMap<Sentence, ExpressionTree> = self.map.iter()
+            .map(|(sen, expr)| (sen.clone(), other.apply(expr)))
+            .collect();
+        for (sen, expr) in other.map.iter(){
+            map.entry(sen.clone()).or_insert_with(|| expr.clone());
+        }
+
+        Substitution { map }
+    }
+}
+
+impl FromIterator<(Sentence, ExpressionTree)> for Substitution{
+    fn from_iter<T: IntoIterator<Item = (Sentence, ExpressionTree)>>(iter: T) -> Self {
+        Self { map: iter.into_iter().collect() }
+    }
+}
+
+impl From<HashMap<Sentence, ExpressionTree>> for Substitution{
+    fn from(map: HashMap<Sentence, ExpressionTree>) -> Self {
+        Self { map }
+    }
+}
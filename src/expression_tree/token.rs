@@ -1,29 +1,79 @@
-use crate::{expression_tree::node::negation::Negation, prelude::{ExpressionVar, Predicate}};
+use crate::expression_tree::node::negation::Negation;
 
 use super::node::operator::Operator;
 
 /// This is a data type made for the shunting yard algorithm. 
 /// 
-/// It represents the tokens of an infix logical expression. 
-#[derive(Debug)]
-pub enum Token{
+/// It represents the tokens of an infix logical expression. Names borrow from the expression.
+#[derive(Debug, Clone, Copy)]
+pub enum Token<'a>{
     /// Binary logical operator.
     Operator(Negation, Operator),
-    /// Boolean Variable.
-    Sentence(Negation, Predicate, Vec<ExpressionVar>),
+    /// Boolean Variable (the name of its predicate and its arguments).
+    Sentence(Negation, &'a str, Args<'a>),
     /// Boolean constant. True or False.
     Constant(Negation, bool),
     /// Unary quantifier
-    Quantifier(Negation, Operator, Vec<ExpressionVar>),
+    Quantifier(Negation, Operator, Args<'a>),
     /// Open Parentheses.
     OpenParenthesis,
     ///Closed Parantheses.
     ClosedParenthesis,
     /// Boolean denial operator.
     Tilde(Negation),
+    /// Conjunction implied by two operands being next to each other. Binds tighter than any binary operator.
+    ImplicitAnd,
 }
 
-impl Token{
+/// Variables of a sentence or quantifier, as written in the expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Args<'a>{
+    /// In the form `x1y2...` (possibly empty).
+    Run(&'a str),
+    /// In the form `(x1, y2, ...)`, without the parentheses.
+    List(&'a str),
+}
+
+impl<'a> Args<'a>{
+    /// No variables.
+    pub const NONE: Self = Self::Run("");
+
+    /// Whether there are no variables.
+    pub fn is_empty(&self) -> bool{
+        match self{
+            Self::Run(s) => s.is_empty(),
+            Self::List(s) => s.trim().is_empty(),
+        }
+    }
+
+    /// Number of variables.
+    pub fn len(&self) -> usize{
+        self.vars().count()
+    }
+
+    /// Every variable name, in order.
+    pub fn vars(self) -> impl Iterator<Item = &'a str>{
+        let mut rest = if self.is_empty() {None} else {Some(self)};
+        std::iter::from_fn(move || {
+            let (var, tail) = match rest?{
+                Self::List(s) => match s.split_once(','){
+                    Some((var, tail)) => (var.trim(), Some(Self::List(tail))),
+                    None => (s.trim(), None),
+                },
+                Self::Run(s) => {
+                    //every variable is a letter followed by digits
+                    let first = s.chars().next()?.len_utf8();
+                    let end = s[first..].find(|c: char| !c.is_numeric()).map_or(s.len(), |i| first + i);
+                    (&s[..end], if end < s.len() {Some(Self::Run(&s[end..]))} else {None})
+                },
+            };
+            rest = tail;
+            Some(var)
+        })
+    }
+}
+
+impl Token<'_>{
     /// Whether the `Shell` is an `Operator`.
     pub fn is_operator(&self) -> bool{
         match self{
@@ -78,4 +128,14 @@ impl Token{
             _ => false,
         }
     }
+
+    /// Whether an operand can end with this token.
+    pub fn ends_operand(&self) -> bool{
+        self.is_sentence() || self.is_constant() || self.is_closed_parentheses()
+    }
+
+    /// Whether an operand can start with this token.
+    pub fn starts_operand(&self) -> bool{
+        self.is_sentence() || self.is_constant() || self.is_open_parentheses() || self.is_tilde() || self.is_quantifier()
+    }
 }
\ No newline at end of file
@@ -203,6 +203,19 @@ impl Universe{
         );
     }
 
+    ///Copies every known truth value in other whose predicate is also in self.
+    ///If there are conflicts, defaults to other's values.
+    pub fn inherit_tvals(&mut self, other: &Universe){
+        for (pred, sentences) in other.predicates.iter(){
+            if self.contains_predicate(pred){
+                for (sen, b) in sentences.iter(){
+                    self.insert_variables(sen.vars().iter().cloned());
+                    self.insert_sentence(sen.clone(), *b);
+                }
+            }
+        }
+    }
+
     ///Makes self entirely distinct from other.
     pub fn subtract_universe(&mut self, other: &Universe){
         self.remove_variables(other.variables.iter().cloned());
@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+
+use crate::expression_tree::memo::Memo;
+use crate::expression_tree::node::Node;
+use crate::expression_tree::universe::Universe;
+use crate::prelude::{Assignment, Sentence};
+use crate::ClawgicError;
+
+/// Truth values shared by every `ExpressionTree` attached to it (see `ExpressionTree::attach()`).
+///
+/// Setting a sentence on the context sets it for every attached tree at once, and only throws away the parts
+/// of their cached evaluations that depend on it. Clones of a `VarContext` are handles to the same context.
+///
+/// The context is one shared universe, so the individuals of every attached tree are in the range of
+/// every attached tree's quantifiers (just like with `ExpressionTree::and()`).
+/// ```
+/// use clawgic::prelude::*;
+/// let context = VarContext::new();
+/// let mut wet = ExpressionTree::new("R->W").unwrap();
+/// let mut dry = ExpressionTree::new("~W").unwrap();
+/// wet.attach(&context);
+/// dry.attach(&context);
+/// context.set_variable("R", true).unwrap();
+/// context.set_variable("W", false).unwrap();
+/// assert!(!wet.evaluate().unwrap());
+/// assert!(dry.evaluate().unwrap());
+/// context.set_variable("W", true).unwrap();
+/// assert!(wet.evaluate().unwrap());
+/// assert!(!dry.evaluate().unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VarContext{
+    shared: Arc<RwLock<Shared>>,
+}
+
+/// Everything behind a `VarContext`.
+#[derive(Debug)]
+struct Shared{
+    /// Every individual and sentence of the attached trees, along with the current truth values.
+    uni: Universe,
+    /// What has changed for each attached tree since it last evaluated. Trees that are gone get dropped on the next change.
+    trees: Vec<Weak<Mutex<Stale>>>,
+}
+
+/// Sentences whose truth values changed since a tree last evaluated.
+#[derive(Debug, Clone, Default)]
+struct Stale{
+    sentences: HashSet<Sentence>,
+    /// Whether new individuals showed up (which changes what the quantifiers range over).
+    everything: bool,
+}
+
+impl Default for Shared{
+    fn default() -> Self {
+        Self { uni: Universe::new(), trees: Vec::new() }
+    }
+}
+
+impl Shared{
+    /// Tells every attached tree that the sentence changed (or everything, if there's no sentence).
+    fn mark(&mut self, sentence: Option<&Sentence>){
+        self.trees.retain(|tree| match tree.upgrade(){
+            Some(stale) => {
+                let mut stale = stale.lock().unwrap();
+                match sentence{
+                    Some(sen) => {stale.sentences.insert(sen.clone());},
+                    None => stale.everything = true,
+                }
+                true
+            },
+            None => false,
+        });
+    }
+
+    /// Sets the truth value of the sentence. Returns whether anything changed.
+    fn set_tval(&mut self, sentence: &Sentence, value: bool) -> bool{
+        if let Some(tval) = self.uni.get_tval_mut(sentence){
+            if *tval == value{
+                return false;
+            }
+            *tval = value;
+            self.mark(Some(sentence));
+            return true;
+        }
+        let new_individual = sentence.vars().iter().any(|v| !self.uni.variables().contains(v));
+        self.uni.insert_variables(sentence.vars().iter().cloned());
+        self.uni.insert_sentence(sentence.clone(), value);
+        self.mark(if new_individual {None} else {Some(sentence)});
+
+        true
+    }
+
+    /// Adds everything in the universe that the context doesn't know about yet (keeping the context's truth values).
+    fn absorb(&mut self, uni: &Universe){
+        self.uni.insert_predicates(uni.predicates().cloned());
+        for sentences in uni.predicates().filter_map(|p| uni.all_sentences(p)){
+            for (sen, b) in sentences.iter(){
+                if !self.uni.contains_sentence(sen){
+                    self.set_tval(sen, *b);
+                }
+            }
+        }
+        if uni.variables().iter().any(|v| !self.uni.variables().contains(v)){
+            self.uni.insert_variables(uni.variables().iter().cloned());
+            self.mark(None);
+        }
+    }
+}
+
+impl VarContext{
+    /// Constructs an empty `VarContext`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Sets the truth value of the sentence written as a string (i.e. "A", "P(a, b)", "Rab") for every attached tree.
+    pub fn set_variable(&self, sentence: &str, value: bool) -> Result<(), ClawgicError>{
+        self.set_tval(&Sentence::try_from(sentence)?, value);
+        Ok(())
+    }
+
+    /// Sets the truth value of the sentence for every attached tree.
+    pub fn set_tval(&self, sentence: &Sentence, value: bool){
+        self.shared.write().unwrap().set_tval(sentence, value);
+    }
+
+    /// Sets the truth values of the given sentences for every attached tree.
+    pub fn set_tvals(&self, sentences: &Assignment){
+        let mut shared = self.shared.write().unwrap();
+        for (sen, b) in sentences.iter(){
+            shared.set_tval(sen, *b);
+        }
+    }
+
+    /// Gets the truth value of the sentence.
+    pub fn get_tval(&self, sentence: &Sentence) -> Option<bool>{
+        self.shared.read().unwrap().uni.get_tval(sentence)
+    }
+
+    /// Gets the truth value of every sentence in the context.
+    pub fn tvals(&self) -> Assignment{
+        let shared = self.shared.read().unwrap();
+        let mut tvals = Assignment::new();
+        for sentences in shared.uni.predicates().filter_map(|p| shared.uni.all_sentences(p)){
+            for (sen, b) in sentences.iter(){
+                tvals.insert(sen.clone(), *b);
+            }
+        }
+
+        tvals
+    }
+
+    /// Whether both are handles to the same context.
+    pub(crate) fn same_as(&self, other: &Self) -> bool{
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+
+    /// Number of trees (and clones of trees) that are attached.
+    pub fn attached(&self) -> usize{
+        self.shared.read().unwrap().trees.iter().filter(|tree| tree.strong_count() > 0).count()
+    }
+
+    /// Attaches a tree with the given universe, adding whatever the context doesn't know about yet.
+    pub(crate) fn attach(&self, uni: &Universe) -> Attachment{
+        let mut shared = self.shared.write().unwrap();
+        shared.absorb(uni);
+        let stale = Arc::new(Mutex::new(Stale::default()));
+        shared.trees.push(Arc::downgrade(&stale));
+
+        Attachment { context: self.clone(), stale }
+    }
+}
+
+/// An `ExpressionTree`'s link to the `VarContext` it's attached to.
+#[derive(Debug)]
+pub(crate) struct Attachment{
+    context: VarContext,
+    /// What has changed since the tree last evaluated.
+    stale: Arc<Mutex<Stale>>,
+}
+
+impl Attachment{
+    /// The context the tree is attached to.
+    pub(crate) fn context(&self) -> &VarContext{
+        &self.context
+    }
+
+    /// Lets go of the context.
+    pub(crate) fn into_context(self) -> VarContext{
+        self.context
+    }
+
+    /// Sets the truth value of the sentence in the context. Returns whether anything changed.
+    pub(crate) fn set_tval(&self, sentence: &Sentence, value: bool) -> bool{
+        self.context.shared.write().unwrap().set_tval(sentence, value)
+    }
+
+    /// Copies the context's truth values (for the predicates in the universe) into the universe.
+    pub(crate) fn copy_tvals(&self, uni: &mut Universe){
+        uni.inherit_tvals(&self.context.shared.read().unwrap().uni);
+    }
+
+    /// Evaluates the tree with the context's truth values, first forgetting whatever changed since the last time.
+    pub(crate) fn evaluate(&self, root: &Node, uni: &Universe, memo: &OnceLock<Memo>) -> Result<bool, ClawgicError>{
+        if memo.get().is_none(){
+            //the tree might have changed shape since it was attached, so the context might be missing some of its sentences
+            self.context.shared.write().unwrap().absorb(uni);
+        }
+        let shared = self.context.shared.read().unwrap();
+        let memo = memo.get_or_init(|| Memo::new(root));
+        let stale = std::mem::take(&mut *self.stale.lock().unwrap());
+        if stale.everything{
+            memo.forget_all();
+        }else{
+            for sen in stale.sentences.iter(){
+                memo.forget(sen);
+            }
+        }
+
+        memo.evaluate(root, &shared.uni)
+    }
+}
+
+/// The clone is attached to the same context, but keeps track of its own changes.
+impl Clone for Attachment{
+    fn clone(&self) -> Self {
+        let mut shared = self.context.shared.write().unwrap();
+        let stale = Arc::new(Mutex::new(self.stale.lock().unwrap().clone()));
+        shared.trees.push(Arc::downgrade(&stale));
+
+        Self { context: self.context.clone(), stale }
+    }
+}
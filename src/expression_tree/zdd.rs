@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crate::prelude::{Assignment, Bdd, Sentence};
+
+/// Index of the terminal for the empty family.
+const EMPTY: usize = 0;
+/// Index of the terminal for the family holding just the empty set.
+const BASE: usize = 1;
+
+/// Node of a `Zdd`: the sets in `high` get the atom added, the sets in `low` don't have it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ZddNode{
+    atom: usize,
+    low: usize,
+    high: usize,
+}
+
+/// A zero-suppressed decision diagram (ZDD): a family of sets of atoms, here the set of true atoms of each model of an expression.
+///
+/// Unlike a `Bdd`, atoms that are false in every model below a node just don't show up,
+/// so families of small sets (i.e. matchings or covers picked out of lots of atoms) stay compact.
+/// Converts to and from a `Bdd` with the same order with `From`.
+/// ```
+/// use clawgic::prelude::*;
+/// let sen = |s: &str| Sentence::try_from(s).unwrap();
+/// //exactly one of A, B, C
+/// let zdd = ExpressionTree::new("((AvB)vC)&((~Av~B)&((~Av~C)&(~Bv~C)))").unwrap().to_zdd();
+/// assert_eq!(zdd.count(), 3);
+/// assert!(zdd.contains(&[sen("B")]));
+/// assert!(!zdd.contains(&[sen("A"), sen("B")]));
+/// assert_eq!(Bdd::from(&zdd).model_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Zdd{
+    /// Every ground sentence, by atom index.
+    atoms: Vec<Sentence>,
+    /// Atom index at each level (top first).
+    order: Vec<usize>,
+    /// Level of each atom.
+    levels: Vec<usize>,
+    /// Every node. The first two are the terminals.
+    nodes: Vec<ZddNode>,
+    /// Where each node already is in `nodes` (so nothing gets stored twice).
+    unique: HashMap<ZddNode, usize>,
+    /// Index of the root node.
+    root: usize,
+}
+
+impl Zdd{
+    /// Constructs an empty family over the atoms with the given order (as atom indices, top first).
+    fn empty(atoms: Vec<Sentence>, order: Vec<usize>) -> Self{
+        let mut levels = vec![0 ; atoms.len()];
+        for (level, atom) in order.iter().enumerate(){
+            levels[*atom] = level;
+        }
+        let terminal = |i| ZddNode { atom: usize::MAX, low: i, high: i };
+        Self { atoms, order, levels, nodes: vec![terminal(EMPTY), terminal(BASE)], unique: HashMap::new(), root: EMPTY }
+    }
+
+    /// Gets every ground sentence the family is over, in no particular order.
+    pub fn atoms(&self) -> &Vec<Sentence>{
+        &self.atoms
+    }
+
+    /// Gets the order of the atoms (top first).
+    pub fn order(&self) -> Vec<&Sentence>{
+        self.order.iter().map(|i| &self.atoms[*i]).collect()
+    }
+
+    /// Number of nodes reachable from the root (not counting the terminals).
+    pub fn size(&self) -> usize{
+        let mut seen = vec![false ; self.nodes.len()];
+        let mut stack = vec![self.root];
+        let mut size = 0;
+        while let Some(i) = stack.pop(){
+            if i <= BASE || seen[i]{
+                continue;
+            }
+            seen[i] = true;
+            size += 1;
+            stack.push(self.nodes[i].low);
+            stack.push(self.nodes[i].high);
+        }
+
+        size
+    }
+
+    /// Whether the family has no sets (the expression is unsatisfiable).
+    pub fn is_empty(&self) -> bool{
+        self.root == EMPTY
+    }
+
+    /// Number of sets in the family (the number of models of the expression).
+    ///
+    /// Saturates at `u128::MAX`.
+    pub fn count(&self) -> u128{
+        self.count_rec(self.root, &mut HashMap::new())
+    }
+
+    /// Whether exactly the given sentences being true (and every other atom false) is one of the sets.
+    pub fn contains(&self, set: &[Sentence]) -> bool{
+        let mut i = self.root;
+        let mut remaining: Vec<&Sentence> = set.iter().collect();
+        while i > BASE{
+            let node = self.nodes[i];
+            let before = remaining.len();
+            remaining.retain(|s| **s != self.atoms[node.atom]);
+            i = if remaining.len() < before {node.high} else {node.low};
+        }
+        i == BASE && remaining.is_empty()
+    }
+
+    /// Converts the family back into a `Bdd` with the same order.
+    pub fn to_bdd(&self) -> Bdd{
+        Bdd::from(self)
+    }
+
+    /// Gets every set in the family (the true sentences of each model), in order.
+    pub fn sets(&self) -> Vec<Vec<Sentence>>{
+        let mut sets = Vec::new();
+        self.sets_rec(self.root, &mut Vec::new(), &mut sets);
+        sets
+    }
+
+    /// Gets every set in the family as an `Assignment` of all of the atoms.
+    pub fn assignments(&self) -> Vec<Assignment>{
+        self.sets().into_iter().map(|set| {
+            self.atoms.iter().map(|a| (a.clone(), set.contains(a))).collect()
+        }).collect()
+    }
+
+    /// Recursive helper for `Zdd::sets()`.
+    fn sets_rec(&self, i: usize, current: &mut Vec<Sentence>, sets: &mut Vec<Vec<Sentence>>){
+        match i{
+            EMPTY => (),
+            BASE => sets.push(current.clone()),
+            _ => {
+                let node = self.nodes[i];
+                self.sets_rec(node.low, current, sets);
+                current.push(self.atoms[node.atom].clone());
+                self.sets_rec(node.high, current, sets);
+                current.pop();
+            },
+        }
+    }
+
+    /// Recursive helper for `Zdd::count()`.
+    fn count_rec(&self, i: usize, counts: &mut HashMap<usize, u128>) -> u128{
+        if i <= BASE{
+            return i as u128;
+        }
+        if let Some(c) = counts.get(&i){
+            return *c;
+        }
+        let count = self.count_rec(self.nodes[i].low, counts).saturating_add(self.count_rec(self.nodes[i].high, counts));
+        counts.insert(i, count);
+
+        count
+    }
+
+    /// Level of the node (terminals are below every atom).
+    fn level(&self, i: usize) -> usize{
+        if i <= BASE {self.atoms.len()} else {self.levels[self.nodes[i].atom]}
+    }
+
+    /// Gets the node for the atom (unless `high` is empty, since then the atom is never in any set).
+    fn mk(&mut self, atom: usize, low: usize, high: usize) -> usize{
+        if high == EMPTY{
+            return low;
+        }
+        let node = ZddNode { atom, low, high };
+        if let Some(i) = self.unique.get(&node){
+            return *i;
+        }
+        self.nodes.push(node);
+        self.unique.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Recursive helper for `Bdd::to_zdd()`. Converts the BDD node, counting only the atoms at or below `level`.
+    fn convert_bdd_rec(&mut self, bdd: &Bdd, i: usize, level: usize, memo: &mut HashMap<(usize, usize), usize>) -> usize{
+        if level == self.atoms.len(){
+            return i;
+        }
+        if let Some(z) = memo.get(&(i, level)){
+            return *z;
+        }
+        let atom = self.order[level];
+        let result = match bdd.branches(i){
+            Some((_, low, high)) if bdd.level(i) == level => {
+                let low = self.convert_bdd_rec(bdd, low, level + 1, memo);
+                let high = self.convert_bdd_rec(bdd, high, level + 1, memo);
+                self.mk(atom, low, high)
+            },
+            //the BDD doesn't care about this atom, so both ways are in the family
+            _ => {
+                let rest = self.convert_bdd_rec(bdd, i, level + 1, memo);
+                self.mk(atom, rest, rest)
+            },
+        };
+        memo.insert((i, level), result);
+
+        result
+    }
+
+    /// Recursive helper for `Zdd::to_bdd()`. Converts the node, counting only the atoms at or below `level`.
+    fn convert_to_bdd_rec(&self, bdd: &mut Bdd, i: usize, level: usize, memo: &mut HashMap<(usize, usize), usize>) -> usize{
+        if level == self.atoms.len(){
+            return i;
+        }
+        if let Some(b) = memo.get(&(i, level)){
+            return *b;
+        }
+        let atom = self.order[level];
+        let (low, high) = if self.level(i) == level {(self.nodes[i].low, self.nodes[i].high)} else {(i, EMPTY)};
+        let low = self.convert_to_bdd_rec(bdd, low, level + 1, memo);
+        let high = self.convert_to_bdd_rec(bdd, high, level + 1, memo);
+        let result = bdd.mk(atom, low, high);
+        memo.insert((i, level), result);
+
+        result
+    }
+}
+
+impl From<&Bdd> for Zdd{
+    fn from(bdd: &Bdd) -> Self {
+        let mut zdd = Zdd::empty(bdd.atoms().clone(), bdd.atom_order().clone());
+        zdd.root = zdd.convert_bdd_rec(bdd, bdd.root(), 0, &mut HashMap::new());
+        zdd
+    }
+}
+
+impl From<&Zdd> for Bdd{
+    fn from(zdd: &Zdd) -> Self {
+        let mut bdd = Bdd::empty(zdd.atoms.clone(), zdd.order.clone());
+        let root = zdd.convert_to_bdd_rec(&mut bdd, zdd.root, 0, &mut HashMap::new());
+        bdd.set_root(root);
+        bdd
+    }
+}
@@ -0,0 +1,280 @@
+pub mod forward_chain;
+
+use crate::expression_tree::bdd::Bdd;
+use crate::expression_tree::compiled::Compiled;
+use crate::expression_tree::grounding::Grounding;
+use crate::expression_tree::node::Node;
+use crate::knowledge_base::forward_chain::ForwardChain;
+use crate::prelude::{Assignment, ExpressionTree, Predicate, Sentence};
+use crate::ClawgicError;
+use crate::budget::{Budget, Meter};
+
+/// A growing set of formulas that can be queried.
+///
+/// Instead of rebuilding one giant conjunction for every query, the knowledge base keeps
+/// a binary decision diagram of everything it has been told so far. Telling it something new only adds that
+/// formula's sentences below the ones already there and conjoins it onto the diagram.
+/// (The diagram is only rebuilt from scratch if a new individual shows up, since that changes
+/// what the quantifiers range over.)
+/// ```
+/// use clawgic::prelude::*;
+/// let mut kb = KnowledgeBase::new();
+/// kb.tell(ExpressionTree::new("A->B").unwrap());
+/// kb.tell(ExpressionTree::new("A").unwrap());
+/// assert!(kb.consistent());
+/// assert!(kb.ask(&ExpressionTree::new("B").unwrap()));
+/// assert!(!kb.ask(&ExpressionTree::new("C").unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnowledgeBase{
+    /// Everything that has been told to the knowledge base.
+    formulas: Vec<ExpressionTree>,
+    /// The atoms (and domain) of all the formulas.
+    grounding: Grounding,
+    /// Every formula conjoined together, over the atoms of the grounding (in the same order).
+    bdd: Bdd,
+}
+
+impl KnowledgeBase{
+    /// Constructs an empty `KnowledgeBase`. It has exactly one (empty) model.
+    pub fn new() -> Self{
+        Self { formulas: Vec::new(), grounding: Grounding::new(Vec::new()), bdd: Bdd::tautology() }
+    }
+
+    /// Adds the formula to the knowledge base; returns a mutable reference.
+    pub fn tell(&mut self, formula: ExpressionTree) -> &mut Self{
+        self.tell_within(formula, &Budget::new()).unwrap()
+    }
+
+    /// `tell()`, but gives up with `Interrupted` once the budget runs out.
+    ///
+    /// The knowledge base is left the way it was if it gives up.
+    pub fn tell_within(&mut self, formula: ExpressionTree, budget: &Budget) -> Result<&mut Self, ClawgicError>{
+        let mut meter = budget.meter();
+        if self.domain_changed(&formula){
+            (self.grounding, self.bdd) = self.build(Some(&formula), &mut meter)?;
+        }else{
+            let grounding = self.grounding.clone();
+            let checkpoint = self.bdd.checkpoint();
+            if let Err(e) = Self::assert(&mut self.grounding, &mut self.bdd, formula.node(), &mut meter){
+                self.grounding = grounding;
+                self.bdd.roll_back(checkpoint);
+                return Err(e);
+            }
+        }
+        self.formulas.push(formula);
+        Ok(self)
+    }
+
+    /// Whether the knowledge base entails the query (the query is true in every model).
+    ///
+    /// Always true if the knowledge base is inconsistent.
+    pub fn ask(&self, query: &ExpressionTree) -> bool{
+        self.ask_within(query, &Budget::new()).unwrap()
+    }
+
+    /// `ask()`, but gives up with `Interrupted` once the budget runs out.
+    ///
+    /// If the query brings in a new individual, rebuilding the diagram (with the query denied) counts against the budget too.
+    pub fn ask_within(&self, query: &ExpressionTree, budget: &Budget) -> Result<bool, ClawgicError>{
+        let mut meter = budget.meter();
+        if self.domain_changed(query){
+            let (_, bdd) = self.build(Some(&!query.clone()), &mut meter)?;
+            return Ok(!bdd.is_satisfiable());
+        }
+
+        let mut grounding = self.grounding.clone();
+        let mut bdd = self.bdd.clone();
+        let known = grounding.atoms().len();
+        grounding.add_atoms(query.node());
+        bdd.add_atoms(&grounding.atoms()[known..]);
+        bdd.entails_within(query.node(), &grounding, &mut meter)
+    }
+
+    /// Whether there is at least one way for everything in the knowledge base to be true.
+    pub fn consistent(&self) -> bool{
+        self.bdd.is_satisfiable()
+    }
+
+    /// Goes through every model of the knowledge base, one at a time.
+    ///
+    /// Every model assigns a truth value to every (ground) sentence the knowledge base has been told about.
+    pub fn models(&self) -> impl Iterator<Item = Assignment> + '_{
+        self.bdd.models().map(|m| self.grounding.to_assignment(&m))
+    }
+
+    /// Gets the number of models of the knowledge base. Saturates at `u128::MAX`.
+    pub fn model_count(&self) -> u128{
+        self.bdd.model_count()
+    }
+
+    /// Derives every sentence entailed by the knowledge base with forward chaining (and how each one was derived).
+    ///
+    /// Much faster than `ask()`, but only works if every formula is a definite clause: either a single sentence (`A`)
+    /// or a conjunction of sentences implying a sentence (`(A&B)&C->D`). Fails with `NotDefiniteClause` otherwise.
+    pub fn forward_chain(&self) -> Result<ForwardChain, ClawgicError>{
+        ForwardChain::new(&self.formulas)
+    }
+
+    /// Finds a reason the knowledge base is inconsistent: a minimal set of formulas (as indices into `formulas()`)
+    /// that can't all be true, but where every proper subset can. Returns `None` if the knowledge base is consistent.
+    ///
+    /// Minimal doesn't mean minimum: there might be other reasons with fewer formulas (see `minimal_unsatisfiable_subsets()`).
+    pub fn unsat_core(&self) -> Option<Vec<usize>>{
+        self.unsat_core_within(&Budget::new()).unwrap()
+    }
+
+    /// `unsat_core()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn unsat_core_within(&self, budget: &Budget) -> Result<Option<Vec<usize>>, ClawgicError>{
+        Ok(self.minimal_unsatisfiable_subsets_within(Some(1), budget)?.pop())
+    }
+
+    /// Finds every minimal unsatisfiable subset of the formulas (or only the first `limit` of them),
+    /// as sorted lists of indices into `formulas()`. Each one is an independent reason the knowledge base is inconsistent.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut kb = KnowledgeBase::new();
+    /// for f in ["A", "~A", "B", "B->~A", "C"]{
+    ///     kb.tell(ExpressionTree::new(f).unwrap());
+    /// }
+    /// let mut muses = kb.minimal_unsatisfiable_subsets(None);
+    /// muses.sort();
+    /// assert_eq!(muses, vec![vec![0, 1], vec![0, 2, 3]]);
+    /// ```
+    ///
+    /// Works by alternately picking a subset that hasn't been ruled out yet and either shrinking it down to a
+    /// minimal unsatisfiable subset or growing it into a maximal satisfiable one. Very expensive function.
+    pub fn minimal_unsatisfiable_subsets(&self, limit: Option<usize>) -> Vec<Vec<usize>>{
+        self.minimal_unsatisfiable_subsets_within(limit, &Budget::new()).unwrap()
+    }
+
+    /// `minimal_unsatisfiable_subsets()`, but gives up with `Interrupted` once the budget runs out.
+    pub fn minimal_unsatisfiable_subsets_within(&self, limit: Option<usize>, budget: &Budget) -> Result<Vec<Vec<usize>>, ClawgicError>{
+        let mut muses = Vec::new();
+        if self.consistent(){
+            return Ok(muses);
+        }
+
+        //the seeds that are still possible, over one sentence per formula (true means the formula is in the seed)
+        let picks: Vec<Sentence> = (0..self.formulas.len())
+            .map(|i| Predicate::new(&format!("S{i}"), 0).unwrap().inst(&vec![]).unwrap())
+            .collect();
+        let mut compiled: Vec<Compiled> = self.formulas.iter().map(|f| Compiled::new(f.node(), &self.grounding)).collect();
+        let mut map = ExpressionTree::TRUE();
+        let mut meter = budget.meter();
+        while limit.is_none_or(|l| muses.len() < l){
+            let Some(assignment) = map.first_model(&mut meter, &Assignment::new())? else {break};
+            let seed: Vec<usize> = (0..picks.len()).filter(|i| assignment.get(&picks[*i]).unwrap_or(true)).collect();
+
+            if self.subset_consistent(&mut compiled, &seed, &mut meter)?{
+                //nothing inside a satisfiable subset can be unsatisfiable, so at least one formula outside it has to be picked
+                let mss = self.grow(&mut compiled, seed, &mut meter)?;
+                let outside = (0..picks.len()).filter(|i| !mss.contains(i)).map(|i| picks[i].expr());
+                map &= outside.reduce(|a, b| a | b).unwrap_or(ExpressionTree::FALSE());
+            }else{
+                //every superset of an unsatisfiable subset is unsatisfiable, so at least one formula inside it has to be left out
+                let mus = self.shrink(&mut compiled, seed, &mut meter)?;
+                let inside = mus.iter().map(|i| !picks[*i].expr());
+                map &= inside.reduce(|a, b| a | b).unwrap_or(ExpressionTree::FALSE());
+                muses.push(mus);
+            }
+        }
+
+        Ok(muses)
+    }
+
+    /// Whether the formulas at the given indices (compiled over the knowledge base's atoms) can all be true at once.
+    fn subset_consistent(&self, compiled: &mut [Compiled], subset: &[usize], meter: &mut Meter) -> Result<bool, ClawgicError>{
+        let atoms = self.grounding.atoms().len();
+        let mask = Compiled::row_mask(atoms);
+        for b in 0..Compiled::block_count(atoms){
+            meter.step(mask.count_ones() as u64)?;
+            if subset.iter().fold(mask, |column, i| column & compiled[*i].evaluate_block(b)) != 0{
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Removes formulas from an unsatisfiable subset for as long as it stays unsatisfiable.
+    fn shrink(&self, compiled: &mut [Compiled], mut subset: Vec<usize>, meter: &mut Meter) -> Result<Vec<usize>, ClawgicError>{
+        let mut i = 0;
+        while i < subset.len(){
+            let removed = subset.remove(i);
+            if self.subset_consistent(compiled, &subset, meter)?{
+                subset.insert(i, removed);
+                i += 1;
+            }
+        }
+        Ok(subset)
+    }
+
+    /// Adds formulas to a satisfiable subset for as long as it stays satisfiable.
+    fn grow(&self, compiled: &mut [Compiled], mut subset: Vec<usize>, meter: &mut Meter) -> Result<Vec<usize>, ClawgicError>{
+        for i in 0..self.formulas.len(){
+            if subset.contains(&i){
+                continue;
+            }
+            subset.push(i);
+            if !self.subset_consistent(compiled, &subset, meter)?{
+                subset.pop();
+            }
+        }
+        subset.sort();
+        Ok(subset)
+    }
+
+    /// Gets everything that has been told to the knowledge base.
+    pub fn formulas(&self) -> &Vec<ExpressionTree>{
+        &self.formulas
+    }
+
+    /// Number of formulas in the knowledge base.
+    pub fn len(&self) -> usize{
+        self.formulas.len()
+    }
+
+    /// Whether the knowledge base has been told nothing.
+    pub fn is_empty(&self) -> bool{
+        self.formulas.is_empty()
+    }
+
+    /// Whether adding the formula would change what quantifiers range over.
+    fn domain_changed(&self, formula: &ExpressionTree) -> bool{
+        let domain = Grounding::domain_of(
+            self.formulas.iter().chain(std::iter::once(formula)).map(|f| f.node()),
+            self.formulas.iter().chain(std::iter::once(formula)).flat_map(|f| f.universe().variables().iter()),
+        );
+        domain != *self.grounding.domain()
+    }
+
+    /// Builds a fresh grounding and diagram of every formula (and the extra one, if there is one).
+    fn build(&self, extra: Option<&ExpressionTree>, meter: &mut Meter) -> Result<(Grounding, Bdd), ClawgicError>{
+        let formulas: Vec<&ExpressionTree> = self.formulas.iter().chain(extra).collect();
+        let domain = Grounding::domain_of(
+            formulas.iter().map(|f| f.node()),
+            formulas.iter().flat_map(|f| f.universe().variables().iter()),
+        );
+        let mut grounding = Grounding::new(domain);
+        let mut bdd = Bdd::tautology();
+        for f in formulas{
+            Self::assert(&mut grounding, &mut bdd, f.node(), meter)?;
+        }
+        Ok((grounding, bdd))
+    }
+
+    /// Adds the new atoms of the formula to the diagram and conjoins the formula onto it.
+    fn assert(grounding: &mut Grounding, bdd: &mut Bdd, formula: &Node, meter: &mut Meter) -> Result<(), ClawgicError>{
+        let known = grounding.atoms().len();
+        grounding.add_atoms(formula);
+        bdd.add_atoms(&grounding.atoms()[known..]);
+        bdd.conjoin_within(formula, grounding, meter)
+    }
+}
+
+impl Default for KnowledgeBase{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use crate::expression_tree::node::Node;
+use crate::prelude::{ExpressionTree, Operator, Sentence};
+use crate::ClawgicError;
+
+/// One step of forward chaining: a sentence and why it's true.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Derivation{
+    /// The derived sentence.
+    sentence: Sentence,
+    /// Index (into `KnowledgeBase::formulas()`) of the clause that derived it.
+    formula: usize,
+    /// The premises of that clause. Empty if the sentence was told directly.
+    premises: Vec<Sentence>,
+}
+
+impl Derivation{
+    /// Gets the derived sentence.
+    pub fn sentence(&self) -> &Sentence{
+        &self.sentence
+    }
+
+    /// Gets the index of the clause (in `KnowledgeBase::formulas()`) that derived the sentence.
+    pub fn formula(&self) -> usize{
+        self.formula
+    }
+
+    /// Gets the premises that had to be derived first.
+    pub fn premises(&self) -> &Vec<Sentence>{
+        &self.premises
+    }
+
+    /// Whether the sentence was a fact in the knowledge base (it has no premises).
+    pub fn is_fact(&self) -> bool{
+        self.premises.is_empty()
+    }
+}
+
+/// Everything entailed by a knowledge base of definite clauses, along with how each one was derived.
+/// Made with `KnowledgeBase::forward_chain()`.
+/// ```
+/// use clawgic::prelude::*;
+/// let mut kb = KnowledgeBase::new();
+/// kb.tell(ExpressionTree::new("A").unwrap());
+/// kb.tell(ExpressionTree::new("A->B").unwrap());
+/// kb.tell(ExpressionTree::new("A&B->C").unwrap());
+/// let fc = kb.forward_chain().unwrap();
+/// let c = Predicate::new("C", 0).unwrap().inst(&vec![]).unwrap();
+/// assert!(fc.entails(&c));
+/// //A, then B, then C
+/// assert_eq!(fc.chain(&c).len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ForwardChain{
+    /// Every derivation, in the order they were made (premises always come before their conclusions).
+    derivations: Vec<Derivation>,
+    /// Where each derived sentence is in `derivations`.
+    index: HashMap<Sentence, usize>,
+}
+
+impl ForwardChain{
+    /// Runs forward chaining over the given formulas.
+    /// Fails if any of them isn't a definite clause.
+    pub(crate) fn new(formulas: &[ExpressionTree]) -> Result<Self, ClawgicError>{
+        let mut clauses = Vec::with_capacity(formulas.len());
+        for f in formulas.iter(){
+            match definite_clause(f.node()){
+                Some(c) => clauses.push(c),
+                None => return Err(ClawgicError::NotDefiniteClause(f.infix(None))),
+            }
+        }
+
+        //how many premises each clause is still waiting on
+        let mut count: Vec<usize> = clauses.iter().map(|(premises, _)| premises.len()).collect();
+        //which clauses each sentence is a premise of
+        let mut uses: HashMap<&Sentence, Vec<usize>> = HashMap::new();
+        let mut agenda = VecDeque::new();
+        for (i, (premises, conclusion)) in clauses.iter().enumerate(){
+            for p in premises.iter(){
+                uses.entry(p).or_default().push(i);
+            }
+            if premises.is_empty(){
+                agenda.push_back((conclusion, i));
+            }
+        }
+
+        let mut fc = Self { derivations: Vec::new(), index: HashMap::new() };
+        while let Some((sentence, formula)) = agenda.pop_front(){
+            if fc.index.contains_key(sentence){
+                continue;
+            }
+            fc.index.insert(sentence.clone(), fc.derivations.len());
+            fc.derivations.push(Derivation {
+                sentence: sentence.clone(),
+                formula,
+                premises: clauses[formula].0.iter().cloned().collect(),
+            });
+
+            for c in uses.get(sentence).into_iter().flatten(){
+                count[*c] -= 1;
+                if count[*c] == 0{
+                    agenda.push_back((&clauses[*c].1, *c));
+                }
+            }
+        }
+
+        Ok(fc)
+    }
+
+    /// Whether the sentence is entailed.
+    pub fn entails(&self, sentence: &Sentence) -> bool{
+        self.index.contains_key(sentence)
+    }
+
+    /// creates an iterator over every entailed sentence in the order they were derived.
+    pub fn entailed(&self) -> impl Iterator<Item = &Sentence>{
+        self.derivations.iter().map(|d| &d.sentence)
+    }
+
+    /// Gets the step that derived the sentence (if it's entailed).
+    pub fn derivation(&self, sentence: &Sentence) -> Option<&Derivation>{
+        self.index.get(sentence).map(|i| &self.derivations[*i])
+    }
+
+    /// Gets every step needed to derive the sentence, in order. The last step derives the sentence itself.
+    /// 
+    /// Empty if the sentence isn't entailed.
+    pub fn chain(&self, sentence: &Sentence) -> Vec<&Derivation>{
+        let mut needed = BTreeSet::new();
+        let mut stack: Vec<usize> = self.index.get(sentence).copied().into_iter().collect();
+        while let Some(i) = stack.pop(){
+            if needed.insert(i){
+                stack.extend(self.derivations[i].premises.iter().map(|p| self.index[p]));
+            }
+        }
+
+        needed.into_iter().map(|i| &self.derivations[i]).collect()
+    }
+
+    /// creates an iterator over every derivation in the order they were made.
+    pub fn iter(&self) -> std::slice::Iter<'_, Derivation>{
+        self.derivations.iter()
+    }
+
+    /// Number of entailed sentences.
+    pub fn len(&self) -> usize{
+        self.derivations.len()
+    }
+
+    /// Whether nothing is entailed.
+    pub fn is_empty(&self) -> bool{
+        self.derivations.is_empty()
+    }
+}
+
+/// Splits a definite clause (`A`, `A->B`, `(A&B)&C->D`, etc.) into its premises and conclusion.
+fn definite_clause(node: &Node) -> Option<(BTreeSet<Sentence>, Sentence)>{
+    match node{
+        Node::Sentence { neg, sen } if !neg.is_denied() => Some((BTreeSet::new(), sen.clone())),
+        Node::Operator { neg, op: Operator::CON, left, right } if !neg.is_denied() => {
+            let mut premises = BTreeSet::new();
+            if !conjoined_atoms(left, &mut premises){
+                return None;
+            }
+            match &**right{
+                Node::Sentence { neg, sen } if !neg.is_denied() => Some((premises, sen.clone())),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Collects the atoms of a conjunction of (un-denied) atoms. Returns false if it's anything else.
+fn conjoined_atoms(node: &Node, atoms: &mut BTreeSet<Sentence>) -> bool{
+    match node{
+        Node::Sentence { neg, sen } if !neg.is_denied() => {
+            atoms.insert(sen.clone());
+            true
+        },
+        Node::Operator { neg, op: Operator::AND, left, right } if !neg.is_denied() => {
+            conjoined_atoms(left, atoms) && conjoined_atoms(right, atoms)
+        },
+        _ => false,
+    }
+}
@@ -3,6 +3,18 @@ pub mod expression_tree;
 
 pub mod operator_notation;
 
+pub mod parser_config;
+
+pub mod knowledge_base;
+
+pub mod expression_system;
+
+pub mod budget;
+
+pub mod proof;
+
+pub mod theory;
+
 pub mod prelude;
 
 mod utils;
@@ -28,6 +40,18 @@ pub enum ClawgicError{
     AmbiguousExpression,
     TooFewVariables,
     TooManyVariables,
+    NotDefiniteClause(String),
+    Interrupted,
+    UnboundMetavariable(String),
+    CapturedVariable(String),
+    WrongArgumentCount(usize, usize),
+    InvalidProofStep(usize),
+    NotEquivalent(String),
+    TermOutOfRange(u64),
+    TooManySentences(usize),
+    MissingLabel,
+    InvalidLabel(String),
+    DuplicateLabel(String),
 }
 
 impl std::fmt::Display for ClawgicError{
@@ -48,6 +72,18 @@ impl std::fmt::Display for ClawgicError{
             Self::MultiBoundVar(s) => format!("Expression contains variable \"{s}\" that is bound by nested quantifiers"),
             Self::NoVarQuantifier => "Expression contains a quantifier with no variables".to_string(),
             Self::InvalidVarBounds => "Invalid bounds on ExpressionVars object".to_string(),
+            Self::NotDefiniteClause(s) => format!("\"{s}\" is not a definite clause"),
+            Self::Interrupted => "Ran out of budget (time, steps, or cancelled)".to_string(),
+            Self::UnboundMetavariable(s) => format!("Metavariable \"?{s}\" isn't bound to anything"),
+            Self::CapturedVariable(s) => format!("Metavariable \"?{s}\" is bound to something that a quantifier would capture"),
+            Self::WrongArgumentCount(expected, found) => format!("Expected {expected} expressions but got {found}"),
+            Self::InvalidProofStep(i) => format!("Line {i} of the proof doesn't follow from its justification"),
+            Self::NotEquivalent(s) => format!("The sides of \"{s}\" aren't logically equivalent"),
+            Self::TermOutOfRange(t) => format!("Term {t} is out of range for the number of variables"),
+            Self::TooManySentences(n) => format!("Expression has {n} sentences, but terms only have room for 64"),
+            Self::MissingLabel => "Formula has no label (expected \"label: formula\")".to_string(),
+            Self::InvalidLabel(s) => format!("Invalid label \"{s}\""),
+            Self::DuplicateLabel(s) => format!("Label \"{s}\" is used more than once"),
         })
     }
 }
@@ -1,7 +1,41 @@
-use std::{collections::HashMap, ops::Index};
+use std::{collections::HashMap, ops::Index, sync::OnceLock};
 
 use crate::expression_tree::node::operator::Operator;
 
+/// Symbols from textbooks (and Wikipedia) that every `OperatorNotation` accepts, unless it uses the symbol itself.
+/// 
+/// Operators without their own `Operator` are written as denied ones (i.e. XOR is a denied biconditional),
+/// which is what the last value is for.
+const UNICODE_OPERATORS: [(char, Operator, bool) ; 22] = [
+    ('∼', Operator::NOT, false),
+    ('∧', Operator::AND, false),
+    ('·', Operator::AND, false),
+    ('∙', Operator::AND, false),
+    ('∨', Operator::OR, false),
+    ('∥', Operator::OR, false),
+    ('→', Operator::CON, false),
+    ('⇒', Operator::CON, false),
+    ('⊃', Operator::CON, false),
+    ('↔', Operator::BICON, false),
+    ('⇔', Operator::BICON, false),
+    ('≡', Operator::BICON, false),
+    ('⊕', Operator::BICON, true),
+    ('⊻', Operator::BICON, true),
+    ('↮', Operator::BICON, true),
+    ('≢', Operator::BICON, true),
+    ('⊼', Operator::AND, true),
+    ('↑', Operator::AND, true),
+    ('⊽', Operator::OR, true),
+    ('↓', Operator::OR, true),
+    ('∀', Operator::UNI, false),
+    ('∃', Operator::EXI, false),
+];
+
+/// Makes the constant notations (false, then true).
+fn constants(tval: &str, fval: &str) -> [String ; 2]{
+    [fval.to_string(), tval.to_string()]
+}
+
 /// Fake HashMap for OperatorNotation.
 struct NotationMap{
     map: [Vec<String> ; 7],
@@ -29,6 +63,10 @@ impl Index<Operator> for NotationMap{
 ///Contains a set of symbols for printing `ExpressionTree`s. Used in certain `ExpressionTree` functions to customize expression printing.
 pub struct OperatorNotation{
     map: NotationMap,
+    /// How constants are printed (false, then true).
+    constants: [String ; 2],
+    /// Whether the digits in names are printed as subscripts.
+    subscripts: bool,
 }
 
 impl OperatorNotation{
@@ -39,8 +77,9 @@ impl OperatorNotation{
     /// * negation ~
     /// * conditional ->
     /// * biconditional <->
+    /// * constants TRUE FALSE
     pub fn ascii() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new([
             (Operator::NOT, ("~".to_string(), vec!["¬".to_string(), "!".to_string()])),
             (Operator::AND, ("&".to_string(), vec!["^".to_string(), "∧".to_string(), "*".to_string(), "⋅".to_string()])),
             (Operator::OR, ("v".to_string(), vec!["∨".to_string(), "|".to_string(), "+".to_string()])),
@@ -59,8 +98,9 @@ impl OperatorNotation{
     /// * negation ¬
     /// * conditional ➞
     /// * biconditional ⟷
+    /// * constants ⊤ ⊥
     pub fn mathematical() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("⊤", "⊥"), map: NotationMap::new([
             (Operator::NOT, ("¬".to_string(), vec!["~".to_string(), "!".to_string()])),
             (Operator::AND, ("∧".to_string(), vec!["^".to_string(), "&".to_string(), "*".to_string(), "⋅".to_string()])),
             (Operator::OR, ("v".to_string(), vec!["∨".to_string(), "|".to_string(), "+".to_string()])),
@@ -79,8 +119,9 @@ impl OperatorNotation{
     /// * negation ~
     /// * conditional ->
     /// * biconditional <->
+    /// * constants TRUE FALSE
     pub fn mathematical_ascii() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new([
             (Operator::NOT, ("~".to_string(), vec!["¬".to_string(), "!".to_string()])),
             (Operator::AND, ("^".to_string(), vec!["&".to_string(), "∧".to_string(), "*".to_string(), "⋅".to_string()])),
             (Operator::OR, ("v".to_string(), vec!["∨".to_string(), "|".to_string(), "+".to_string()])),
@@ -99,8 +140,9 @@ impl OperatorNotation{
     /// * negation ¬
     /// * conditional ➞
     /// * biconditional ⟷
+    /// * constants 1 0
     pub fn bits() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("1", "0"), map: NotationMap::new([
             (Operator::NOT, ("¬".to_string(), vec!["~".to_string(), "!".to_string()])),
             (Operator::AND, ("⋅".to_string(), vec!["^".to_string(), "&".to_string(), "*".to_string(), "∧".to_string()])),
             (Operator::OR, ("+".to_string(), vec!["∨".to_string(), "|".to_string(), "v".to_string()])),
@@ -119,8 +161,9 @@ impl OperatorNotation{
     /// * negation ~
     /// * conditional ->
     /// * biconditional <->
+    /// * constants 1 0
     pub fn bits_ascii() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("1", "0"), map: NotationMap::new([
             (Operator::NOT, ("~".to_string(), vec!["¬".to_string(), "!".to_string()])),
             (Operator::AND, ("*".to_string(), vec!["&".to_string(), "∧".to_string(), "^".to_string(), "⋅".to_string()])),
             (Operator::OR, ("+".to_string(), vec!["∨".to_string(), "|".to_string(), "v".to_string()])),
@@ -139,8 +182,9 @@ impl OperatorNotation{
     /// * negation !
     /// * conditional ➞
     /// * biconditional ⟷
+    /// * constants TRUE FALSE
     pub fn boolean() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new([
             (Operator::NOT, ("!".to_string(), vec!["~".to_string(), "¬".to_string()])),
             (Operator::AND, ("&".to_string(), vec!["^".to_string(), "⋅".to_string(), "*".to_string(), "∧".to_string()])),
             (Operator::OR, ("|".to_string(), vec!["∨".to_string(), "+".to_string(), "v".to_string()])),
@@ -159,8 +203,9 @@ impl OperatorNotation{
     /// * negation !
     /// * conditional ->
     /// * biconditional <->
+    /// * constants TRUE FALSE
     pub fn boolean_ascii() -> Self{
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new([
             (Operator::NOT, ("!".to_string(), vec!["~".to_string(), "¬".to_string()])),
             (Operator::AND, ("&".to_string(), vec!["^".to_string(), "⋅".to_string(), "*".to_string(), "∧".to_string()])),
             (Operator::OR, ("|".to_string(), vec!["∨".to_string(), "+".to_string(), "v".to_string()])),
@@ -195,7 +240,48 @@ impl OperatorNotation{
             }
         }
 
-        Ok(Self{map: NotationMap::new(map)})
+        Ok(Self{subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new(map)})
+    }
+
+    ///Changes how constants are printed; returns a mutable reference.
+    /// 
+    /// Only changes printing. Constants are always parsed as `TRUE`/`FALSE`, `⊤`/`⊥`, or `1`/`0`
+    /// (and `T`/`F` with `ParserConfig::set_letter_constants()`).
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut notation = OperatorNotation::default();
+    /// notation.set_constant_notation("T", "F");
+    /// let tree = ExpressionTree::new("TRUE&~FALSE").unwrap();
+    /// assert_eq!(tree.infix(Some(&notation)), "T&¬F");
+    /// ```
+    pub fn set_constant_notation(&mut self, tval: &str, fval: &str) -> &mut Self{
+        self.constants = constants(tval, fval);
+        self
+    }
+
+    ///Returns the notation of the given constant.
+    pub fn get_constant_notation(&self, value: bool) -> &str{
+        &self.constants[value as usize]
+    }
+
+    ///Prints the digits in names as subscripts (or not); returns a mutable reference.
+    /// 
+    /// Subscripts are always accepted when parsing (`A₁` is the same as `A1`).
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut notation = OperatorNotation::default();
+    /// notation.set_subscripts(true);
+    /// let tree = ExpressionTree::new("A1&R(x12, y)").unwrap();
+    /// assert_eq!(tree.infix(Some(&notation)), "A₁&R(x₁₂, y)");
+    /// ```
+    pub fn set_subscripts(&mut self, subscripts: bool) -> &mut Self{
+        self.subscripts = subscripts;
+        self
+    }
+
+    ///Whether the digits in names are printed as subscripts.
+    pub fn subscripts(&self) -> bool{
+        self.subscripts
     }
 
     ///Returns the notation of the given operator.
@@ -221,6 +307,61 @@ impl OperatorNotation{
         None
     }
 
+    ///Returns the operator for one of the common unicode symbols every notation accepts (if it is one),
+    /// along with whether it's denied.
+    /// 
+    /// * negation ∼
+    /// * conjunction ∧ · ∙
+    /// * disjunction ∨ ∥
+    /// * conditional → ⇒ ⊃
+    /// * biconditional ↔ ⇔ ≡
+    /// * exclusive or (denied biconditional) ⊕ ⊻ ↮ ≢
+    /// * nand (denied conjunction) ⊼ ↑
+    /// * nor (denied disjunction) ⊽ ↓
+    /// * universal ∀
+    /// * existential ∃
+    /// ```
+    /// use clawgic::prelude::*;
+    /// assert_eq!(OperatorNotation::get_unicode_operator('⊃'), Some((Operator::CON, false)));
+    /// assert_eq!(OperatorNotation::get_unicode_operator('⊕'), Some((Operator::BICON, true)));
+    /// assert_eq!(OperatorNotation::get_unicode_operator('&'), None);
+    /// ```
+    pub fn get_unicode_operator(symbol: char) -> Option<(Operator, bool)>{
+        UNICODE_OPERATORS.iter().find(|(c, ..)| *c == symbol).map(|(_, op, denied)| (*op, *denied))
+    }
+
+    /// Matches the start of the input against every notation in one pass, preferring the longest match.
+    ///
+    /// Returns the operator and how many bytes it takes up, or (if nothing matches) how many bytes
+    /// at the start of the input are the start of some notation.
+    pub(crate) fn match_operator(&self, input: &str) -> Result<(Operator, usize), usize>{
+        let mut best: Option<(Operator, usize)> = None;
+        let mut partial = 0;
+        for op in [Operator::NOT, Operator::AND, Operator::OR, Operator::CON, Operator::BICON, Operator::UNI, Operator:: EXI]{
+            for notation in self.map[op].iter(){
+                let mut common = notation.bytes().zip(input.bytes()).take_while(|(a, b)| a == b).count();
+                if common == notation.len(){
+                    if best.is_none_or(|(_, len)| len < common){
+                        best = Some((op, common));
+                    }
+                    continue;
+                }
+                while !input.is_char_boundary(common){
+                    common -= 1;
+                }
+                partial = partial.max(common);
+            }
+        }
+
+        best.ok_or(partial)
+    }
+
+    /// The default notation, only constructed once (so parsing with it doesn't build a new one every time).
+    pub(crate) fn shared_default() -> &'static Self{
+        static DEFAULT: OnceLock<OperatorNotation> = OnceLock::new();
+        DEFAULT.get_or_init(Self::default)
+    }
+
     ///Returns all operators that have partial matches with the given string 
     /// 
     /// The map it returns has the key-value pair of (operator, # of partially-matching notations)
@@ -270,8 +411,9 @@ impl Default for OperatorNotation{
     /// * negation ¬
     /// * conditional ➞
     /// * biconditional ⟷
+    /// * constants TRUE FALSE
     fn default() -> Self {
-        Self { map: NotationMap::new([
+        Self { subscripts: false, constants: constants("TRUE", "FALSE"), map: NotationMap::new([
             (Operator::NOT, ("¬".to_string(), vec!["~".to_string(), "!".to_string()])),
             (Operator::AND, ("&".to_string(), vec!["^".to_string(), "∧".to_string(), "*".to_string(), "⋅".to_string()])),
             (Operator::OR, ("∨".to_string(), vec!["v".to_string(), "|".to_string(), "+".to_string()])),
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::Operator;
+use crate::utils;
+
+/// Options for how `ExpressionTree`s get parsed. Used in `ExpressionTree::new_with_config()`.
+///
+/// The default configuration is the same one used by `ExpressionTree::new()`.
+/// ```
+/// use clawgic::prelude::*;
+/// let mut config = ParserConfig::default();
+/// config.set_long_names(true);
+/// let tree = ExpressionTree::new_with_config("Rain -> Wet(ground_1)", None, &config).unwrap();
+/// assert_eq!(tree.infix(None), "Rain➞Wet(ground_1)");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParserConfig{
+    /// Whether multi-letter predicate and variable names are allowed.
+    long_names: bool,
+    /// Whether operands next to each other are conjoined.
+    implicit_and: bool,
+    /// Whether `T` and `F` are constants.
+    letter_constants: bool,
+    /// Precedences of binary operators that differ from `Operator::precedence()`.
+    precedence: BTreeMap<Operator, u8>,
+}
+
+impl ParserConfig{
+    /// Constructs the default `ParserConfig`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Allows (or disallows) multi-letter names; returns a mutable reference.
+    ///
+    /// With long names, predicates are an uppercase letter followed by any letters, digits, or underscores (i.e. "Rain", "P_1"),
+    /// and variables are the same but starting with a lowercase letter (i.e. "ground", "x_1").
+    /// Because of that:
+    /// * whitespace separates names instead of being ignored, so `v` still works as disjunction if it's surrounded by spaces.
+    /// * the arguments of a predicate must be written in parentheses (`Lxy` would just be a predicate named "Lxy").
+    pub fn set_long_names(&mut self, long_names: bool) -> &mut Self{
+        self.long_names = long_names;
+        self
+    }
+
+    /// Whether multi-letter names are allowed.
+    pub fn long_names(&self) -> bool{
+        self.long_names
+    }
+
+    /// Turns implicit conjunction on (or off); returns a mutable reference.
+    ///
+    /// With implicit conjunction, operands written next to each other are conjoined like in bit logic (`AB` is `A&B`).
+    /// Implicit conjunctions bind tighter than any binary operator, and can be chained (`ABC+D` is `((A&B)&C)+D`).
+    /// Because of that:
+    /// * a run of uppercase letters is a run of sentences (`ABC` is `A&B&C`), except for `TRUE` and `FALSE`.
+    /// * without long names, the arguments of a predicate can't be written in parentheses (`A(BvC)` is `A&(BvC)`; write `Rab`, not `R(a,b)`).
+    /// * with long names, a parenthesis right after a predicate is still its arguments (`Loves(x, y)`), so put a space before a group.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut config = ParserConfig::default();
+    /// config.set_implicit_and(true);
+    /// let tree = ExpressionTree::new_with_config("AB+~AC", Some(&OperatorNotation::bits_ascii()), &config).unwrap();
+    /// assert_eq!(tree.infix(None), "(A&B)∨(¬A&C)");
+    /// ```
+    pub fn set_implicit_and(&mut self, implicit_and: bool) -> &mut Self{
+        self.implicit_and = implicit_and;
+        self
+    }
+
+    /// Whether operands next to each other are conjoined.
+    pub fn implicit_and(&self) -> bool{
+        self.implicit_and
+    }
+
+    /// Makes `T` and `F` constants instead of sentences (or not); returns a mutable reference.
+    ///
+    /// Only a bare `T` or `F` is a constant; `T1` and `Fab` are still sentences.
+    /// (`TRUE`/`FALSE`, `⊤`/`⊥`, and `1`/`0` are always constants.)
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut config = ParserConfig::default();
+    /// config.set_letter_constants(true);
+    /// let tree = ExpressionTree::new_with_config("T&(F1vF)", None, &config).unwrap();
+    /// assert_eq!(tree.infix(None), "TRUE&(F1∨FALSE)");
+    /// ```
+    pub fn set_letter_constants(&mut self, letter_constants: bool) -> &mut Self{
+        self.letter_constants = letter_constants;
+        self
+    }
+
+    /// Whether `T` and `F` are constants.
+    pub fn letter_constants(&self) -> bool{
+        self.letter_constants
+    }
+
+    /// Changes the precedence of a binary operator; returns a mutable reference.
+    ///
+    /// Higher precedence binds tighter. Operators with the same precedence still can't be chained
+    /// without parentheses (that's an `AmbiguousExpression`). Only matters for binary operators.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut config = ParserConfig::default();
+    /// config.set_precedence(Operator::AND, 4);
+    /// let tree = ExpressionTree::new_with_config("A&BvC", None, &config).unwrap();
+    /// assert_eq!(tree.infix(None), "(A&B)∨C");
+    /// assert_eq!(tree.infix_minimal(None, &config), "A&B∨C");
+    /// ```
+    pub fn set_precedence(&mut self, op: Operator, precedence: u8) -> &mut Self{
+        if precedence == op.precedence(){
+            self.precedence.remove(&op);
+        }else{
+            self.precedence.insert(op, precedence);
+        }
+        self
+    }
+
+    /// Gets the precedence of the operator under this configuration.
+    pub fn precedence(&self, op: Operator) -> u8{
+        self.precedence.get(&op).copied().unwrap_or(op.precedence())
+    }
+
+    /// Returns whether the given string is a valid predicate name under this configuration.
+    pub fn is_valid_predicate_name(&self, name: &str) -> bool{
+        if self.long_names{
+            utils::is_valid_long_name(name, char::is_uppercase)
+        }else{
+            utils::is_valid_predicate_name(name)
+        }
+    }
+
+    /// Returns whether the given string is a valid variable name under this configuration.
+    pub fn is_valid_var_name(&self, name: &str) -> bool{
+        if self.long_names{
+            utils::is_valid_long_name(name, char::is_lowercase)
+        }else{
+            utils::is_valid_var_name(name)
+        }
+    }
+}
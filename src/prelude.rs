@@ -1,7 +1,27 @@
 pub use crate::operator_notation::OperatorNotation;
+pub use crate::parser_config::ParserConfig;
 pub use crate::expression_tree::ExpressionTree;
+pub use crate::expression_system::ExpressionSystem;
+pub use crate::knowledge_base::KnowledgeBase;
+pub use crate::knowledge_base::forward_chain::{Derivation, ForwardChain};
 pub use crate::ClawgicError;
+pub use crate::budget::{Budget, CancelToken, Progress};
 pub use crate::expression_tree::expression_var::ExpressionVar;
 pub use crate::expression_tree::expression_var::ExpressionVars;
+pub use crate::expression_tree::expression_var::ExpressionVarGrid;
 pub use crate::expression_tree::node::operator::Operator;
-pub use crate::expression_tree::node::sentence::{Predicate, Sentence};
\ No newline at end of file
+pub use crate::expression_tree::substitution::Substitution;
+pub use crate::expression_tree::pattern::{Bindings, Pattern};
+pub use crate::expression_tree::assignment::Assignment;
+pub use crate::expression_tree::aig::Aig;
+pub use crate::expression_tree::bdd::Bdd;
+pub use crate::expression_tree::cnf::{Clause, Cnf, Implication, Literal, Propagation};
+pub use crate::expression_tree::ddnnf::Ddnnf;
+pub use crate::expression_tree::zdd::Zdd;
+pub use crate::expression_tree::var_context::VarContext;
+pub use crate::expression_tree::diagnostic::Diagnostic;
+pub use crate::expression_tree::node::sentence::{Predicate, Sentence};
+pub use crate::proof::schema::Schema;
+pub use crate::theory::{parse_theory, parse_theory_with_config, Theory};
+pub use crate::proof::{Justification, Proof};
+pub use crate::proof::equivalence::{Equivalence, EquivalenceRegistry};
\ No newline at end of file
@@ -0,0 +1,225 @@
+pub mod schema;
+pub mod equivalence;
+
+use crate::expression_tree::node::Node;
+use crate::prelude::{EquivalenceRegistry, ExpressionTree, Operator, Schema};
+use crate::ClawgicError;
+
+/// Why a line of a `Proof` is allowed to be there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Justification{
+    /// The line is assumed. Always allowed; every premise ends up on the left of the turnstile.
+    Premise,
+    /// The line is an instance of the schema with the given name.
+    Axiom(String),
+    /// The line follows from two earlier lines (by index): one with some formula `P` and one with `P->(this line)`.
+    ModusPonens(usize, usize),
+    /// The line is an earlier line (by index) with one subexpression replaced using the equivalence with the given name.
+    Replacement(usize, String),
+}
+
+/// A Hilbert-style derivation: a list of formulas, each one a premise, an axiom, the result of modus ponens on earlier lines,
+/// or an earlier line with part of it replaced by something equivalent.
+///
+/// Uses the standard schemas (see `Schema::standard()`) unless told otherwise,
+/// and the standard equivalences (see `EquivalenceRegistry::standard()`).
+/// ```
+/// use clawgic::prelude::*;
+/// let tree = |e: &str| ExpressionTree::new(e).unwrap();
+/// //A->A
+/// let mut proof = Proof::new();
+/// proof.push(tree("(A->((A->A)->A))->((A->(A->A))->(A->A))"), Justification::Axiom("S".to_string()))
+///     .push(tree("A->((A->A)->A)"), Justification::Axiom("K".to_string()))
+///     .push(tree("(A->(A->A))->(A->A)"), Justification::ModusPonens(1, 0))
+///     .push(tree("A->(A->A)"), Justification::Axiom("K".to_string()))
+///     .push(tree("A->A"), Justification::ModusPonens(3, 2));
+/// assert!(proof.check().is_ok());
+/// assert_eq!(proof.conclusion(), Some(&tree("A->A")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Proof{
+    /// Schemas that axiom lines can be instances of.
+    schemas: Vec<Schema>,
+    /// Equivalences that replacement lines can use.
+    equivalences: EquivalenceRegistry,
+    /// Every line and its justification, in order.
+    lines: Vec<(ExpressionTree, Justification)>,
+}
+
+impl Proof{
+    /// Constructs an empty `Proof` that uses the standard schemas.
+    pub fn new() -> Self{
+        Self::with_schemas(Schema::standard())
+    }
+
+    /// Constructs an empty `Proof` that uses the given schemas.
+    pub fn with_schemas(schemas: Vec<Schema>) -> Self{
+        Self { schemas, equivalences: EquivalenceRegistry::standard(), lines: Vec::new() }
+    }
+
+    /// Adds a line to the end of the proof; returns a mutable reference.
+    pub fn push(&mut self, formula: ExpressionTree, justification: Justification) -> &mut Self{
+        self.lines.push((formula, justification));
+        self
+    }
+
+    /// Gets the schemas that axiom lines can be instances of.
+    pub fn schemas(&self) -> &Vec<Schema>{
+        &self.schemas
+    }
+
+    /// Gets the equivalences that replacement lines can use.
+    pub fn equivalences(&self) -> &EquivalenceRegistry{
+        &self.equivalences
+    }
+
+    /// Gets the equivalences that replacement lines can use mutably (i.e. to register a lemma).
+    pub fn equivalences_mut(&mut self) -> &mut EquivalenceRegistry{
+        &mut self.equivalences
+    }
+
+    /// Gets every line and its justification.
+    pub fn lines(&self) -> &Vec<(ExpressionTree, Justification)>{
+        &self.lines
+    }
+
+    /// Number of lines.
+    pub fn len(&self) -> usize{
+        self.lines.len()
+    }
+
+    /// Whether the proof has no lines.
+    pub fn is_empty(&self) -> bool{
+        self.lines.is_empty()
+    }
+
+    /// Gets every line justified as a premise.
+    pub fn premises(&self) -> Vec<&ExpressionTree>{
+        self.lines.iter().filter(|(_, j)| *j == Justification::Premise).map(|(f, _)| f).collect()
+    }
+
+    /// Gets the last line (what the proof proves).
+    pub fn conclusion(&self) -> Option<&ExpressionTree>{
+        self.lines.last().map(|(f, _)| f)
+    }
+
+    /// Checks every line of the proof.
+    ///
+    /// Fails with `InvalidProofStep` (holding the index of the line) at the first line that doesn't follow from its justification.
+    pub fn check(&self) -> Result<(), ClawgicError>{
+        for i in 0..self.lines.len(){
+            if !self.check_line(i){
+                return Err(ClawgicError::InvalidProofStep(i));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the (checked) proof as a LaTeX table with one numbered line per row.
+    /// ```
+    /// use clawgic::prelude::*;
+    /// let mut proof = Proof::new();
+    /// proof.push(ExpressionTree::new("A").unwrap(), Justification::Premise)
+    ///     .push(ExpressionTree::new("A->(B->A)").unwrap(), Justification::Axiom("K".to_string()))
+    ///     .push(ExpressionTree::new("B->A").unwrap(), Justification::ModusPonens(0, 1));
+    /// assert_eq!(proof.to_latex().unwrap(), "\\begin{tabular}{rll}\n\
+    ///     1. & $A$ & Premise \\\\\n\
+    ///     2. & $A \\rightarrow (B \\rightarrow A)$ & K \\\\\n\
+    ///     3. & $B \\rightarrow A$ & MP 1, 2 \\\\\n\
+    ///     \\end{tabular}\n");
+    /// ```
+    ///
+    /// Fails like `check()` if the proof isn't valid.
+    pub fn to_latex(&self) -> Result<String, ClawgicError>{
+        self.check()?;
+        let mut latex = String::from("\\begin{tabular}{rll}\n");
+        for (i, (formula, justification)) in self.lines.iter().enumerate(){
+            let reason = match justification{
+                Justification::Premise => "Premise".to_string(),
+                Justification::Axiom(name) => name.clone(),
+                Justification::ModusPonens(a, c) => format!("MP {}, {}", a + 1, c + 1),
+                Justification::Replacement(l, name) => format!("{name} {}", l + 1),
+            };
+            latex.push_str(&format!("{}. & ${}$ & {} \\\\\n", i + 1, formula.latex(), reason));
+        }
+        latex.push_str("\\end{tabular}\n");
+
+        Ok(latex)
+    }
+
+    /// Writes the (checked) proof of its conclusion as a `bussproofs` tree.
+    ///
+    /// Axioms are leaves labeled with their schema, and premises are plain leaves.
+    /// Lines that get used more than once show up once per use. An empty proof gives an empty string.
+    ///
+    /// Fails like `check()` if the proof isn't valid.
+    pub fn to_bussproofs(&self) -> Result<String, ClawgicError>{
+        self.check()?;
+        if self.lines.is_empty(){
+            return Ok(String::new());
+        }
+        let mut latex = String::from("\\begin{prooftree}\n");
+        self.bussproofs_rec(self.lines.len() - 1, &mut latex);
+        latex.push_str("\\end{prooftree}\n");
+
+        Ok(latex)
+    }
+
+    /// Recursive helper for `Proof::to_bussproofs()`.
+    fn bussproofs_rec(&self, i: usize, latex: &mut String){
+        let (formula, justification) = &self.lines[i];
+        match justification{
+            Justification::Premise => latex.push_str(&format!("\\AxiomC{{${}$}}\n", formula.latex())),
+            Justification::Axiom(name) => {
+                latex.push_str("\\AxiomC{}\n");
+                latex.push_str(&format!("\\RightLabel{{\\scriptsize {name}}}\n"));
+                latex.push_str(&format!("\\UnaryInfC{{${}$}}\n", formula.latex()));
+            },
+            Justification::ModusPonens(a, c) => {
+                self.bussproofs_rec(*a, latex);
+                self.bussproofs_rec(*c, latex);
+                latex.push_str("\\RightLabel{\\scriptsize MP}\n");
+                latex.push_str(&format!("\\BinaryInfC{{${}$}}\n", formula.latex()));
+            },
+            Justification::Replacement(l, name) => {
+                self.bussproofs_rec(*l, latex);
+                latex.push_str(&format!("\\RightLabel{{\\scriptsize {name}}}\n"));
+                latex.push_str(&format!("\\UnaryInfC{{${}$}}\n", formula.latex()));
+            },
+        }
+    }
+
+    /// Whether the i'th line follows from its justification.
+    fn check_line(&self, i: usize) -> bool{
+        let (formula, justification) = &self.lines[i];
+        match justification{
+            Justification::Premise => true,
+            Justification::Axiom(name) => self.schemas.iter()
+                .filter(|s| s.name() == name)
+                .any(|s| s.matches(formula).is_some()),
+            Justification::ModusPonens(antecedent, conditional) => {
+                if *antecedent >= i || *conditional >= i{
+                    return false;
+                }
+                match self.lines[*conditional].0.node(){
+                    Node::Operator { neg, op: Operator::CON, left, right } => {
+                        neg.count() == 0
+                            && **left == *self.lines[*antecedent].0.node()
+                            && **right == *formula.node()
+                    },
+                    _ => false,
+                }
+            },
+            Justification::Replacement(l, name) => {
+                *l < i && self.equivalences.get(name).is_some_and(|e| e.justifies(&self.lines[*l].0, formula))
+            },
+        }
+    }
+}
+
+impl Default for Proof{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use crate::expression_tree::node::Node;
+use crate::prelude::{Bindings, ExpressionTree, Pattern};
+use crate::ClawgicError;
+
+/// A named replacement rule: pairs of patterns that are logically equivalent, so either side
+/// can replace the other anywhere inside a formula.
+///
+/// Some rules have more than one form (i.e. De Morgan's has one for conjunctions and one for disjunctions).
+/// ```
+/// use clawgic::prelude::*;
+/// let exportation = Equivalence::new("Exportation", "(?p&?q)->?r", "?p->(?q->?r)").unwrap();
+/// let before = ExpressionTree::new("(A&B)->C").unwrap();
+/// assert_eq!(exportation.apply(&before), Some(ExpressionTree::new("A->(B->C)").unwrap()));
+/// assert!(exportation.justifies(&ExpressionTree::new("D&((A&B)->C)").unwrap(), &ExpressionTree::new("D&(A->(B->C))").unwrap()));
+///
+/// //only actual equivalences are allowed
+/// assert!(Equivalence::new("Bad", "?p->?q", "?q->?p").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Equivalence{
+    name: String,
+    /// Each form as a single pattern `(left)<->(right)`, so both sides share their metavariables.
+    forms: Vec<Pattern>,
+}
+
+impl Equivalence{
+    /// Constructs a new `Equivalence` with a single form.
+    ///
+    /// Fails with `NotEquivalent` if the two sides aren't logically equivalent.
+    pub fn new(name: &str, left: &str, right: &str) -> Result<Self, ClawgicError>{
+        Self { name: name.to_string(), forms: Vec::new() }.with_form(left, right)
+    }
+
+    /// Adds another form to the equivalence and returns it.
+    ///
+    /// Fails with `NotEquivalent` if the two sides aren't logically equivalent.
+    /// A metavariable inside a quantifier never stands for anything that uses the quantifier's variables (see `Pattern`),
+    /// which is what keeps forms like `@x?p` and `?p` sound.
+    pub fn with_form(mut self, left: &str, right: &str) -> Result<Self, ClawgicError>{
+        let form = Pattern::new(&format!("({left})<->({right})"))?;
+        if !form.tree().is_tautology(){
+            return Err(ClawgicError::NotEquivalent(self.name));
+        }
+        self.forms.push(form);
+        Ok(self)
+    }
+
+    /// Gets the name of the equivalence.
+    pub fn name(&self) -> &str{
+        &self.name
+    }
+
+    /// Gets every form of the equivalence as (left, right) nodes, with a placeholder sentence for each metavariable.
+    pub fn forms(&self) -> Vec<(&Node, &Node)>{
+        self.forms.iter().filter_map(|f| Self::sides(f)).collect()
+    }
+
+    /// Rewrites the whole expression with the first form (in either direction) that matches it.
+    /// Returns `None` if none of them match.
+    pub fn apply(&self, expression: &ExpressionTree) -> Option<ExpressionTree>{
+        self.apply_node(expression.node()).map(ExpressionTree::from)
+    }
+
+    /// `apply()`, but for a node.
+    pub fn apply_node(&self, node: &Node) -> Option<Node>{
+        for form in self.forms.iter(){
+            let (left, right) = Self::sides(form)?;
+            for (from, to) in [(left, right), (right, left)]{
+                let mut bindings = Bindings::new();
+                if form.match_rec(from, node, &mut bindings) && let Ok(result) = form.instantiate_rec(to, &bindings){
+                    return Some(result);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Whether `after` is `before` with exactly one subexpression replaced using this equivalence (in either direction).
+    pub fn justifies(&self, before: &ExpressionTree, after: &ExpressionTree) -> bool{
+        self.justifies_node(before.node(), after.node())
+    }
+
+    /// `justifies()`, but for nodes.
+    pub fn justifies_node(&self, before: &Node, after: &Node) -> bool{
+        if self.replaces(before, after){
+            return true;
+        }
+        match (before, after){
+            (Node::Operator { neg: bneg, op: bop, left: bleft, right: bright }, Node::Operator { neg, op, left, right }) => {
+                bneg == neg && bop == op
+                    && ((bleft == left && self.justifies_node(bright, right)) || (bright == right && self.justifies_node(bleft, left)))
+            },
+            (Node::Quantifier { neg: bneg, op: bop, vars: bvars, subexpr: bsubexpr }, Node::Quantifier { neg, op, vars, subexpr }) => {
+                bneg == neg && bop == op && bvars == vars && self.justifies_node(bsubexpr, subexpr)
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether one side of a form matches `before` while the other side matches `after` (with the same bindings).
+    fn replaces(&self, before: &Node, after: &Node) -> bool{
+        self.forms.iter().any(|form| {
+            let Some((left, right)) = Self::sides(form) else {return false};
+            [(left, right), (right, left)].into_iter().any(|(from, to)| {
+                let mut bindings = Bindings::new();
+                form.match_rec(from, before, &mut bindings) && form.match_rec(to, after, &mut bindings)
+            })
+        })
+    }
+
+    /// Splits a form back into its left and right sides.
+    fn sides(form: &Pattern) -> Option<(&Node, &Node)>{
+        match form.tree().node(){
+            Node::Operator { left, right, .. } => Some((left, right)),
+            _ => None,
+        }
+    }
+}
+
+/// Equivalences that can be looked up by name. Used for replacement steps in a `Proof`.
+///
+/// `standard()` has the usual replacement rules from intro logic textbooks, and lemmas can be added with `register()`.
+/// ```
+/// use clawgic::prelude::*;
+/// let mut registry = EquivalenceRegistry::standard();
+/// assert!(registry.get("DeMorgan").is_some());
+/// registry.register(Equivalence::new("Absorption", "?p&(?pv?q)", "?p").unwrap());
+/// assert!(registry.get("Absorption").is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EquivalenceRegistry{
+    equivalences: BTreeMap<String, Equivalence>,
+}
+
+impl EquivalenceRegistry{
+    /// Constructs an empty `EquivalenceRegistry`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Constructs an `EquivalenceRegistry` with the standard replacement rules:
+    /// * DeMorgan: `~(?p&?q)` and `~?pv~?q`, `~(?pv?q)` and `~?p&~?q`
+    /// * Commutation: `?p&?q` and `?q&?p`, `?pv?q` and `?qv?p`
+    /// * Association: `?p&(?q&?r)` and `(?p&?q)&?r`, `?pv(?qv?r)` and `(?pv?q)v?r`
+    /// * Distribution: `?p&(?qv?r)` and `(?p&?q)v(?p&?r)`, `?pv(?q&?r)` and `(?pv?q)&(?pv?r)`
+    /// * DoubleNegation: `~~?p` and `?p`
+    /// * Transposition: `?p->?q` and `~?q->~?p`
+    /// * Implication: `?p->?q` and `~?pv?q`
+    /// * Exportation: `(?p&?q)->?r` and `?p->(?q->?r)`
+    /// * Tautology: `?p` and `?p&?p`, `?p` and `?pv?p`
+    /// * MaterialEquivalence: `?p<->?q` and `(?p->?q)&(?q->?p)`, `?p<->?q` and `(?p&?q)v(~?p&~?q)`
+    pub fn standard() -> Self{
+        let mut registry = Self::new();
+        let rules = [
+            ("DeMorgan", vec![("~(?p&?q)", "~?pv~?q"), ("~(?pv?q)", "~?p&~?q")]),
+            ("Commutation", vec![("?p&?q", "?q&?p"), ("?pv?q", "?qv?p")]),
+            ("Association", vec![("?p&(?q&?r)", "(?p&?q)&?r"), ("?pv(?qv?r)", "(?pv?q)v?r")]),
+            ("Distribution", vec![("?p&(?qv?r)", "(?p&?q)v(?p&?r)"), ("?pv(?q&?r)", "(?pv?q)&(?pv?r)")]),
+            ("DoubleNegation", vec![("~~?p", "?p")]),
+            ("Transposition", vec![("?p->?q", "~?q->~?p")]),
+            ("Implication", vec![("?p->?q", "~?pv?q")]),
+            ("Exportation", vec![("(?p&?q)->?r", "?p->(?q->?r)")]),
+            ("Tautology", vec![("?p", "?p&?p"), ("?p", "?pv?p")]),
+            ("MaterialEquivalence", vec![("?p<->?q", "(?p->?q)&(?q->?p)"), ("?p<->?q", "(?p&?q)v(~?p&~?q)")]),
+        ];
+        for (name, forms) in rules{
+            let mut equivalence = Equivalence { name: name.to_string(), forms: Vec::new() };
+            for (left, right) in forms{
+                equivalence = equivalence.with_form(left, right).unwrap();
+            }
+            registry.register(equivalence);
+        }
+
+        registry
+    }
+
+    /// Adds the equivalence under its name. If there already was one with that name, returns the old one.
+    pub fn register(&mut self, equivalence: Equivalence) -> Option<Equivalence>{
+        self.equivalences.insert(equivalence.name().to_string(), equivalence)
+    }
+
+    /// Removes the equivalence with the given name (if there is one) and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<Equivalence>{
+        self.equivalences.remove(name)
+    }
+
+    /// Gets the equivalence with the given name.
+    pub fn get(&self, name: &str) -> Option<&Equivalence>{
+        self.equivalences.get(name)
+    }
+
+    /// Gets the name of every equivalence, in alphabetical order.
+    pub fn names(&self) -> Vec<&str>{
+        self.equivalences.keys().map(|n| n.as_str()).collect()
+    }
+
+    /// Number of equivalences.
+    pub fn len(&self) -> usize{
+        self.equivalences.len()
+    }
+
+    /// Whether there are no equivalences.
+    pub fn is_empty(&self) -> bool{
+        self.equivalences.is_empty()
+    }
+
+    /// Iterates over all equivalences in alphabetical order of their names.
+    pub fn iter(&self) -> std::collections::btree_map::Values<'_, String, Equivalence>{
+        self.equivalences.values()
+    }
+}
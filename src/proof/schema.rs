@@ -0,0 +1,93 @@
+use crate::expression_tree::node::Node;
+use crate::prelude::{Bindings, ExpressionTree, Pattern};
+use crate::ClawgicError;
+
+/// A named axiom schema: a `Pattern` whose every instance is an axiom.
+///
+/// The standard schemas (`k()`, `s()` and `contraposition()`) are the axioms of the usual
+/// Hilbert-style system for SL, where modus ponens is the only rule.
+/// ```
+/// use clawgic::prelude::*;
+/// let a = ExpressionTree::new("A").unwrap();
+/// let b = ExpressionTree::new("B&C").unwrap();
+/// let axiom = Schema::k().instantiate(&[a, b]).unwrap();
+/// assert_eq!(axiom, ExpressionTree::new("A->((B&C)->A)").unwrap());
+/// assert!(Schema::k().matches(&axiom).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schema{
+    name: String,
+    pattern: Pattern,
+}
+
+impl Schema{
+    /// Constructs a new `Schema` out of a name and a pattern (see `Pattern` for how metavariables are written).
+    pub fn new(name: &str, pattern: &str) -> Result<Self, ClawgicError>{
+        Ok(Self { name: name.to_string(), pattern: Pattern::new(pattern)? })
+    }
+
+    /// K: `?p->(?q->?p)`.
+    pub fn k() -> Self{
+        Self::new("K", "?p->(?q->?p)").unwrap()
+    }
+
+    /// S: `(?p->(?q->?r))->((?p->?q)->(?p->?r))`.
+    pub fn s() -> Self{
+        Self::new("S", "(?p->(?q->?r))->((?p->?q)->(?p->?r))").unwrap()
+    }
+
+    /// Contraposition: `(~?p->~?q)->(?q->?p)`.
+    pub fn contraposition() -> Self{
+        Self::new("Contraposition", "(~?p->~?q)->(?q->?p)").unwrap()
+    }
+
+    /// Double negation elimination: `~~?p->?p`.
+    pub fn double_negation() -> Self{
+        Self::new("DN", "~~?p->?p").unwrap()
+    }
+
+    /// Every standard schema: K, S and contraposition.
+    pub fn standard() -> Vec<Self>{
+        vec![Self::k(), Self::s(), Self::contraposition()]
+    }
+
+    /// Gets the name of the schema.
+    pub fn name(&self) -> &str{
+        &self.name
+    }
+
+    /// Gets the pattern of the schema.
+    pub fn pattern(&self) -> &Pattern{
+        &self.pattern
+    }
+
+    /// Gets the number of expressions `instantiate()` needs (one per metavariable).
+    pub fn arity(&self) -> usize{
+        self.pattern.metavars().len()
+    }
+
+    /// Makes the instance of the schema where the i'th metavariable (in alphabetical order) is the i'th expression.
+    ///
+    /// Fails with `WrongArgumentCount` if there isn't exactly one expression per metavariable.
+    pub fn instantiate(&self, expressions: &[ExpressionTree]) -> Result<ExpressionTree, ClawgicError>{
+        let metavars = self.pattern.metavars();
+        if metavars.len() != expressions.len(){
+            return Err(ClawgicError::WrongArgumentCount(metavars.len(), expressions.len()));
+        }
+        let bindings = metavars.into_iter()
+            .zip(expressions.iter())
+            .fold(Bindings::new(), |b, (m, e)| b.with(m, e.node().clone()));
+
+        self.pattern.instantiate(&bindings)
+    }
+
+    /// If the expression is an instance of the schema, returns what each metavariable stands for. Otherwise returns `None`.
+    pub fn matches(&self, expression: &ExpressionTree) -> Option<Bindings>{
+        self.match_node(expression.node())
+    }
+
+    /// `matches()`, but for a node.
+    pub fn match_node(&self, node: &Node) -> Option<Bindings>{
+        self.pattern.match_against(node)
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+#[test_case("A&B" ; "and")]
+#[test_case("AvB" ; "or")]
+#[test_case("A->B" ; "conditional")]
+#[test_case("(A<->B)<->~C" ; "biconditionals")]
+#[test_case("~~(A&~(BvC))" ; "negations")]
+#[test_case("(A&1)v(B&0)" ; "constants")]
+#[test_case("@(x)(Px->Qx)" ; "universal")]
+#[test_case("(#(x)Px)&~Pa" ; "existential")]
+fn evaluate(expr: &str){
+    let system: ExpressionSystem = [tree(expr)].into_iter().collect();
+    let aig = tree(expr).to_aig();
+    assert_eq!(aig.output_count(), 1);
+    for (assignment, outputs) in system.truth_table(){
+        assert_eq!(aig.evaluate(&assignment), outputs, "{assignment}");
+    }
+}
+
+#[test_case("(A&B)&(B&A)", 1 ; "commuted")]
+#[test_case("(A&B)v~(A&B)", 1 ; "shared")]
+#[test_case("A&~A", 0 ; "contradiction")]
+#[test_case("(A&1)&A", 0 ; "constant and repeat")]
+fn structural_hashing(expr: &str, ands: usize){
+    assert_eq!(tree(expr).to_aig().and_count(), ands);
+}
+
+#[test]
+fn system(){
+    //full adder
+    let mut adder = ExpressionSystem::new();
+    adder.push(tree("(A<->B)<->C"));
+    adder.push(tree("(A&B)v(C&~(A<->B))"));
+    let aig = adder.to_aig();
+    assert_eq!(aig.output_count(), 2);
+    assert_eq!(aig.inputs().len(), 3);
+    //A<->B is built once for both outputs
+    let separate = adder.iter().map(|t| t.to_aig().and_count()).sum::<usize>();
+    assert!(aig.and_count() < separate);
+    for (assignment, outputs) in adder.truth_table(){
+        assert_eq!(aig.evaluate(&assignment), outputs);
+    }
+}
+
+#[test_case("A&B", "aag 3 2 0 1 1\n2\n4\n6\n6 4 2\ni0 A\ni1 B\n" ; "and")]
+#[test_case("AvB", "aag 3 2 0 1 1\n2\n4\n7\n6 5 3\ni0 A\ni1 B\n" ; "or")]
+#[test_case("A&~A", "aag 1 1 0 1 0\n2\n0\ni0 A\n" ; "constant")]
+fn to_aiger(expr: &str, expected: &str){
+    assert_eq!(tree(expr).to_aig().to_aiger(), expected);
+}
+
+#[test]
+fn to_aiger_binary(){
+    let mut expected = b"aig 5 2 0 1 3\n11\n".to_vec();
+    //6 = 4 & 2, 8 = 5 & 3, 10 = 9 & 7
+    expected.extend([2, 2, 3, 2, 1, 2]);
+    expected.extend(b"i0 A\ni1 B\n");
+    assert_eq!(tree("(A&B)v(~A&~B)").to_aig().to_aiger_binary(), expected);
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+#[test_case("A", Ok(("A", 0)) ; "atomic")]
+#[test_case("P(a, b)", Ok(("P", 2)) ; "with variables")]
+#[test_case("Rab", Ok(("R", 2)) ; "without parentheses")]
+#[test_case("~A", Err(ClawgicError::InvalidExpression) ; "denied")]
+#[test_case("A&B", Err(ClawgicError::InvalidExpression) ; "compound")]
+#[test_case("a", Err(ClawgicError::InvalidPredicateName("a".to_string())) ; "not a sentence")]
+fn sentence_try_from(s: &str, expected: Result<(&str, usize), ClawgicError>){
+    let sen = Sentence::try_from(s).map(|sen| (sen.name().to_string(), sen.arity()));
+    assert_eq!(sen, expected.map(|(n, a)| (n.to_string(), a)));
+}
+
+#[test]
+fn builder(){
+    let a = Assignment::new().set("C", true).unwrap().set("A", false).unwrap().set("B", true).unwrap().set("A", true).unwrap();
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get(&Sentence::try_from("A").unwrap()), Some(true));
+    assert_eq!(a, Assignment::try_from([("A", true), ("B", true), ("C", true)]).unwrap());
+}
+
+#[test]
+fn builder_error(){
+    assert_eq!(Assignment::new().set("A&B", true), Err(ClawgicError::InvalidExpression));
+    assert_eq!(Assignment::try_from([("A", true), ("a", false)]), Err(ClawgicError::InvalidPredicateName("a".to_string())));
+}
+
+#[test_case(&[("B", false), ("A", true)], "A=T, B=F" ; "sorted")]
+#[test_case(&[("P(b)", true), ("P(a)", false), ("Q", true)], "P(a)=F, P(b)=T, Q=T" ; "variables")]
+#[test_case(&[], "" ; "empty")]
+fn display(tvals: &[(&str, bool)], expected: &str){
+    assert_eq!(Assignment::try_from(tvals).unwrap().to_string(), expected);
+}
+
+#[test]
+fn iteration_order(){
+    let a = Assignment::try_from([("C", true), ("A", false), ("B", true)]).unwrap();
+    let names: Vec<_> = a.iter().map(|(s, _)| s.name().to_string()).collect();
+    assert_eq!(names, vec!["A", "B", "C"]);
+}
+
+#[test]
+fn satisfy_returns_assignments(){
+    let tree = ExpressionTree::new("A&~B").unwrap();
+    assert_eq!(tree.satisfy_one(), Some(Assignment::try_from([("A", true), ("B", false)]).unwrap()));
+    assert_eq!(tree.satisfy_all(), vec![Assignment::try_from([("A", true), ("B", false)]).unwrap()]);
+}
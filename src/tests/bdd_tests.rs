@@ -0,0 +1,103 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+fn sentences(names: &[&str]) -> Vec<Sentence>{
+    names.iter().map(|s| Sentence::try_from(*s).unwrap()).collect()
+}
+
+#[test_case("A" ; "single")]
+#[test_case("A&~A" ; "contradiction")]
+#[test_case("Av~A" ; "tautology")]
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("(A<->B)<->(C<->D)" ; "biconditionals")]
+#[test_case("~((A&B)v(~C->D))" ; "denied")]
+#[test_case("@(x)(Px->Qx)" ; "universal")]
+#[test_case("(#(x)Px)&~Pa" ; "existential")]
+fn model_count(expr: &str){
+    let expr = tree(expr);
+    assert_eq!(expr.to_bdd().model_count(), expr.satisfy_count()[0]);
+}
+
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("((A&D)v(B&E))v(C&F)" ; "pairs")]
+#[test_case("(A<->~B)v(C&~A)" ; "mixed")]
+fn evaluate(expr: &str){
+    let expr = tree(expr);
+    let bdd = expr.to_bdd();
+    for model in expr.satisfy_all(){
+        assert!(bdd.evaluate(&model));
+    }
+    assert_eq!(bdd.model_count(), expr.satisfy_count()[0]);
+}
+
+#[test_case("A&~A", false, false ; "contradiction")]
+#[test_case("(A->B)v(B->A)", true, true ; "tautology")]
+#[test_case("A->B", true, false ; "contingent")]
+fn is_satisfiable_tautology(expr: &str, satisfiable: bool, tautology: bool){
+    let bdd = tree(expr).to_bdd();
+    assert_eq!(bdd.is_satisfiable(), satisfiable);
+    assert_eq!(bdd.is_tautology(), tautology);
+}
+
+#[test]
+fn equivalent_expressions_same_size(){
+    let order = sentences(&["A", "B", "C"]);
+    let left = tree("A->(B->C)").to_bdd_with_order(&order);
+    let right = tree("(A&B)->C").to_bdd_with_order(&order);
+    assert_eq!(left.size(), right.size());
+    assert_eq!(left.model_count(), right.model_count());
+}
+
+#[test_case(&["A", "B", "C", "D", "E", "F"], 14 ; "separated")]
+#[test_case(&["A", "D", "B", "E", "C", "F"], 6 ; "interleaved")]
+fn order_changes_size(order: &[&str], size: usize){
+    let bdd = tree("((A&D)v(B&E))v(C&F)").to_bdd_with_order(&sentences(order));
+    assert_eq!(bdd.size(), size);
+    assert_eq!(bdd.order(), sentences(order).iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn to_bdd_with_partial_order(){
+    let bdd = tree("(A&B)vC").to_bdd_with_order(&sentences(&["C", "Z", "C"]));
+    assert_eq!(bdd.order(), sentences(&["C", "A", "B"]).iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn reorder(){
+    let expr = tree("((A&D)v(B&E))v(C&F)");
+    let mut bdd = expr.to_bdd_with_order(&sentences(&["A", "D", "B", "E", "C", "F"]));
+    bdd.reorder(&sentences(&["A", "B", "C"]));
+    assert_eq!(bdd.order(), sentences(&["A", "B", "C", "D", "E", "F"]).iter().collect::<Vec<_>>());
+    assert_eq!(bdd.size(), 14);
+    assert_eq!(bdd.model_count(), expr.satisfy_count()[0]);
+}
+
+#[test_case("((A&D)v(B&E))v(C&F)", &["A", "B", "C", "D", "E", "F"] ; "pairs")]
+#[test_case("((A<->E)&(B<->F))&(C<->G)", &["A", "B", "C", "E", "F", "G"] ; "equalities")]
+#[test_case("(A->B)&(B->C)", &["C", "A", "B"] ; "chain")]
+fn sift(expr: &str, order: &[&str]){
+    let expr = tree(expr);
+    let mut bdd = expr.to_bdd_with_order(&sentences(order));
+    let before = bdd.size();
+    bdd.sift();
+    assert!(bdd.size() <= before);
+    assert_eq!(bdd.model_count(), expr.satisfy_count()[0]);
+    for model in expr.satisfy_all(){
+        assert!(bdd.evaluate(&model));
+    }
+}
+
+#[test_case("((A&D)v(B&E))v(C&F)", 6 ; "pairs")]
+#[test_case("((A<->E)&(B<->F))&(C<->G)", 9 ; "equalities")]
+fn best_order(expr: &str, size: usize){
+    let expr = tree(expr);
+    let order = Bdd::best_order(&expr);
+    assert_eq!(expr.to_bdd_with_order(&order).size(), size);
+    assert_eq!(expr.to_bdd().size(), size);
+}
@@ -0,0 +1,190 @@
+#![cfg(test)]
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::prelude::*;
+
+/// Disjunction of A0 through A(n-1).
+fn disjunction(n: usize) -> ExpressionTree{
+    let mut tree = ExpressionTree::new("A0").unwrap();
+    for i in 1..n{
+        tree |= ExpressionTree::new(&format!("A{i}")).unwrap();
+    }
+    tree
+}
+
+#[test]
+fn unlimited(){
+    let tree = disjunction(4);
+    let budget = Budget::new();
+    assert_eq!(tree.is_satisfiable_within(&budget), Ok(true));
+    assert_eq!(tree.is_tautology_within(&budget), Ok(false));
+    assert_eq!(tree.is_contingency_within(&budget), Ok(true));
+    assert_eq!(tree.satisfy_count_within(&budget), Ok(vec![15]));
+    assert_eq!(tree.satisfy_all_within(&budget).unwrap().len(), 15);
+    assert_eq!(tree.log_eq_within(&tree, &budget), Ok(true));
+}
+
+#[test]
+fn max_steps(){
+    //tautology, so every row has to be checked
+    let tree = disjunction(20) | !ExpressionTree::new("A0").unwrap();
+    let budget = Budget::new().with_max_steps(1000);
+    assert_eq!(tree.is_tautology_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.counterexample_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.log_eq_within(&tree, &budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.satisfy_count_within(&budget), Err(ClawgicError::Interrupted));
+    //finding a model is quick even though there are a lot of rows
+    assert!(tree.satisfy_one_within(&budget).unwrap().is_some());
+}
+
+#[test]
+fn too_many_rows(){
+    //2^70 rows, more than there are 64 row blocks in a u64
+    let mut tree = ExpressionTree::new("A0").unwrap();
+    for i in 1..70{
+        tree &= ExpressionTree::new(&format!("A{i}")).unwrap();
+    }
+    let budget = Budget::new().with_max_steps(1000);
+    assert_eq!(tree.is_satisfiable_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.log_eq_within(&tree, &budget), Err(ClawgicError::Interrupted));
+    //they disagree on the very first row
+    assert_eq!(tree.log_eq_within(&!tree.clone(), &budget), Ok(false));
+    assert!(!tree.is_tautology());
+}
+
+#[test]
+fn solvers(){
+    //tautology with 2^16 rows
+    let tree = disjunction(16) | !ExpressionTree::new("A0").unwrap();
+    let onto: Vec<Sentence> = (0..8).map(|i| Sentence::try_from(format!("A{i}").as_str()).unwrap()).collect();
+    let budget = Budget::new().with_max_steps(1000);
+    assert_eq!(tree.disagreements_within(&tree, &budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.log_eq_with_dont_care_within(&tree, &ExpressionTree::FALSE(), &budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.log_eq_under_within(&tree, &ExpressionTree::TRUE(), &budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.minimize_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.minterms_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.maxterms_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.essential_vars_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.irrelevant_vars_within(&budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.satisfy_all_projected_within(&onto, &budget), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.satisfy_count_projected_within(&onto, &budget), Err(ClawgicError::Interrupted));
+
+    //the same answers as without a budget when there's enough of it
+    let tree = disjunction(4);
+    let budget = Budget::new();
+    assert_eq!(tree.disagreements_within(&tree, &budget), Ok(tree.disagreements(&tree)));
+    assert_eq!(tree.minimize_within(&budget), Ok(tree.minimize()));
+    assert_eq!(tree.minterms_within(&budget), tree.minterms());
+    assert_eq!(tree.essential_vars_within(&budget), Ok(tree.essential_vars()));
+    assert_eq!(tree.satisfy_count_projected_within(&onto[..2], &budget), Ok(tree.satisfy_count_projected(&onto[..2])));
+}
+
+#[test]
+fn contingency_shares_budget(){
+    //A0 is true on the first row and false on the second, so each half takes one block of 16 rows
+    let tree = disjunction(4) & ExpressionTree::new("A0").unwrap();
+    assert_eq!(tree.is_satisfiable_within(&Budget::new().with_max_steps(16)), Ok(true));
+    assert_eq!(tree.is_tautology_within(&Budget::new().with_max_steps(16)), Ok(false));
+    assert_eq!(tree.is_contingency_within(&Budget::new().with_max_steps(16)), Err(ClawgicError::Interrupted));
+    assert_eq!(tree.is_contingency_within(&Budget::new().with_max_steps(32)), Ok(true));
+}
+
+#[test]
+fn enough_steps(){
+    let tree = disjunction(4);
+    assert_eq!(tree.satisfy_count_within(&Budget::new().with_max_steps(16)), Ok(vec![15]));
+    assert_eq!(tree.satisfy_count_within(&Budget::new().with_max_steps(15)), Err(ClawgicError::Interrupted));
+}
+
+#[test]
+fn cancelled(){
+    let tree = disjunction(4);
+    let token = CancelToken::new();
+    let budget = Budget::new().with_cancel_token(token.clone());
+    assert_eq!(tree.is_satisfiable_within(&budget), Ok(true));
+    token.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(tree.is_satisfiable_within(&budget), Err(ClawgicError::Interrupted));
+}
+
+#[test]
+fn deadline(){
+    let tree = disjunction(4);
+    let budget = Budget::new().with_deadline(Instant::now());
+    assert_eq!(tree.is_inconsistency_within(&budget), Err(ClawgicError::Interrupted));
+    let budget = Budget::new().with_timeout(Duration::from_secs(3600));
+    assert_eq!(tree.is_inconsistency_within(&budget), Ok(false));
+}
+
+#[test]
+fn ask(){
+    let mut kb = KnowledgeBase::new();
+    kb.tell(ExpressionTree::new("A->B").unwrap()).tell(ExpressionTree::new("A").unwrap());
+    let query = disjunction(12) | ExpressionTree::new("B").unwrap();
+    assert_eq!(kb.ask_within(&query, &Budget::new()), Ok(true));
+    assert_eq!(kb.ask_within(&query, &Budget::new().with_max_steps(10)), Err(ClawgicError::Interrupted));
+}
+
+#[test]
+fn ask_new_individual(){
+    //the query brings in b, so the whole knowledge base gets rebuilt
+    let mut kb = KnowledgeBase::new();
+    for i in 0..12{
+        kb.tell(ExpressionTree::new(&format!("@x(P{i}x->P{}x)", i + 1)).unwrap());
+    }
+    kb.tell(ExpressionTree::new("P0a").unwrap());
+    let query = ExpressionTree::new("P0b->P12b").unwrap();
+    assert_eq!(kb.ask_within(&query, &Budget::new()), Ok(true));
+    assert_eq!(kb.ask_within(&query, &Budget::new().with_max_steps(10)), Err(ClawgicError::Interrupted));
+}
+
+#[test]
+fn tell(){
+    let mut kb = KnowledgeBase::new();
+    kb.tell(ExpressionTree::new("A->B").unwrap());
+    let formula = disjunction(12);
+    assert_eq!(kb.tell_within(formula.clone(), &Budget::new().with_max_steps(10)).err(), Some(ClawgicError::Interrupted));
+    //left the way it was
+    assert_eq!(kb.len(), 1);
+    assert_eq!(kb.model_count(), 3);
+    assert_eq!(kb.models().count(), 3);
+
+    assert!(kb.tell_within(formula, &Budget::new()).is_ok());
+    assert_eq!(kb.len(), 2);
+    assert_eq!(kb.model_count(), 3 * ((1 << 12) - 1));
+}
+
+#[test]
+fn minimal_unsatisfiable_subsets(){
+    let mut kb = KnowledgeBase::new();
+    for f in ["A", "~A", "B", "~B", "C", "~C"]{
+        kb.tell(ExpressionTree::new(f).unwrap());
+    }
+    assert_eq!(kb.minimal_unsatisfiable_subsets_within(None, &Budget::new()), Ok(kb.minimal_unsatisfiable_subsets(None)));
+    assert_eq!(kb.minimal_unsatisfiable_subsets_within(None, &Budget::new().with_max_steps(10)), Err(ClawgicError::Interrupted));
+    assert_eq!(kb.unsat_core_within(&Budget::new()), Ok(kb.unsat_core()));
+    assert_eq!(kb.unsat_core_within(&Budget::new().with_max_steps(1)), Err(ClawgicError::Interrupted));
+}
+
+#[test]
+fn progress(){
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let r = reports.clone();
+    let budget = Budget::new().with_progress(8, move |p| r.lock().unwrap().push((p.processed(), p.models())));
+    assert_eq!(disjunction(4).satisfy_all_within(&budget).unwrap().len(), 15);
+    assert_eq!(*reports.lock().unwrap(), vec![(8, 7), (16, 15), (16, 15)]);
+}
+
+#[test]
+fn progress_interrupted(){
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let r = reports.clone();
+    let budget = Budget::new()
+        .with_max_steps(10)
+        .with_progress(100, move |p| r.lock().unwrap().push(p.processed()));
+    assert_eq!(disjunction(4).satisfy_count_within(&budget), Err(ClawgicError::Interrupted));
+    //still told how far it got
+    assert_eq!(*reports.lock().unwrap(), vec![11]);
+}
@@ -0,0 +1,123 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn lit(name: &str) -> Literal{
+    match name.strip_prefix('~'){
+        Some(name) => Literal::new(Sentence::try_from(name).unwrap(), false),
+        None => Literal::new(Sentence::try_from(name).unwrap(), true),
+    }
+}
+
+fn clause(lits: &[&str]) -> Clause{
+    lits.iter().map(|l| lit(l)).collect()
+}
+
+#[test_case("A", "A" ; "sentence")]
+#[test_case("~(A&B)", "~A v ~B" ; "denied conjunction")]
+#[test_case("A->(B&C)", "(~A v B) & (~A v C)" ; "distributes")]
+#[test_case("(A&B)v(C&D)", "(A v C) & (A v D) & (B v C) & (B v D)" ; "dnf")]
+#[test_case("~(A<->B)", "(~A v ~B) & (A v B)" ; "denied biconditional")]
+#[test_case("Av~A", "TRUE" ; "tautology")]
+#[test_case("A&~A", "A & ~A" ; "contradiction")]
+#[test_case("FALSE", "FALSE" ; "constant false")]
+#[test_case("A&(AvB)", "A" ; "subsumed")]
+fn to_cnf(expr: &str, expected: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let cnf = tree.to_cnf();
+    assert_eq!(cnf.to_string(), expected);
+    let back = ExpressionTree::from(&cnf);
+    assert!(back.is_cnf());
+    assert!(back.log_eq(&tree));
+}
+
+#[test_case("@(x)(Px->Qx)&Pa" ; "universal")]
+#[test_case("~#(x)Px" ; "denied existential")]
+#[test_case("#(x)(Px&Qx)" ; "existential")]
+fn to_cnf_quantified(expr: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let cnf = tree.to_cnf();
+    for row in tree.satisfy_all(){
+        assert!(cnf.is_satisfied_by(&row));
+    }
+    assert_eq!(ExpressionTree::from(&cnf).satisfy_count(), tree.satisfy_count());
+}
+
+#[test_case(&["A", "B"], &["~A", "C"], "A", Some(&["B", "C"]) ; "resolves")]
+#[test_case(&["~A", "B"], &["A"], "A", Some(&["B"]) ; "either side denied")]
+#[test_case(&["A"], &["~A"], "A", Some(&[]) ; "empty resolvent")]
+#[test_case(&["A", "B"], &["A", "C"], "A", None ; "same sign")]
+#[test_case(&["A", "B"], &["~A", "C"], "B", None ; "not in both")]
+fn resolve(first: &[&str], second: &[&str], on: &str, expected: Option<&[&str]>){
+    let resolvent = clause(first).resolve(&clause(second), lit(on).sentence());
+    assert_eq!(resolvent, expected.map(clause));
+}
+
+#[test]
+fn resolvents(){
+    //resolving on A or B both give tautologies
+    assert!(clause(&["A", "B"]).resolvents(&clause(&["~A", "~B"])).is_empty());
+    assert_eq!(clause(&["A", "B"]).resolvents(&clause(&["~A", "C"])), vec![clause(&["B", "C"])]);
+}
+
+#[test_case(&["A"], &["A", "B"], true ; "subset")]
+#[test_case(&["A", "B"], &["A"], false ; "superset")]
+#[test_case(&[], &["A"], true ; "empty clause")]
+#[test_case(&["~A"], &["A", "B"], false ; "different sign")]
+fn subsumes(first: &[&str], second: &[&str], expected: bool){
+    assert_eq!(clause(first).subsumes(&clause(second)), expected);
+}
+
+#[test]
+fn clause_basics(){
+    let c = clause(&["B", "~A", "B"]);
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.to_string(), "~A v B");
+    assert!(c.contains(&lit("~A")));
+    assert!(c.unit().is_none());
+    assert_eq!(clause(&["~C"]).unit(), Some(&lit("~C")));
+    assert!(clause(&["A", "~A"]).is_tautology());
+    assert!(c.is_satisfied_by(&Assignment::new().set("A", false).unwrap()));
+    assert!(!c.is_satisfied_by(&Assignment::new().set("A", true).unwrap()));
+    assert_eq!(!lit("A"), lit("~A"));
+}
+
+#[test]
+fn simplify(){
+    let mut cnf: Cnf = [clause(&["A", "B"]), clause(&["A", "~A"]), clause(&["A"]), clause(&["C", "B"]), clause(&["B", "C"])].into_iter().collect();
+    cnf.simplify();
+    assert_eq!(cnf.clauses(), &vec![clause(&["A"]), clause(&["B", "C"])]);
+    assert_eq!(cnf.sentences(), vec![lit("A").sentence().clone(), lit("B").sentence().clone(), lit("C").sentence().clone()]);
+    assert!(!cnf.has_empty_clause());
+    cnf.add_clause(Clause::new());
+    assert!(cnf.has_empty_clause());
+    assert!(ExpressionTree::from(&cnf).is_inconsistency());
+}
+
+#[test_case(&[&["~A", "B"], &["~B", "C"]], &[("A", true)], &["B", "C"], None ; "chain")]
+#[test_case(&[&["A"], &["~A", "B", "C"]], &[("B", false)], &["A", "C"], None ; "unit clause")]
+#[test_case(&[&["A", "B"], &["C", "D"]], &[], &[], None ; "nothing forced")]
+#[test_case(&[&["~A", "B"], &["~A", "~B"]], &[("A", true)], &["B"], Some(1) ; "conflict")]
+#[test_case(&[&["A", "B"]], &[("A", false), ("B", false)], &[], Some(0) ; "assumptions conflict")]
+#[test_case(&[&["A", "B"]], &[("A", true)], &[], None ; "already satisfied")]
+fn unit_propagate(clauses: &[&[&str]], assumptions: &[(&str, bool)], forced: &[&str], conflict: Option<usize>){
+    let cnf: Cnf = clauses.iter().map(|c| clause(c)).collect();
+    let assumptions = Assignment::try_from(assumptions).unwrap();
+    let propagation = cnf.unit_propagate(&assumptions);
+    assert_eq!(propagation.literals().cloned().collect::<Vec<_>>(), forced.iter().map(|l| lit(l)).collect::<Vec<_>>());
+    assert_eq!(propagation.conflict(), conflict);
+    for imp in propagation.forced(){
+        assert!(cnf.clauses()[imp.clause()].contains(imp.literal()));
+        assert_eq!(propagation.assignment().get(imp.literal().sentence()), Some(imp.literal().is_positive()));
+    }
+}
+
+#[test]
+fn implication_graph(){
+    let cnf: Cnf = [clause(&["~A", "~B", "C"]), clause(&["~C", "D"])].into_iter().collect();
+    let propagation = cnf.unit_propagate(&Assignment::new().set("A", true).unwrap().set("B", true).unwrap());
+    let edges: Vec<_> = propagation.edges().map(|(from, to, c)| (from.clone(), to.clone(), c)).collect();
+    assert_eq!(edges, vec![(lit("A"), lit("C"), 0), (lit("B"), lit("C"), 0), (lit("C"), lit("D"), 1)]);
+    assert_eq!(propagation.forced()[1].antecedents(), &vec![lit("C")]);
+}
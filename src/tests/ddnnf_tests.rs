@@ -0,0 +1,70 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+fn sen(sentence: &str) -> Sentence{
+    Sentence::try_from(sentence).unwrap()
+}
+
+#[test_case("A" ; "single")]
+#[test_case("A&~A" ; "contradiction")]
+#[test_case("Av~A" ; "tautology")]
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("(A<->B)<->(C<->D)" ; "biconditionals")]
+#[test_case("((AvB)&(CvD))&(EvF)" ; "independent parts")]
+#[test_case("~((A&B)v(~C->D))" ; "denied")]
+#[test_case("@(x)(Px->Qx)" ; "universal")]
+#[test_case("(#(x)Px)&~Pa" ; "existential")]
+fn model_count(expr: &str){
+    let expr = tree(expr);
+    assert_eq!(expr.compile_ddnnf().model_count(), expr.satisfy_count()[0]);
+}
+
+#[test_case("(A->B)&(B->C)", true ; "satisfiable")]
+#[test_case("(A->B)&(A&~B)", false ; "unsatisfiable")]
+fn is_satisfiable(expr: &str, expected: bool){
+    assert_eq!(tree(expr).compile_ddnnf().is_satisfiable(), expected);
+}
+
+#[test_case("(A->B)&(B->C)", &[("A", false), ("C", true)], true ; "chain")]
+#[test_case("(A->B)&(B->C)", &[("C", true)], false ; "not entailed")]
+#[test_case("AvB", &[("A", true), ("B", true)], true ; "same clause")]
+#[test_case("A", &[("Z", true)], false ; "unknown sentence")]
+#[test_case("A", &[("B", true), ("B", false)], true ; "tautological clause")]
+#[test_case("A&~A", &[], true ; "contradiction entails empty clause")]
+#[test_case("A", &[], false ; "empty clause")]
+fn entails_clause(expr: &str, clause: &[(&str, bool)], expected: bool){
+    let clause: Vec<(Sentence, bool)> = clause.iter().map(|(s, b)| (sen(s), *b)).collect();
+    assert_eq!(tree(expr).compile_ddnnf().entails_clause(&clause), expected);
+}
+
+#[test_case("(A->B)&(B->C)", &[("A", true)] ; "forces the rest")]
+#[test_case("(A<->B)v(C&D)", &[("C", false)] ; "one branch")]
+#[test_case("(AvB)&(CvD)", &[("A", false), ("D", true)] ; "two atoms")]
+#[test_case("A&B", &[("A", false)] ; "unsatisfiable")]
+fn condition(expr: &str, assignment: &[(&str, bool)]){
+    let expr = tree(expr);
+    let mut restricted = expr.clone();
+    let mut tvals = Assignment::new();
+    for (s, b) in assignment{
+        tvals.insert(sen(s), *b);
+        restricted &= if *b {tree(s)} else {!tree(s)};
+    }
+    let conditioned = expr.compile_ddnnf().condition(&tvals);
+    assert_eq!(conditioned.model_count(), restricted.satisfy_count()[0]);
+    assert_eq!(conditioned.is_satisfiable(), restricted.is_satisfiable());
+}
+
+#[test]
+fn condition_twice(){
+    let ddnnf = tree("(AvB)&C").compile_ddnnf();
+    let once = ddnnf.condition(&Assignment::new().set("A", false).unwrap());
+    assert_eq!(once.model_count(), 1);
+    assert!(!once.condition(&Assignment::new().set("A", true).unwrap()).is_satisfiable());
+    assert_eq!(once.condition(&Assignment::new().set("A", false).unwrap()).model_count(), 1);
+}
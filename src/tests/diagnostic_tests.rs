@@ -0,0 +1,48 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn diagnose(expr: &str) -> Vec<(ClawgicError, &str)>{
+    ExpressionTree::diagnose(expr).into_iter().map(|d| {
+        let span = d.span();
+        (d.into_error(), &expr[span])
+    }).collect()
+}
+
+#[test_case("A & ? & B", vec![(ClawgicError::UnknownSymbol("?".to_string()), "?"), (ClawgicError::AmbiguousExpression, "&")] ; "keeps going after a bad symbol")]
+#[test_case("A &", vec![(ClawgicError::TooManyOperators, "")] ; "missing operand at the end")]
+#[test_case("& A v v B", vec![(ClawgicError::TooManyOperators, "&"), (ClawgicError::TooManyOperators, "v")] ; "extra operators")]
+#[test_case("A B", vec![(ClawgicError::NotEnoughOperators, "")] ; "missing operator")]
+#[test_case("(A & B", vec![(ClawgicError::InvalidParentheses, "(")] ; "unclosed parenthesis")]
+#[test_case("A) & (B))", vec![(ClawgicError::InvalidParentheses, ")"), (ClawgicError::InvalidParentheses, ")")] ; "unopened parentheses")]
+#[test_case("(A & ) v BC <- D", vec![(ClawgicError::TooManyOperators, ""), (ClawgicError::InvalidPredicateName("BC".to_string()), "BC"), (ClawgicError::UnknownSymbol("<-".to_string()), "<-")] ; "several problems")]
+#[test_case("   ", vec![(ClawgicError::EmptyExpression, "   ")] ; "empty")]
+fn diagnostics(expr: &str, expected: Vec<(ClawgicError, &str)>){
+    assert_eq!(diagnose(expr), expected);
+    assert!(ExpressionTree::new(expr).is_err());
+}
+
+#[test_case("A & B" ; "simple")]
+#[test_case("(@xPx) -> (#yRay)" ; "quantifiers")]
+#[test_case("~(A₁ v A₂)" ; "subscripts")]
+fn no_diagnostics(expr: &str){
+    assert!(ExpressionTree::diagnose(expr).is_empty());
+}
+
+#[test]
+fn subscript_spans(){
+    let expr = "(A₁₂ & ?) v A₃";
+    let diagnostics = ExpressionTree::diagnose(expr);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(&expr[diagnostics[0].span()], "?");
+}
+
+#[test]
+fn new_with_diagnostics(){
+    let config = ParserConfig::default();
+    assert!(ExpressionTree::new_with_diagnostics("A -> B", None, &config).unwrap().lit_eq(&ExpressionTree::new("A -> B").unwrap()));
+    let diagnostics = ExpressionTree::new_with_diagnostics("A -> ( B", None, &config).unwrap_err();
+    assert_eq!(diagnostics, vec![Diagnostic::new(ClawgicError::InvalidParentheses, 5..6)]);
+    assert_eq!(diagnostics[0].to_string(), "Invalid parenthesis (at 5..6)");
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+#[test_case("DeMorgan", "~(A&B)", "~Av~B" ; "demorgan conjunction")]
+#[test_case("DeMorgan", "~Av~B", "~(A&B)" ; "demorgan backwards")]
+#[test_case("DeMorgan", "~(AvB)", "~A&~B" ; "demorgan disjunction")]
+#[test_case("Commutation", "A&(BvC)", "(BvC)&A" ; "commutation")]
+#[test_case("Distribution", "A&(BvC)", "(A&B)v(A&C)" ; "distribution")]
+#[test_case("DoubleNegation", "~~~A", "~A" ; "double negation")]
+#[test_case("Implication", "A->~B", "~Av~B" ; "implication")]
+#[test_case("Exportation", "A->(B->C)", "(A&B)->C" ; "exportation backwards")]
+#[test_case("MaterialEquivalence", "A<->B", "(A->B)&(B->A)" ; "material equivalence")]
+fn apply(name: &str, before: &str, after: &str){
+    let registry = EquivalenceRegistry::standard();
+    assert_eq!(registry.get(name).unwrap().apply(&tree(before)), Some(tree(after)));
+}
+
+#[test_case("DeMorgan", "A&B" ; "demorgan")]
+#[test_case("Exportation", "A->B" ; "exportation")]
+fn apply_none(name: &str, before: &str){
+    assert!(EquivalenceRegistry::standard().get(name).unwrap().apply(&tree(before)).is_none());
+}
+
+#[test_case("DeMorgan", "C->~(A&B)", "C->(~Av~B)", true ; "inside")]
+#[test_case("DoubleNegation", "@(x)(~~P(x)&Q(x))", "@(x)(P(x)&Q(x))", true ; "inside quantifier")]
+#[test_case("Commutation", "(A&B)v(C&D)", "(B&A)v(C&D)", true ; "left operand")]
+#[test_case("Commutation", "(A&B)v(C&D)", "(B&A)v(D&C)", false ; "two replacements")]
+#[test_case("Commutation", "(A&B)v(C&D)", "(A&B)v(C&D)", false ; "nothing replaced")]
+#[test_case("Transposition", "A->B", "B->A", false ; "wrong rule")]
+fn justifies(name: &str, before: &str, after: &str, expected: bool){
+    let registry = EquivalenceRegistry::standard();
+    assert_eq!(registry.get(name).unwrap().justifies(&tree(before), &tree(after)), expected);
+}
+
+#[test]
+fn standard_is_valid(){
+    let registry = EquivalenceRegistry::standard();
+    assert_eq!(registry.len(), 10);
+    for equivalence in registry.iter(){
+        for (left, right) in equivalence.forms(){
+            assert!(ExpressionTree::from(left.clone()).log_eq(&ExpressionTree::from(right.clone())), "{}", equivalence.name());
+        }
+    }
+}
+
+#[test]
+fn register(){
+    let mut registry = EquivalenceRegistry::new();
+    assert!(registry.is_empty());
+    let absorption = Equivalence::new("Absorption", "?p&(?pv?q)", "?p").unwrap()
+        .with_form("?pv(?p&?q)", "?p").unwrap();
+    assert!(registry.register(absorption).is_none());
+    assert_eq!(registry.names(), vec!["Absorption"]);
+    assert!(registry.get("Absorption").unwrap().justifies(&tree("Cv(C&D)"), &tree("C")));
+    assert!(registry.remove("Absorption").is_some());
+    assert!(registry.get("Absorption").is_none());
+}
+
+#[test]
+fn not_equivalent(){
+    let result = Equivalence::new("Converse", "?p->?q", "?q->?p");
+    assert_eq!(result.unwrap_err(), ClawgicError::NotEquivalent("Converse".to_string()));
+    let result = Equivalence::new("Fine", "?p", "~~?p").unwrap().with_form("?p&?q", "?p");
+    assert_eq!(result.unwrap_err(), ClawgicError::NotEquivalent("Fine".to_string()));
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn sen0(name: &str) -> Sentence{
+    Sentence::new(&Predicate::new(name, 0).unwrap(), &vec![]).unwrap()
+}
+
+fn system(exprs: &[&str]) -> ExpressionSystem{
+    exprs.iter().map(|e| ExpressionTree::new(e).unwrap()).collect()
+}
+
+#[test_case(&["A&B", "BvC"], &[("A", true), ("B", false), ("C", true)], Ok(vec![false, true]) ; "every output")]
+#[test_case(&["A&B", "~A"], &[("A", false), ("B", true)], Ok(vec![false, true]) ; "shared sentence")]
+#[test_case(&["A", "D"], &[("A", true)], Err(ClawgicError::UninitializedSentence("D".to_string())) ; "missing sentence")]
+fn evaluate(exprs: &[&str], tvals: &[(&str, bool)], expected: Result<Vec<bool>, ClawgicError>){
+    let mut system = system(exprs);
+    system.set_tvals(&Assignment::try_from(tvals).unwrap());
+    assert_eq!(system.evaluate(), expected);
+}
+
+#[test]
+fn set_tval(){
+    let mut system = system(&["A", "~A", "B"]);
+    system.set_tval(&sen0("A"), true);
+    system.set_tval(&sen0("B"), false);
+    assert_eq!(system.evaluate(), Ok(vec![true, false, false]));
+}
+
+#[test]
+fn shared_context(){
+    let mut system = system(&["A&B", "~A"]);
+    system.set_tval(&sen0("A"), true);
+    system.set_tval(&sen0("B"), true);
+    assert_eq!(system.context().get_tval(&sen0("A")), Some(true));
+    assert!(system.iter().all(|t| t.context().is_some()));
+
+    //setting it on the context is the same as setting it on the system
+    system.context().set_tval(&sen0("A"), false);
+    assert_eq!(system.evaluate(), Ok(vec![false, true]));
+}
+
+#[test]
+fn clone_has_own_context(){
+    let mut system = system(&["A", "~A"]);
+    system.set_tval(&sen0("A"), true);
+    let mut clone = system.clone();
+    assert_eq!(clone.evaluate(), Ok(vec![true, false]));
+    clone.set_tval(&sen0("A"), false);
+    assert_eq!(clone.evaluate(), Ok(vec![false, true]));
+    assert_eq!(system.evaluate(), Ok(vec![true, false]));
+}
+
+#[test]
+fn truth_table(){
+    let system = system(&["A&B", "AvC"]);
+    let table = system.truth_table();
+    assert_eq!(table.len(), 8);
+    for (row, outputs) in table{
+        assert_eq!(row.len(), 3);
+        assert_eq!(outputs, vec![row[&sen0("A")] && row[&sen0("B")], row[&sen0("A")] || row[&sen0("C")]]);
+    }
+    assert_eq!(system.sentences(), vec![sen0("A"), sen0("B"), sen0("C")]);
+}
+
+#[test_case(&["(A&B)vC", "(A&B)->D"], &[("A&B", &[0, 1])] ; "one shared")]
+#[test_case(&["((A&B)vC)&D", "E->((A&B)vC)", "(A&B)<->E"], &[("(A&B)vC", &[0, 1]), ("A&B", &[0, 1, 2])] ; "nested")]
+#[test_case(&["A&B", "B&A"], &[] ; "only literally the same")]
+#[test_case(&["(A&B)v(A&B)"], &[] ; "only one output")]
+fn shared_subexpressions(exprs: &[&str], expected: &[(&str, &[usize])]){
+    let shared = system(exprs).shared_subexpressions();
+    assert_eq!(shared.len(), expected.len());
+    for ((tree, outputs), (expr, expected_outputs)) in shared.iter().zip(expected.iter()){
+        assert!(tree.lit_eq(&ExpressionTree::new(expr).unwrap()), "{}", tree.infix(None));
+        assert_eq!(outputs, expected_outputs);
+    }
+}
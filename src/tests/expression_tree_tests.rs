@@ -1,8 +1,9 @@
 #![cfg(test)]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use test_case::test_case;
-use crate::{expression_tree::universe::Universe, prelude::*};
+use crate::{expression_tree::node::Node, expression_tree::universe::Universe, prelude::*};
 
 fn sen0(name: &str) -> Sentence{
     Sentence::new(&Predicate::new(name, 0).unwrap(), &vec![]).unwrap()
@@ -178,6 +179,24 @@ fn evaluate_with_uni(expression: &str, ex1: bool, ex2: bool, ex3: bool, ex4: boo
     assert_eq!(t.evaluate_with_uni(&v).unwrap(), ex4, "failed false true");
 }
 
+#[test_case("A&B", "A \\land B" ; "one connective")]
+#[test_case("~(A1vB)<->C", "\\neg (A_{1} \\lor B) \\leftrightarrow C" ; "denied operand")]
+#[test_case("~~(A->FALSE)", "\\neg \\neg (A \\rightarrow \\bot)" ; "denied root")]
+#[test_case("@(x, y2)(P(x)&~Q(y2))", "\\forall x \\forall y_{2} (P(x) \\land \\neg Q(y_{2}))" ; "quantifier")]
+fn latex(expression: &str, expected: &str){
+    assert_eq!(ExpressionTree::new(expression).unwrap().latex(), expected);
+}
+
+#[test_case("Rain_1 & Wet_day", "\\mathit{Rain}_{1} \\land \\mathit{Wet\\_day}" ; "underscores")]
+#[test_case("Rain -> Wet(ground)", "\\mathit{Rain} \\rightarrow \\mathit{Wet}(\\mathit{ground})" ; "multi-letter names")]
+#[test_case("@(x_1) Loves(x_1, bob2)", "\\forall x_{1} \\mathit{Loves}(x_{1}, \\mathit{bob}_{2})" ; "quantified long names")]
+#[test_case("P12 v Q_2_3", "P_{12} \\lor \\mathit{Q\\_2}_{3}" ; "only the last digits")]
+fn latex_long_names(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+    assert_eq!(ExpressionTree::new_with_config(expression, None, &config).unwrap().latex(), expected);
+}
+
 #[test_case("A&B", "&AB" ; "One connective")]
 #[test_case("(A&B)vC", "∨&ABC" ; "Two connectives")]
 #[test_case("(A&B)vC->D", "➞∨&ABCD" ; "Three connectives")]
@@ -260,6 +279,10 @@ fn assignop_construction(){
 #[test_case("A&B", "A&B", true ; "same expression")]
 #[test_case("A&~A", "B&~B", true ; "inconsistencies")]
 #[test_case("A&B", "A&C", false ; "completely different")]
+#[test_case("@x(Px&Qx)", "(@xPx)&(@xQx)", true ; "universal distribution")]
+#[test_case("#x(PxvQx)", "(#xPx)v(#xQx)", true ; "existential distribution")]
+#[test_case("~@xPx", "#x~Px", true ; "quantifier exchange")]
+#[test_case("@xPx", "Pa", false ; "universal and instance")]
 fn log_eq(expr1: &str, expr2: &str, expected: bool){
     let t1 = ExpressionTree::new(expr1).unwrap();
     let t2 = ExpressionTree::new(expr2).unwrap();
@@ -267,6 +290,45 @@ fn log_eq(expr1: &str, expr2: &str, expected: bool){
     assert_eq!(t1.log_eq(&t2), expected);
 }
 
+#[test_case("A&B", "B&A", &[] ; "equivalent")]
+#[test_case("A->B", "B->A", &[&[("A", true), ("B", false)], &[("A", false), ("B", true)]] ; "converse")]
+#[test_case("A", "AvB", &[&[("A", false), ("B", true)]] ; "different sentences")]
+#[test_case("A&~A", "B&~B", &[] ; "inconsistencies")]
+fn disagreements(expr1: &str, expr2: &str, expected: &[&[(&str, bool)]]){
+    let t1 = ExpressionTree::new(expr1).unwrap();
+    let t2 = ExpressionTree::new(expr2).unwrap();
+    let rows = t1.disagreements(&t2);
+
+    assert_eq!(rows.len(), expected.len());
+    for row in expected{
+        let row = Assignment::try_from(*row).unwrap();
+        assert!(rows.contains(&row), "missing {row:?}");
+    }
+    for row in rows{
+        let (mut t1, mut t2) = (t1.clone(), t2.clone());
+        t1.set_tvals(&row);
+        t2.set_tvals(&row);
+        assert_ne!(t1.evaluate().unwrap(), t2.evaluate().unwrap());
+    }
+}
+
+#[test_case(20, true ; "same sentences")]
+#[test_case(21, false ; "extra sentence")]
+fn log_eq_many_sentences(right_len: usize, expected: bool){
+    //A0&(A1&(A2&...)) vs ((...&A2)&A1)&A0
+    let mut left = ExpressionTree::new("A0").unwrap();
+    for i in 1..20{
+        left = ExpressionTree::new(&format!("A{i}")).unwrap() & left;
+    }
+    let mut right = ExpressionTree::new(&format!("A{}", right_len - 1)).unwrap();
+    for i in (0..right_len - 1).rev(){
+        right &= ExpressionTree::new(&format!("A{i}")).unwrap();
+    }
+
+    assert_eq!(left.log_eq(&right), expected);
+    assert_eq!(right.log_eq(&left), expected);
+}
+
 #[test_case("A&B", "B&A", false ; "swapped operands")]
 #[test_case("A&B", "~~(A&B)", false ; "double negation")]
 #[test_case("A&B", "A&B", true ; "same expression")]
@@ -284,6 +346,7 @@ fn lit_eq(expr1: &str, expr2: &str, expected: bool){
 #[test_case("A&B", "A&B", true ; "same expression")]
 #[test_case("A&~A", "B&~B", false ; "inconsistencies")]
 #[test_case("A&B", "A&C", false ; "completely different")]
+#[test_case("A", "A&(Bv~B)", false ; "different sentences")]
 fn syn_eq(expr1: &str, expr2: &str, expected: bool){
     let t1 = ExpressionTree::new(expr1).unwrap();
     let t2 = ExpressionTree::new(expr2).unwrap();
@@ -296,11 +359,7 @@ fn syn_eq(expr1: &str, expr2: &str, expected: bool){
 #[test_case("A&B->C&D", Err(ClawgicError::UninitializedSentence("D".to_string())) ; "under-populating")]
 fn set_tvals(expr: &str, expected: Result<bool, ClawgicError>){
     let mut t = ExpressionTree::new(expr).unwrap();
-    let mut uni = HashMap::new();
-    uni.insert(sen0("A"), true);
-    uni.insert(sen0("B"), true);
-    uni.insert(sen0("C"), true);
-    t.set_tvals(&uni);
+    t.set_tvals(&Assignment::try_from([("A", true), ("B", true), ("C", true)]).unwrap());
 
     assert_eq!(t.evaluate(), expected);
 }
@@ -318,6 +377,9 @@ fn chaining_functions(){
 #[test_case("Av~A", true ; "tautology")]
 #[test_case("A&~A", false ; "inconsistency")]
 #[test_case("A", true ; "contingency")]
+#[test_case("(@xPx)&~Pa", false ; "universal instance")]
+#[test_case("(#xPx)&~Pa", true ; "existential elsewhere")]
+#[test_case("(@xPx)&(#x~Px)", false ; "quantifier clash")]
 fn is_satisfiable(expr: &str, expected: bool){
     assert_eq!(ExpressionTree::new(expr).unwrap().is_satisfiable(), expected);
 }
@@ -363,15 +425,145 @@ fn satisfy_count(expr: &str, count: u128){
     assert_eq!(tree.satisfy_count()[0], count);
 }
 
+#[test_case("(A&B)v(A&~B)", "A" ; "merge")]
+#[test_case("A&(Bv~B)", "A" ; "irrelevant sentence")]
+#[test_case("Av~A", "TRUE" ; "tautology")]
+#[test_case("A&~A", "FALSE" ; "inconsistency")]
+#[test_case("A->B", "~AvB" ; "conditional")]
+#[test_case("((A&B)v(~A&C))v(B&C)", "(A&B)v(~A&C)" ; "consensus")]
+fn minimize(expr: &str, expected: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let minimized = tree.minimize();
+    assert!(minimized.lit_eq(&ExpressionTree::new(expected).unwrap()), "{}", minimized.infix(None));
+    assert!(minimized.log_eq(&tree));
+    assert!(minimized.is_dnf());
+}
+
+#[test_case("A&B", "A&~B", "A" ; "single don't care")]
+#[test_case("(~A&~B)&C", "~C", "~A&~B" ; "don't care on another sentence")]
+#[test_case("A", "Av~A", "FALSE" ; "everything is don't care")]
+fn minimize_with_dont_care(expr: &str, dont_care: &str, expected: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let dont_care = ExpressionTree::new(dont_care).unwrap();
+    let minimized = tree.minimize_with_dont_care(&dont_care);
+    assert!(minimized.lit_eq(&ExpressionTree::new(expected).unwrap()), "{}", minimized.infix(None));
+    assert!(minimized.log_eq_with_dont_care(&tree, &dont_care));
+}
+
+#[test_case("A&B", "A", "A&~B", true ; "disagree only on don't care")]
+#[test_case("A&B", "A", "~A", false ; "disagree elsewhere")]
+#[test_case("A", "B", "FALSE", false ; "no don't care")]
+fn log_eq_with_dont_care(expr1: &str, expr2: &str, dont_care: &str, expected: bool){
+    let t1 = ExpressionTree::new(expr1).unwrap();
+    let t2 = ExpressionTree::new(expr2).unwrap();
+    let dont_care = ExpressionTree::new(dont_care).unwrap();
+    assert_eq!(t1.log_eq_with_dont_care(&t2, &dont_care), expected);
+}
+
+#[test_case("~(A<->B)", "AvB", "~(A&B)", true ; "agree under constraint")]
+#[test_case("~(A<->B)", "AvB", "~A", true ; "constraint fixes a sentence")]
+#[test_case("A", "B", "A", false ; "disagree under constraint")]
+#[test_case("A", "~A", "FALSE", true ; "unsatisfiable constraint")]
+#[test_case("A", "B", "C", false ; "constraint on another sentence")]
+fn log_eq_under(expr1: &str, expr2: &str, constraint: &str, expected: bool){
+    let t1 = ExpressionTree::new(expr1).unwrap();
+    let t2 = ExpressionTree::new(expr2).unwrap();
+    let constraint = ExpressionTree::new(constraint).unwrap();
+    assert_eq!(t1.log_eq_under(&t2, &constraint), expected);
+    assert_eq!(t2.log_eq_under(&t1, &constraint), expected);
+}
+
+#[test_case(&["A->B", "B->A", "~B->~A", "~A->~B", "A<->B"], &[&[0, 2], &[1, 3], &[4]] ; "contrapositives")]
+#[test_case(&["Av~A", "TRUE", "B->B", "A&~A", "FALSE"], &[&[0, 1, 2], &[3, 4]] ; "constants")]
+#[test_case(&["@xPx", "~#x~Px", "Pa&Pb", "#xPx"], &[&[0, 1], &[2], &[3]] ; "quantifiers")]
+#[test_case(&["A", "B", "C"], &[&[0], &[1], &[2]] ; "all different")]
+#[test_case(&[], &[] ; "no trees")]
+fn group_by_equivalence(exprs: &[&str], expected: &[&[usize]]){
+    let trees: Vec<ExpressionTree> = exprs.iter().map(|e| ExpressionTree::new(e).unwrap()).collect();
+    let classes = ExpressionTree::group_by_equivalence(&trees);
+    assert_eq!(classes, expected.iter().map(|c| c.to_vec()).collect::<Vec<_>>());
+    for class in classes.iter(){
+        assert!(class.iter().all(|i| trees[*i].log_eq(&trees[class[0]])));
+    }
+}
+
+#[test_case("A&(Bv~B)", &["A"], &["B"] ; "tautological part")]
+#[test_case("(A->B)&(A->~B)", &["A"], &["B"] ; "cancelling consequents")]
+#[test_case("A<->A", &[], &["A"] ; "tautology")]
+#[test_case("(A&B)v(~A&C)", &["A", "B", "C"], &[] ; "everything matters")]
+fn essential_vars(expr: &str, essential: &[&str], irrelevant: &[&str]){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let to_sentences = |names: &[&str]| names.iter().map(|n| sen0(n)).collect::<Vec<_>>();
+    assert_eq!(tree.essential_vars(), to_sentences(essential));
+    assert_eq!(tree.irrelevant_vars(), to_sentences(irrelevant));
+}
+
+#[test_case("(A&B)v(A&~B)", &["A"], 1 ; "projected away")]
+#[test_case("(A&B)v(A&~B)", &["A", "B"], 2 ; "nothing projected away")]
+#[test_case("(X<->(A&B))&X", &["A", "B"], 1 ; "auxiliary sentence")]
+#[test_case("A", &["A", "C"], 2 ; "sentence not in expression")]
+#[test_case("A&~A", &["A"], 0 ; "inconsistency")]
+#[test_case("A", &[], 1 ; "empty projection")]
+fn satisfy_projected(expr: &str, onto: &[&str], count: u128){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let onto: Vec<Sentence> = onto.iter().map(|s| sen0(s)).collect();
+    let models = tree.satisfy_all_projected(&onto);
+
+    assert_eq!(tree.satisfy_count_projected(&onto), count);
+    assert_eq!(models.len() as u128, count);
+    for m in models.iter(){
+        assert_eq!(m.len(), onto.len());
+        assert!(tree.is_satisfiable_with(&conjoin_literals(m)));
+    }
+}
+
+//turns a map of truth values into a conjunction of literals
+fn conjoin_literals(tvals: &Assignment) -> ExpressionTree{
+    tvals.iter().fold(ExpressionTree::TRUE(), |acc, (s, b)| {
+        let lit = if *b {s.expr()} else {!s.expr()};
+        acc & lit
+    })
+}
+
 #[test_case("Av~A", true ; "tautology")]
 #[test_case("A&~A", false ; "inconsistency")]
 #[test_case("A", false ; "contingency")]
+#[test_case("(@xPx)->(#xPx)", true ; "universal implies existential")]
+#[test_case("Pa->(@xPx)", false ; "instance doesn't imply universal")]
 fn is_tautology(expr: &str, expected: bool){
     let tree = ExpressionTree::new(expr).unwrap();
 
     assert_eq!(tree.is_tautology(), expected);
 }
 
+#[test_case("A", "A", true ; "itself")]
+#[test_case("B", "A->B", false ; "implication alone")]
+#[test_case("B", "A&(A->B)", true ; "modus ponens")]
+#[test_case("Av~A", "B", true ; "tautology")]
+#[test_case("A", "B", false ; "unrelated")]
+fn is_tautology_with(expr: &str, aux: &str, expected: bool){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let aux = ExpressionTree::new(aux).unwrap();
+
+    assert_eq!(tree.is_tautology_with(&aux), expected);
+}
+
+#[test_case("Av~A", None ; "tautology")]
+#[test_case("A&~A", Some(&[("A", false)]) ; "inconsistency")]
+#[test_case("A->B", Some(&[("A", true), ("B", false)]) ; "contingency")]
+#[test_case("(@xPx)->Pa", None ; "universal instance")]
+fn counterexample(expr: &str, expected: Option<&[(&str, bool)]>){
+    let mut tree = ExpressionTree::new(expr).unwrap();
+    let counterexample = tree.counterexample();
+    assert_eq!(counterexample.is_some(), expected.is_some());
+    if let Some(counterexample) = counterexample{
+        let expected = Assignment::try_from(expected.unwrap()).unwrap();
+        assert_eq!(counterexample, expected);
+        tree.set_tvals(&counterexample);
+        assert!(!tree.evaluate().unwrap());
+    }
+}
+
 #[test_case("Av~A", false ; "tautology")]
 #[test_case("A&~A", true ; "inconsistency")]
 #[test_case("A", false ; "contingency")]
@@ -431,6 +623,94 @@ fn evaluate_after_deny(){
     assert!(tree.not().evaluate().unwrap());
 }
 
+#[test_case("(A&B(a))v(C->~(D<->A))" ; "operators")]
+#[test_case("~(B(a)&(CvD))->(#(x)(B(x)&A))" ; "quantifier")]
+#[test_case("@(x)(B(x)->C)&~(A&D)" ; "rewritten")]
+fn evaluate_incremental(expression: &str){
+    let mut t = ExpressionTree::new(expression).unwrap();
+    let sentences = [sen0("A"), senx("B", vec!["a"]), sen0("C"), sen0("D")];
+    for sen in sentences.iter(){
+        t.set_tval(sen, false);
+    }
+    //flip one sentence at a time (gray code) so only part of the tree changes each time
+    for i in 1..32usize{
+        let sen = &sentences[i.trailing_zeros() as usize % sentences.len()];
+        let b = !t.universe().get_tval(sen).unwrap();
+        t.set_tval(sen, b);
+        if i == 16{
+            t.demorgans();
+        }
+        assert_eq!(t.evaluate().unwrap(), t.evaluate_with_uni(t.universe()).unwrap(), "step {i}");
+    }
+}
+
+#[test]
+fn on_variable_change(){
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let log = changes.clone();
+    let mut t = ExpressionTree::new("A&(@(x)B(x))").unwrap();
+    t.on_variable_change(move |sen, b| log.lock().unwrap().push((sen.clone(), b)));
+    t.set_tval(&sen0("A"), true);
+    t.set_tval(&sen0("A"), true);
+    t.set_tval(&senx("B", vec!["a"]), false);
+    t.set_tval(&sen0("C"), true);
+    let mut t2 = t.clone().not();
+    t2.set_tval(&sen0("A"), false);
+    t.clear_observers();
+    t.set_tval(&sen0("A"), false);
+    assert_eq!(*changes.lock().unwrap(), vec![(sen0("A"), true), (senx("B", vec!["a"]), false), (sen0("A"), false)]);
+}
+
+#[test]
+fn on_variable_change_both_operands(){
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let (left_log, right_log) = (changes.clone(), changes.clone());
+    let mut left = ExpressionTree::new("A").unwrap();
+    left.on_variable_change(move |sen, _| left_log.lock().unwrap().push(("left", sen.clone())));
+    let mut right = ExpressionTree::new("B").unwrap();
+    right.on_variable_change(move |sen, _| right_log.lock().unwrap().push(("right", sen.clone())));
+
+    //a tree joined with a clone of itself doesn't hear about things twice
+    let mut t = left.clone().or(left).con(right);
+    t.set_tval(&sen0("B"), true);
+    assert_eq!(*changes.lock().unwrap(), vec![("left", sen0("B")), ("right", sen0("B"))]);
+}
+
+#[test]
+fn undo_redo(){
+    let mut t = ExpressionTree::new("~(A&B)").unwrap();
+    t.deny();
+    assert!(!t.undo(), "history is off");
+
+    t.enable_history();
+    let steps = [
+        t.infix(None),
+        t.deny().infix(None),
+        t.demorgans().unwrap().infix(None),
+        t.replace_sentence(&sen0("A"), &ExpressionTree::new("C->D").unwrap()).infix(None),
+        {t.monotenize(); t.infix(None)},
+    ];
+    assert!(t.transposition().is_none());
+    t.set_tval(&sen0("B"), true);
+
+    for expected in steps.iter().rev().skip(1){
+        assert!(t.undo());
+        assert_eq!(&t.infix(None), expected);
+    }
+    assert!(!t.undo());
+    assert_eq!(t.universe().get_tval(&sen0("B")), Some(true), "truth values aren't undone");
+    t.set_tval(&sen0("A"), false);
+    assert!(!t.evaluate().unwrap());
+
+    assert!(t.redo());
+    assert!(t.redo());
+    assert_eq!(t.infix(None), steps[2]);
+    t.negate();
+    assert!(!t.can_redo(), "new transformation clears redo");
+    assert!(t.undo());
+    assert_eq!(t.infix(None), steps[2]);
+}
+
 #[test_case("¬(A∧B)∨(C➞TRUE⟷E)", "~(A&B)v(C->TRUE<->E)" ; "mathematical")]
 #[test_case("¬(A⋅B)+(C➞TRUE⟷E)", "~(A&B)v(C->TRUE<->E)" ; "logic gates")]
 #[test_case("~(A*B)+(C->TRUE<->E)", "~(A&B)v(C->TRUE<->E)" ; "logic gates ascii")]
@@ -620,4 +900,381 @@ fn main_connective(expr: &str, op: Option<Operator>){
 fn main_conn_non_tilde(expr: &str, op: Option<Operator>){
     let tree = ExpressionTree::new(expr).unwrap();
     assert_eq!(tree.main_conn_non_tilde(), op);
-}
\ No newline at end of file
+}
+
+#[test_case("A&B", vec![("A", true)], "B" ; "true conjunct")]
+#[test_case("A&B", vec![("A", false)], "FALSE" ; "false conjunct")]
+#[test_case("A->B", vec![("B", false)], "~A" ; "false consequent")]
+#[test_case("~(A&B)", vec![("A", true)], "~B" ; "denied operator")]
+#[test_case("(A<->B)vC", vec![("C", false)], "A<->B" ; "false disjunct")]
+#[test_case("A<->B", vec![("A", false)], "~B" ; "false biconditional side")]
+#[test_case("A&B", vec![("A", true), ("B", true)], "TRUE" ; "everything known")]
+#[test_case("A&B", vec![("C", true)], "A&B" ; "irrelevant sentence")]
+fn restrict(expr: &str, tvals: Vec<(&str, bool)>, expected: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let tvals = tvals.into_iter().map(|(s, b)| (sen0(s), b)).collect();
+    let expected = ExpressionTree::new(expected).unwrap();
+
+    assert!(tree.restrict(&tvals).lit_eq(&expected));
+}
+
+#[test]
+fn restrict_bound_sentences(){
+    let tree = ExpressionTree::new("(@x(Ax&B))&Ax").unwrap();
+    let tvals = Assignment::new().with(senx("A", vec!["x"]), true).set("B", true).unwrap();
+    let expected = ExpressionTree::new("@xAx").unwrap();
+
+    assert!(tree.restrict(&tvals).lit_eq(&expected));
+}
+
+#[test]
+fn restrict_shrinks_universe(){
+    let mut tree = ExpressionTree::new("A&(BvC)").unwrap();
+    tree.set_tval(&sen0("A"), true);
+    let restricted = tree.restrict(&Assignment::new().set("B", false).unwrap());
+
+    assert!(!restricted.universe().contains_predicate(sen0("B").predicate()));
+    assert_eq!(restricted.universe().get_tval(&sen0("A")), Some(true));
+}
+
+#[test_case("Rain -> Wet(ground)", "Rain➞Wet(ground)" ; "multi-letter names")]
+#[test_case("P_1 v Q_2", "P_1∨Q_2" ; "underscores and spaced disjunction")]
+#[test_case("@(x_1) Loves(x_1, bob)", "∀(x_1)(Loves(x_1, bob))" ; "quantified long names")]
+#[test_case("TRUE & A", "TRUE&A" ; "constants still work")]
+fn new_long_names(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+    let t = ExpressionTree::new_with_config(expression, None, &config).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+}
+
+#[test_case("Rain & wet", ClawgicError::InvalidPredicateName("w".to_string()) ; "lowercase predicate")]
+#[test_case("Wet(Ground)", ClawgicError::InvalidVariableName("Ground".to_string()) ; "uppercase variable")]
+#[test_case("Wet(1a)", ClawgicError::InvalidVariableName("1a".to_string()) ; "digit variable")]
+fn new_long_names_err(expression: &str, err: ClawgicError){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+
+    assert_eq!(ExpressionTree::new_with_config(expression, None, &config).unwrap_err(), err);
+}
+
+#[test_case("A&BvC", "(A&B)∨C" ; "and over or")]
+#[test_case("AvB&C", "A∨(B&C)" ; "and over or on the right")]
+#[test_case("A<->B->C", "(A⟷B)➞C" ; "bicon over con")]
+#[test_case("A&B<->C", "(A&B)⟷C" ; "and over bicon")]
+fn set_precedence(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_precedence(Operator::AND, 4).set_precedence(Operator::BICON, 3);
+    let t = ExpressionTree::new_with_config(expression, None, &config).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+    assert_eq!(ExpressionTree::new("A&BvC").unwrap_err(), ClawgicError::AmbiguousExpression);
+}
+
+#[test_case("A→B", "A➞B" ; "arrow")]
+#[test_case("A⊃(B⇒C)", "A➞(B➞C)" ; "horseshoe and double arrow")]
+#[test_case("(A↔B)·(C≡D)", "(A⟷B)&(C⟷D)" ; "biconditionals and dot")]
+#[test_case("∼A∥B", "¬A∨B" ; "tilde operator and parallel")]
+#[test_case("A⊕B", "¬(A⟷B)" ; "xor")]
+#[test_case("A⊼B", "¬(A&B)" ; "nand")]
+#[test_case("A↓B", "¬(A∨B)" ; "nor")]
+#[test_case("A⊻∼B", "¬(A⟷¬B)" ; "xor with denial")]
+#[test_case("(∀xPx)∧(∃yQy)", "∀(x)(P(x))&∃(y)(Q(y))" ; "quantifiers")]
+fn new_unicode(expression: &str, expected: &str){
+    let t = ExpressionTree::new_with_notation(expression, &OperatorNotation::ascii()).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+}
+
+#[test_case("⊤&⊥", "TRUE&FALSE" ; "tee and bottom")]
+#[test_case("1v~0", "TRUE∨¬FALSE" ; "digits")]
+#[test_case("(A&1)->0", "(A&TRUE)➞FALSE" ; "digits next to sentences")]
+fn new_constants(expression: &str, expected: &str){
+    let t = ExpressionTree::new(expression).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+}
+
+#[test_case("T", "TRUE" ; "bare t")]
+#[test_case("~F->T1", "¬FALSE➞T1" ; "digits are still sentences")]
+#[test_case("(@xFx)&T", "∀(x)(F(x))&TRUE" ; "arguments are still sentences")]
+fn new_letter_constants(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_letter_constants(true);
+    let t = ExpressionTree::new_with_config(expression, None, &config).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+    assert!(ExpressionTree::new(expression).unwrap().infix(None) != expected);
+}
+
+#[test]
+fn print_constants(){
+    let t = ExpressionTree::new("TRUE->~FALSE").unwrap();
+
+    assert_eq!(t.infix(Some(&OperatorNotation::mathematical())), "⊤➞¬⊥");
+    assert_eq!(t.infix(Some(&OperatorNotation::bits_ascii())), "1->~0");
+    assert_eq!(t.infix(Some(&OperatorNotation::ascii())), "TRUE->~FALSE");
+    //every notation prints constants so they parse back
+    assert!(ExpressionTree::new(&t.infix(Some(&OperatorNotation::mathematical()))).unwrap().log_eq(&t));
+    assert!(ExpressionTree::new(&t.infix(Some(&OperatorNotation::bits_ascii()))).unwrap().log_eq(&t));
+}
+
+#[test_case("A₁&P₂", "A1&P2" ; "subscript sentences")]
+#[test_case("(∀x₁Rx₁a₂₃)vB₁₀", "∀(x1)(R(x1, a23))∨B10" ; "subscript variables")]
+fn new_subscripts(expression: &str, expected: &str){
+    let t = ExpressionTree::new(expression).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+    assert!(t.log_eq(&ExpressionTree::new(expected).unwrap()));
+}
+
+#[test]
+fn print_subscripts(){
+    let mut notation = OperatorNotation::default();
+    notation.set_subscripts(true);
+    let t = ExpressionTree::new("(@x1Px1y20)->A3").unwrap();
+
+    assert_eq!(t.infix(Some(&notation)), "∀(x₁)(P(x₁, y₂₀))➞A₃");
+    assert_eq!(ExpressionTree::new(&t.infix_minimal(Some(&notation), &ParserConfig::default())).unwrap().node(), t.node());
+    assert_eq!(Predicate::new("P₁", 0).unwrap().name(), "P1");
+    assert_eq!(ExpressionVar::new("x₁").unwrap(), ExpressionVar::new("x1").unwrap());
+}
+
+#[test_case("AB", "A&B" ; "two sentences")]
+#[test_case("ABC", "(A&B)&C" ; "chained")]
+#[test_case("AB+~AC", "(A&B)∨(¬A&C)" ; "sum of products")]
+#[test_case("(A+B)(C+D)", "(A∨B)&(C∨D)" ; "product of sums")]
+#[test_case("A(B+C)", "A&(B∨C)" ; "sentence then group")]
+#[test_case("~(AB)C", "¬(A&B)&C" ; "denied group")]
+#[test_case("A1B2Rab", "(A1&B2)&R(a, b)" ; "digits and arguments")]
+#[test_case("TRUE(A)", "TRUE&A" ; "constants")]
+fn new_implicit_and(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_implicit_and(true);
+    let t = ExpressionTree::new_with_config(expression, Some(&OperatorNotation::bits_ascii()), &config).unwrap();
+
+    assert_eq!(t.infix(None), expected);
+}
+
+#[test]
+fn new_implicit_and_long_names(){
+    let mut config = ParserConfig::default();
+    config.set_implicit_and(true).set_long_names(true);
+    let t = ExpressionTree::new_with_config("Rain Cold v Loves(x, y) (Snow v Hail)", None, &config).unwrap();
+
+    assert_eq!(t.infix(None), "(Rain&Cold)∨(Loves(x, y)&(Snow∨Hail))");
+    assert_eq!(ExpressionTree::new("AB").unwrap_err(), ClawgicError::InvalidPredicateName("AB".to_string()));
+}
+
+#[test_case("A", "A" ; "no connectives")]
+#[test_case("(A&B)vC->D", "(A&B)∨C➞D" ; "only needed parentheses")]
+#[test_case("A->(B->C)", "A➞(B➞C)" ; "same precedence")]
+#[test_case("~(A&B)v~C", "¬(A&B)∨¬C" ; "denied operator")]
+#[test_case("(@x(PxvQx))&A", "(∀(x)(P(x)∨Q(x)))&A" ; "quantifier operand")]
+#[test_case("~#x~Px", "¬∃(x)¬P(x)" ; "denied quantifier")]
+fn infix_minimal(expression: &str, expected: &str){
+    let t = ExpressionTree::new(expression).unwrap();
+    let config = ParserConfig::default();
+
+    assert_eq!(t.infix_minimal(None, &config), expected);
+    assert_eq!(ExpressionTree::new(&t.infix_minimal(None, &config)).unwrap().node(), t.node());
+}
+
+#[test_case("A&BvC->D", "A&B∨C➞D" ; "and over or")]
+#[test_case("(AvB)&C", "(A∨B)&C" ; "or under and")]
+fn infix_minimal_precedence(expression: &str, expected: &str){
+    let mut config = ParserConfig::default();
+    config.set_precedence(Operator::AND, 4);
+    let t = ExpressionTree::new_with_config(expression, None, &config).unwrap();
+
+    assert_eq!(t.infix_minimal(None, &config), expected);
+    assert_eq!(ExpressionTree::new_with_config(&t.infix_minimal(None, &config), None, &config).unwrap().node(), t.node());
+}
+
+#[test]
+#[allow(clippy::mutable_key_type)] //the cached value doesn't affect the hash
+fn eq_and_hash(){
+    let mut a = ExpressionTree::new("A&(B->C)").unwrap();
+    let b = ExpressionTree::new("A & (B -> C)").unwrap();
+    a.set_tval(&sen0("A"), true);
+    let _ = a.evaluate();
+
+    assert_eq!(a, b);
+    assert_ne!(a, ExpressionTree::new("(B->C)&A").unwrap());
+    assert_ne!(a, ExpressionTree::new("A&(~B|C)").unwrap());
+    let set: HashSet<ExpressionTree> = [a, b, ExpressionTree::new("A").unwrap()].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn ord(){
+    let mut trees: Vec<ExpressionTree> = ["TRUE", "B", "A1", "~A", "A", "(@xPx)", "AvB", "A&B", "~(A&B)", "FALSE", "Rab"]
+        .into_iter()
+        .map(|e| ExpressionTree::new(e).unwrap())
+        .collect();
+    trees.sort();
+    let sorted: Vec<String> = trees.iter().map(|t| t.infix(None)).collect();
+
+    assert_eq!(sorted, vec!["A&B", "A∨B", "¬(A&B)", "∀(x)(P(x))", "A", "A1", "B", "R(a, b)", "¬A", "FALSE", "TRUE"]);
+    assert_eq!(ExpressionTree::new("A").unwrap().cmp(&ExpressionTree::new("A").unwrap()), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn send_sync(){
+    fn assert_send_sync<T: Send + Sync>(){}
+    assert_send_sync::<ExpressionTree>();
+
+    let mut tree = ExpressionTree::new("(A&B)->C").unwrap();
+    tree.set_tvals(&Assignment::try_from([("A", true), ("B", true), ("C", false)]).unwrap());
+    let tree = Arc::new(tree);
+    let handles: Vec<_> = (0..4).map(|_| {
+        let tree = tree.clone();
+        std::thread::spawn(move || tree.evaluate())
+    }).collect();
+    for handle in handles{
+        assert_eq!(handle.join().unwrap(), Ok(false));
+    }
+}
+
+#[test]
+fn compact(){
+    let mut tree = ExpressionTree::new("((A&B)v(C->D))&~((A&B)v(C->D))").unwrap();
+    let before = tree.infix(None);
+    tree.compact();
+    assert_eq!(tree.infix(None), before);
+    let Node::Operator { left, right, .. } = tree.node() else {panic!("not an operator")};
+    let (Node::Operator { left: a, .. }, Node::Operator { left: b, .. }) = (&**left, &**right) else {panic!("not operators")};
+    assert!(Arc::ptr_eq(a, b));
+}
+
+#[test_case("((A&B)v(C&D))->(((A&B)v(C&D))&~((A&B)v(C&D)))", "D0->(D0&~D0)", vec!["(A&B)v(C&D)"] ; "repeated")]
+#[test_case("((A&B)&C)v(((A&B)&C)v((A&B)&C))", "D0v(D0vD0)", vec!["(A&B)&C"] ; "three copies")]
+#[test_case("(A&B)v(A&B)", "(A&B)v(A&B)", vec![] ; "not worth it")]
+#[test_case("((A&B)v(C&D))&(((A&B)v(C&D))&(((A&B)v(C&D))&(D0&D1)))", "D2&(D2&(D2&(D0&D1)))", vec!["(A&B)v(C&D)"] ; "names in use")]
+#[test_case("@(x)((Px&Qx)v((Px&Qx)&(Px&Qx)))", "@(x)((Px&Qx)v((Px&Qx)&(Px&Qx)))", vec![] ; "bound variables")]
+fn compact_definitions(expr: &str, expected: &str, definitions: Vec<&str>){
+    let (compacted, defs) = ExpressionTree::new(expr).unwrap().compact_definitions();
+    assert_eq!(compacted, ExpressionTree::new(expected).unwrap());
+    let defs: Vec<ExpressionTree> = defs.into_iter().map(|(_, d)| d).collect();
+    let expected: Vec<ExpressionTree> = definitions.into_iter().map(|d| ExpressionTree::new(d).unwrap()).collect();
+    assert_eq!(defs, expected);
+}
+
+#[test]
+fn compact_definitions_nested(){
+    let tree = ExpressionTree::new("(((AvB)&(CvD))->((AvB)&(CvD)))&((((AvB)->(CvD))v((AvB)->(CvD)))&((AvB)&(CvD)))").unwrap();
+    let (compacted, defs) = tree.compact_definitions();
+    assert!(defs.len() >= 2);
+    //every definition only uses sentences defined before it
+    for (i, (_, def)) in defs.iter().enumerate(){
+        for (later, _) in defs[i..].iter(){
+            assert!(!def.infix(None).contains(&later.to_string()));
+        }
+    }
+    //expanding the definitions (last first) gives back the original
+    let mut expanded = compacted;
+    for (sen, def) in defs.iter().rev(){
+        expanded.replace_sentence(sen, def);
+    }
+    assert_eq!(expanded, tree);
+}
+
+#[test_case("((A&B)v(C&D))->(((A&B)v(C&D))&~((A&B)v(C&D)))" ; "repeated")]
+#[test_case("(((AvB)&(CvD))->((AvB)&(CvD)))&((((AvB)->(CvD))v((AvB)->(CvD)))&((AvB)&(CvD)))" ; "nested")]
+fn definitional_form(expr: &str){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let sentences: Vec<Sentence> = ["A", "B", "C", "D"].into_iter().map(sen0).collect();
+    assert_eq!(tree.definitional_form().satisfy_all_projected(&sentences).len(), tree.satisfy_all_projected(&sentences).len());
+    assert_eq!(tree.definitional_form().satisfy_count()[0], tree.satisfy_count()[0]);
+}
+
+#[test_case("A->B", vec![0, 1, 3], vec![2] ; "conditional")]
+#[test_case("(A&~B)v(B&C)", vec![3, 4, 5, 7], vec![0, 1, 2, 6] ; "three sentences")]
+#[test_case("C&(Av~A)", vec![1, 3], vec![0, 2] ; "order is alphabetical")]
+#[test_case("Av~A", vec![0, 1], vec![] ; "tautology")]
+#[test_case("TRUE", vec![0], vec![] ; "no sentences")]
+fn minterms(expr: &str, minterms: Vec<u64>, maxterms: Vec<u64>){
+    let tree = ExpressionTree::new(expr).unwrap();
+    assert_eq!(tree.minterms().unwrap(), minterms);
+    assert_eq!(tree.maxterms().unwrap(), maxterms);
+    assert!(ExpressionTree::from_minterms(&tree.term_vars(), &minterms).unwrap().log_eq(&tree));
+    assert!(ExpressionTree::from_maxterms(&tree.term_vars(), &maxterms).unwrap().log_eq(&tree));
+}
+
+#[test]
+fn minterms_many_sentences(){
+    //more than 6 sentences, so more than one block of the truth table
+    let tree = ExpressionTree::new("((A&B)&(C&D))&((E&F)&(G&~H))").unwrap();
+    assert_eq!(tree.minterms().unwrap(), vec![0b1111_1110]);
+    assert_eq!(tree.maxterms().unwrap().len(), 255);
+}
+
+#[test]
+fn minterms_too_many_sentences(){
+    let mut tree = ExpressionTree::new("A0").unwrap();
+    for i in 1..65{
+        tree &= ExpressionTree::new(&format!("A{i}")).unwrap();
+    }
+    assert_eq!(tree.minterms(), Err(ClawgicError::TooManySentences(65)));
+    assert_eq!(tree.maxterms(), Err(ClawgicError::TooManySentences(65)));
+}
+
+#[test_case(&["A", "B"], &[3, 0], "(~A&~B)v(A&B)" ; "minterms")]
+#[test_case(&["A", "B"], &[], "FALSE" ; "no minterms")]
+#[test_case(&[], &[0], "TRUE" ; "no sentences")]
+fn from_minterms(sentences: &[&str], minterms: &[u64], expected: &str){
+    let sentences: Vec<Sentence> = sentences.iter().map(|s| sen0(s)).collect();
+    assert_eq!(ExpressionTree::from_minterms(&sentences, minterms).unwrap(), ExpressionTree::new(expected).unwrap());
+}
+
+#[test]
+fn from_maxterms(){
+    let sentences = vec![sen0("A"), sen0("B")];
+    assert_eq!(ExpressionTree::from_maxterms(&sentences, &[1, 2, 1]).unwrap(), ExpressionTree::new("(Av~B)&(~AvB)").unwrap());
+    assert_eq!(ExpressionTree::from_maxterms(&sentences, &[4]).unwrap_err(), ClawgicError::TermOutOfRange(4));
+}
+
+#[test_case("A", true, true, true ; "sentence")]
+#[test_case("~A", true, true, true ; "denied sentence")]
+#[test_case("~~A", false, false, false ; "double tilde")]
+#[test_case("TRUE", true, true, true ; "constant")]
+#[test_case("(Av~B)&((CvD)&~E)", true, false, true ; "cnf")]
+#[test_case("(A&~B)v((C&D)v~E)", false, true, true ; "dnf")]
+#[test_case("(A&B)&C", true, true, true ; "conjunction of literals")]
+#[test_case("(AvB)v~C", true, true, true ; "disjunction of literals")]
+#[test_case("(A&(BvC))vD", false, false, true ; "nested")]
+#[test_case("~(AvB)", false, false, false ; "denied operator")]
+#[test_case("A->B", false, false, false ; "conditional")]
+#[test_case("A<->B", false, false, false ; "biconditional")]
+#[test_case("@(x)(Px&~Qx)", false, false, true ; "quantifier")]
+#[test_case("~@(x)Px", false, false, false ; "denied quantifier")]
+fn normal_forms(expr: &str, cnf: bool, dnf: bool, nnf: bool){
+    let tree = ExpressionTree::new(expr).unwrap();
+    assert_eq!(tree.is_cnf(), cnf);
+    assert_eq!(tree.is_dnf(), dnf);
+    assert_eq!(tree.is_nnf(), nnf);
+}
+
+#[test_case("(A->B)&(B->C)", &[("A", true)], true ; "chain")]
+#[test_case("(A->B)&(B->C)", &[("A", true), ("C", false)], false ; "chain broken")]
+#[test_case("AvB", &[("Z", true)], true ; "sentence not in tree")]
+#[test_case("A&~A", &[], false ; "contradiction")]
+#[test_case("((A&B)&(C&D))&((E&F)&(G->H))", &[("H", false)], true ; "several blocks")]
+#[test_case("((A&B)&(C&D))&((E&F)&(G->H))", &[("G", true), ("H", false)], false ; "several blocks unsatisfiable")]
+#[test_case("@(x)(Px->Qx)&Pa", &[("Qa", false)], false ; "quantified")]
+fn satisfy_one_assuming(expr: &str, assumptions: &[(&str, bool)], satisfiable: bool){
+    let tree = ExpressionTree::new(expr).unwrap();
+    let assumptions = Assignment::try_from(assumptions).unwrap();
+    assert_eq!(tree.is_satisfiable_assuming(&assumptions), satisfiable);
+    let model = tree.satisfy_one_assuming(&assumptions);
+    assert_eq!(model.is_some(), satisfiable);
+    if let Some(model) = model{
+        for (sen, b) in assumptions.iter(){
+            assert_eq!(model.get(sen), Some(*b));
+        }
+        assert!(tree.restrict(&model).is_tautology());
+    }
+    //nothing about the tree changed
+    assert_eq!(tree, ExpressionTree::new(expr).unwrap());
+}
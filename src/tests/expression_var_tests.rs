@@ -51,4 +51,123 @@ fn vars_iter(){
     assert_eq!(iter.next().unwrap().name(), "a2");
     assert_eq!(iter.next().unwrap().name(), "a3");
     assert!(iter.next().is_none());
-}
\ No newline at end of file
+}
+
+#[test_case("x", true, true ; "single letter")]
+#[test_case("x12", true, true ; "letter and digits")]
+#[test_case("ground", false, true ; "multi-letter")]
+#[test_case("x_1", false, true ; "underscore")]
+#[test_case("X", false, false ; "uppercase")]
+#[test_case("_x", false, false ; "leading underscore")]
+fn var_names(name: &str, default_ok: bool, long_ok: bool){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+
+    assert_eq!(ExpressionVar::new(name).is_ok(), default_ok, "default");
+    assert_eq!(ExpressionVar::new_with_config(name, &config).is_ok(), long_ok, "long names");
+}
+
+#[test]
+fn predicate_name_err(){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+
+    assert_eq!(Predicate::new("Rain", 0).unwrap_err(), ClawgicError::InvalidPredicateName("Rain".to_string()));
+    assert!(Predicate::new_with_config("Rain", 0, &config).is_ok());
+}
+
+#[test_case(0..=2, 0..=2, (1, 2), "c12" ; "single digits")]
+#[test_case(1..=3, 8..=10, (2, 9), "c209" ; "padded column")]
+#[test_case(0..=11, 0..=1, (1, 1), "c011" ; "padded row")]
+fn grid_index(rows: std::ops::RangeInclusive<usize>, cols: std::ops::RangeInclusive<usize>, cell: (usize, usize), expected: &str){
+    let c = ExpressionVarGrid::new("c", rows, cols).unwrap();
+    assert_eq!(c[cell].name(), expected);
+}
+
+#[test]
+fn grid_index_ranges(){
+    let c = ExpressionVarGrid::new("c", 1..=3, 8..=10).unwrap();
+    assert_eq!(c[(2, 9)].name(), "c209");
+    assert!(c.get(0, 9).is_none());
+    assert!(c.get(2, 11).is_none());
+}
+
+#[test]
+#[should_panic]
+fn grid_index_panic(){
+    let c = ExpressionVarGrid::new("c", 1..=3, 1..=3).unwrap();
+    let _ = &c[(0, 1)];
+}
+
+#[test]
+fn grid_bad_bounds(){
+    assert_eq!(ExpressionVarGrid::new("c", std::ops::RangeInclusive::new(3, 1), 0..2).unwrap_err(), ClawgicError::InvalidVarBounds);
+    assert_eq!(ExpressionVarGrid::new("c", 0.., 0..2).unwrap_err(), ClawgicError::InvalidVarBounds);
+    assert_eq!(ExpressionVarGrid::new("1", 0..2, 0..2).unwrap_err(), ClawgicError::InvalidVariableName("100".to_string()));
+}
+
+#[test]
+fn grid_uppercase_prefix(){
+    let c = ExpressionVarGrid::new("C", 0..9, 0..9).unwrap();
+    assert_eq!(c.height(), 9);
+    assert_eq!(c[(0, 8)].name(), "c08");
+    assert_eq!(c[(8, 0)], ExpressionVar::new("c80").unwrap());
+}
+
+#[test]
+fn grid_rows_and_columns(){
+    let c = ExpressionVarGrid::new("c", 0..2, 0..3).unwrap();
+    let names = |it: Vec<&ExpressionVar>| it.iter().map(|v| v.name().to_string()).collect::<Vec<_>>();
+
+    assert_eq!(names(c.row(1).collect()), vec!["c10", "c11", "c12"]);
+    assert_eq!(names(c.column(2).collect()), vec!["c02", "c12"]);
+    assert_eq!(c.rows().count(), 2);
+    assert_eq!(c.columns().count(), 3);
+}
+
+#[test]
+fn grid_diagonals(){
+    let c = ExpressionVarGrid::new("c", 0..2, 0..3).unwrap();
+    let names = |d: Vec<&ExpressionVar>| d.iter().map(|v| v.name().to_string()).collect::<Vec<_>>();
+
+    let diagonals: Vec<_> = c.diagonals().map(names).collect();
+    assert_eq!(diagonals, vec![vec!["c10"], vec!["c00", "c11"], vec!["c01", "c12"], vec!["c02"]]);
+
+    let anti_diagonals: Vec<_> = c.anti_diagonals().map(names).collect();
+    assert_eq!(anti_diagonals, vec![vec!["c00"], vec!["c01", "c10"], vec!["c02", "c11"], vec!["c12"]]);
+}
+
+#[test]
+fn from_names(){
+    let vars = ExpressionVars::from_names(&["x", "y2", "z"]).unwrap();
+    assert_eq!(vars.len(), 3);
+    assert_eq!(vars[1].name(), "y2");
+    assert_eq!(vars.end(), 2);
+    assert_eq!(ExpressionVars::from_names(&["x", "Y"]).unwrap_err(), ClawgicError::InvalidVariableName("Y".to_string()));
+    let empty = ExpressionVars::from_names(&[]).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!((empty.start(), empty.end()), (0, 0));
+}
+
+#[test]
+fn from_names_with_config(){
+    let mut config = ParserConfig::default();
+    assert_eq!(ExpressionVars::from_names(&["alice"]).unwrap_err(), ClawgicError::InvalidVariableName("alice".to_string()));
+    assert_eq!(ExpressionVars::from_names_with_config(&["alice"], &config).unwrap_err(), ClawgicError::InvalidVariableName("alice".to_string()));
+    config.set_long_names(true);
+    let people = ExpressionVars::from_names_with_config(&["alice", "bob_2", "c"], &config).unwrap();
+    assert_eq!(people.iter().map(|v| v.name()).collect::<Vec<_>>(), vec!["alice", "bob_2", "c"]);
+    assert_eq!(ExpressionVars::from_names_with_config(&["Alice"], &config).unwrap_err(), ClawgicError::InvalidVariableName("Alice".to_string()));
+}
+
+#[test_case(&["a", "b", "c"], "(Pa&Pb)&Pc", "(PavPb)vPc", "(~(Pa&Pb)&~(Pa&Pc))&~(Pb&Pc)" ; "three")]
+#[test_case(&["a"], "Pa", "Pa", "TRUE" ; "one")]
+#[test_case(&[], "TRUE", "FALSE", "TRUE" ; "none")]
+fn combinators(names: &[&str], conjunction: &str, disjunction: &str, pairwise: &str){
+    let vars = ExpressionVars::from_names(names).unwrap();
+    let p = |x: &ExpressionVar| Predicate::new("P", 1).unwrap().inst(&vec![x.clone()]).unwrap();
+    let expected = |s: &str| ExpressionTree::new(s).unwrap();
+    assert!(vars.conjunction(|x| p(x).expr()).lit_eq(&expected(conjunction)));
+    assert!(vars.disjunction(|x| p(x).expr()).lit_eq(&expected(disjunction)));
+    assert!(vars.pairwise(|x, y| !(&p(x) & &p(y))).lit_eq(&expected(pairwise)));
+}
@@ -0,0 +1,157 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+fn kb(formulas: &[&str]) -> KnowledgeBase{
+    let mut kb = KnowledgeBase::new();
+    for f in formulas{
+        kb.tell(tree(f));
+    }
+    kb
+}
+
+#[test_case(&["A->B", "A"], "B", true ; "modus ponens")]
+#[test_case(&["A->B", "~B"], "~A", true ; "modus tollens")]
+#[test_case(&["AvB", "~A"], "B", true ; "disjunctive syllogism")]
+#[test_case(&["A->B"], "B", false ; "not entailed")]
+#[test_case(&["A"], "C", false ; "unrelated query")]
+#[test_case(&["A"], "Cv~C", true ; "tautological query")]
+#[test_case(&["A", "~A"], "C", true ; "explosion")]
+#[test_case(&[], "A", false ; "empty")]
+fn ask(formulas: &[&str], query: &str, expected: bool){
+    assert_eq!(kb(formulas).ask(&tree(query)), expected);
+}
+
+#[test_case(&["@xPx", "Pa"], "Pa", true ; "universal instantiation")]
+#[test_case(&["@x(Px->Qx)", "Pa"], "Qa", true ; "quantified modus ponens")]
+#[test_case(&["Pa"], "#xPx", true ; "existential generalization")]
+#[test_case(&["#xPx"], "Pa", false ; "existential doesn't pick an individual")]
+#[test_case(&["@x(Px->Qx)"], "Qb", false ; "query adds an individual")]
+fn ask_quantified(formulas: &[&str], query: &str, expected: bool){
+    assert_eq!(kb(formulas).ask(&tree(query)), expected);
+}
+
+#[test_case(&[], true ; "empty")]
+#[test_case(&["A", "A->B"], true ; "consistent")]
+#[test_case(&["A", "A->B", "~B"], false ; "inconsistent")]
+#[test_case(&["@xPx", "~Pa"], false ; "inconsistent with quantifier")]
+fn consistent(formulas: &[&str], expected: bool){
+    assert_eq!(kb(formulas).consistent(), expected);
+}
+
+#[test]
+fn models_are_incremental(){
+    let mut kb = KnowledgeBase::new();
+    kb.tell(tree("AvB"));
+    assert_eq!(kb.model_count(), 3);
+    kb.tell(tree("C"));
+    assert_eq!(kb.model_count(), 3);
+    kb.tell(tree("~A"));
+    assert_eq!(kb.model_count(), 1);
+
+    let models: Vec<Assignment> = kb.models().collect();
+    assert_eq!(models.len(), 1);
+    let mut t = tree("(~A&B)&C");
+    t.set_tvals(&models[0]);
+    assert!(t.evaluate().unwrap());
+    assert_eq!(kb.len(), 3);
+}
+
+#[test]
+fn many_independent_sentences(){
+    //2^100 models, which only ever get looked at one at a time
+    let mut kb = KnowledgeBase::new();
+    for i in 0..100{
+        kb.tell(tree(&format!("A{i}v~A{i}")));
+    }
+    kb.tell(tree("A0"));
+    assert_eq!(kb.model_count(), 1 << 99);
+    assert!(kb.ask(&tree("A0vA99")));
+    assert!(!kb.ask(&tree("A99")));
+    let mut models = kb.models();
+    for _ in 0..3{
+        let model = models.next().unwrap();
+        assert_eq!(model.len(), 100);
+        assert_eq!(model.get(&sen0("A0")), Some(true));
+    }
+}
+
+#[test]
+fn new_individual_rebuilds(){
+    let mut kb = kb(&["@x(Px->Qx)", "Pa"]);
+    kb.tell(tree("Pb"));
+    assert!(kb.ask(&tree("Qa&Qb")));
+    kb.tell(tree("~Qb"));
+    assert!(!kb.consistent());
+}
+
+fn sen0(name: &str) -> Sentence{
+    Sentence::new(&Predicate::new(name, 0).unwrap(), &vec![]).unwrap()
+}
+
+#[test_case(&["A", "A->B", "B->C"], "C", true ; "chain")]
+#[test_case(&["A", "B", "A&B->C"], "C", true ; "conjoined premises")]
+#[test_case(&["A", "(A&B)&C->D", "A->B"], "D", false ; "missing premise")]
+#[test_case(&["A->B", "B->A"], "A", false ; "cycle without facts")]
+#[test_case(&["A", "A->B", "B->A"], "B", true ; "cycle with fact")]
+fn forward_chain_entails(formulas: &[&str], sentence: &str, expected: bool){
+    let kb = kb(formulas);
+    let fc = kb.forward_chain().unwrap();
+    assert_eq!(fc.entails(&sen0(sentence)), expected);
+    //should always agree with ask
+    assert_eq!(kb.ask(&tree(sentence)), expected);
+}
+
+#[test]
+fn forward_chain_derivations(){
+    let fc = kb(&["A", "B->D", "A->B", "C->D", "A&B->C"]).forward_chain().unwrap();
+    assert_eq!(fc.entailed().cloned().collect::<Vec<_>>(), vec![sen0("A"), sen0("B"), sen0("D"), sen0("C")]);
+
+    let d = fc.derivation(&sen0("D")).unwrap();
+    assert_eq!(d.formula(), 1);
+    assert_eq!(d.premises(), &vec![sen0("B")]);
+    assert!(fc.derivation(&sen0("A")).unwrap().is_fact());
+
+    let chain: Vec<_> = fc.chain(&sen0("C")).iter().map(|d| d.sentence().clone()).collect();
+    assert_eq!(chain, vec![sen0("A"), sen0("B"), sen0("C")]);
+    assert!(fc.chain(&sen0("E")).is_empty());
+}
+
+#[test_case("~A" ; "denied fact")]
+#[test_case("AvB" ; "disjunction")]
+#[test_case("A->~B" ; "denied conclusion")]
+#[test_case("A->B&C" ; "conjoined conclusion")]
+#[test_case("AvB->C" ; "disjoined premises")]
+#[test_case("@xPx" ; "quantifier")]
+fn forward_chain_not_definite(formula: &str){
+    let kb = kb(&["A", formula]);
+    assert_eq!(kb.forward_chain().unwrap_err(), ClawgicError::NotDefiniteClause(tree(formula).infix(None)));
+}
+
+#[test_case(&["A", "B"], vec![] ; "consistent")]
+#[test_case(&["A&~A", "B"], vec![vec![0]] ; "contradiction")]
+#[test_case(&["A", "~A", "B", "B->~A", "C"], vec![vec![0, 1], vec![0, 2, 3]] ; "two reasons")]
+#[test_case(&["AvB", "~A", "~B", "A", "B->C", "~C"], vec![vec![0, 1, 2], vec![0, 1, 4, 5], vec![1, 3]] ; "overlapping")]
+#[test_case(&["@xPx", "~Pa", "Pb"], vec![vec![0, 1]] ; "quantifier")]
+fn minimal_unsatisfiable_subsets(formulas: &[&str], expected: Vec<Vec<usize>>){
+    let kb = kb(formulas);
+    let mut muses = kb.minimal_unsatisfiable_subsets(None);
+    muses.sort();
+    assert_eq!(muses, expected);
+    assert_eq!(kb.unsat_core().is_some(), !expected.is_empty());
+    if let Some(core) = kb.unsat_core(){
+        assert!(expected.contains(&core));
+    }
+}
+
+#[test]
+fn minimal_unsatisfiable_subsets_limit(){
+    let kb = kb(&["A", "~A", "B", "~B", "C", "~C"]);
+    assert_eq!(kb.minimal_unsatisfiable_subsets(None).len(), 3);
+    assert_eq!(kb.minimal_unsatisfiable_subsets(Some(2)).len(), 2);
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::expression_tree::lexer::Lexer;
+use crate::expression_tree::token::Token;
+use crate::prelude::*;
+
+/// Lexes the expression, writing each token out as a short string.
+fn lex(expr: &str, notation: &OperatorNotation, config: &ParserConfig) -> Result<Vec<String>, ClawgicError>{
+    Lexer::new(expr, notation, config).map(|token| Ok(match token?{
+        Token::Operator(neg, op) => format!("{}{op:?}", "~".repeat(neg.count() as usize)),
+        Token::Sentence(_, name, args) => format!("{name}[{}]", args.vars().collect::<Vec<_>>().join(",")),
+        Token::Constant(_, b) => b.to_string(),
+        Token::Quantifier(_, op, args) => format!("{op:?}[{}]", args.vars().collect::<Vec<_>>().join(",")),
+        Token::OpenParenthesis => "(".to_string(),
+        Token::ClosedParenthesis => ")".to_string(),
+        Token::Tilde(_) => "~".to_string(),
+        Token::ImplicitAnd => "&".to_string(),
+    })).collect()
+}
+
+#[test_case("A&B", &["A[]", "AND", "B[]"] ; "binary")]
+#[test_case("~(A1vB23)", &["~", "(", "A1[]", "OR", "B23[]", ")"] ; "digits")]
+#[test_case("A(x, y1)->Bxy2", &["A[x,y1]", "CON", "B[x,y2]"] ; "variables")]
+#[test_case("A<->B", &["A[]", "BICON", "B[]"] ; "longest operator")]
+#[test_case("A-->B", &["A[]", "CON", "B[]"] ; "long alternate")]
+#[test_case("@(x, y)#zPxyz", &["UNI[x,y]", "EXI[z]", "P[x,y,z]"] ; "quantifiers")]
+#[test_case("TRUE ⊕ 0", &["true", "~BICON", "false"] ; "unicode and constants")]
+#[test_case(" A  &  B ", &["A[]", "AND", "B[]"] ; "whitespace")]
+fn tokens(expr: &str, expected: &[&str]){
+    assert_eq!(lex(expr, &OperatorNotation::default(), &ParserConfig::default()).unwrap(), expected);
+}
+
+#[test_case("AB+~A(C)", &["A[]", "&", "B[]", "OR", "~", "A[]", "&", "(", "C[]", ")"] ; "letters and groups")]
+#[test_case("TRUE1", &["true", "&", "true"] ; "constants")]
+#[test_case("A1Bx", &["A1[]", "&", "B[x]"] ; "only the last letter gets variables")]
+fn implicit_and(expr: &str, expected: &[&str]){
+    let mut config = ParserConfig::default();
+    config.set_implicit_and(true);
+    assert_eq!(lex(expr, &OperatorNotation::bits_ascii(), &config).unwrap(), expected);
+}
+
+#[test]
+fn long_names(){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+    let tokens = lex("Loves(x_1, bob) v TRUE", &OperatorNotation::default(), &config).unwrap();
+    assert_eq!(tokens, ["Loves[x_1,bob]", "OR", "true"]);
+}
+
+#[test_case("A&<-", ClawgicError::UnknownSymbol("<-".to_string()) ; "partial operator")]
+#[test_case("A&?", ClawgicError::UnknownSymbol("?".to_string()) ; "unknown symbol")]
+#[test_case("A(x", ClawgicError::InvalidExpression ; "unclosed variables")]
+#[test_case("@Px", ClawgicError::NoVarQuantifier ; "no variables")]
+#[test_case("A&BC", ClawgicError::InvalidPredicateName("BC".to_string()) ; "multi-letter name")]
+fn errors(expr: &str, expected: ClawgicError){
+    assert_eq!(lex(expr, &OperatorNotation::default(), &ParserConfig::default()).unwrap_err(), expected);
+}
+
+#[test]
+fn borrows_names(){
+    let expr = "Rain(x) -> Wet(x)";
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+    for token in Lexer::new(expr, &OperatorNotation::default(), &config){
+        if let Token::Sentence(_, name, args) = token.unwrap(){
+            assert!(expr.as_bytes().as_ptr_range().contains(&name.as_ptr()));
+            assert!(args.vars().all(|v| expr.as_bytes().as_ptr_range().contains(&v.as_ptr())));
+        }
+    }
+}
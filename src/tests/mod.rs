@@ -1,7 +1,43 @@
+mod aig_tests;
+
+mod assignment_tests;
+
+mod bdd_tests;
+
+mod budget_tests;
+
+mod cnf_tests;
+
+mod ddnnf_tests;
+
+mod diagnostic_tests;
+
+mod equivalence_tests;
+
+mod expression_system_tests;
+
 mod expression_tree_tests;
 
 mod expression_var_tests;
 
+mod knowledge_base_tests;
+
+mod lexer_tests;
+
 mod node_tests;
 
-mod universe_tests;
\ No newline at end of file
+mod pattern_tests;
+
+mod proof_tests;
+
+mod schema_tests;
+
+mod substitution_tests;
+
+mod theory_tests;
+
+mod universe_tests;
+
+mod var_context_tests;
+
+mod zdd_tests;
\ No newline at end of file
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::expression_tree::universe::Universe;
 use crate::prelude::*;
@@ -45,32 +46,32 @@ fn operator_nodes(operator: Operator, ex1: bool, ex2: bool, ex3: bool, ex4: bool
     let op = Node::Operator {
         neg: Negation::new(0),
         op: operator,
-        left: Box::new(Node::Constant(Negation::new(0), true)),
-        right: Box::new(Node::Constant(Negation::new(0), true)) 
+        left: Arc::new(Node::Constant(Negation::new(0), true)),
+        right: Arc::new(Node::Constant(Negation::new(0), true)) 
     };
     assert_eq!(op.evaluate(&uni, &mut HashMap::new()).unwrap(), ex1, "true true failed");
 
     let op = Node::Operator {
         neg: Negation::new(0),
         op: operator,
-        left: Box::new(Node::Constant(Negation::new(0), true)),
-        right: Box::new(Node::Constant(Negation::new(0), false)) 
+        left: Arc::new(Node::Constant(Negation::new(0), true)),
+        right: Arc::new(Node::Constant(Negation::new(0), false)) 
     };
     assert_eq!(op.evaluate(&uni, &mut HashMap::new()).unwrap(), ex2, "true false failed");
 
     let op = Node::Operator {
         neg: Negation::new(0),
         op: operator,
-        left: Box::new(Node::Constant(Negation::new(0), false)),
-        right: Box::new(Node::Constant(Negation::new(0), true)) 
+        left: Arc::new(Node::Constant(Negation::new(0), false)),
+        right: Arc::new(Node::Constant(Negation::new(0), true)) 
     };
     assert_eq!(op.evaluate(&uni, &mut HashMap::new()).unwrap(), ex3, "false true failed");
 
     let op = Node::Operator {
         neg: Negation::new(0),
         op: operator,
-        left: Box::new(Node::Constant(Negation::new(0), false)),
-        right: Box::new(Node::Constant(Negation::new(0), false)) 
+        left: Arc::new(Node::Constant(Negation::new(0), false)),
+        right: Arc::new(Node::Constant(Negation::new(0), false)) 
     };
     assert_eq!(op.evaluate(&uni, &mut HashMap::new()).unwrap(), ex4, "false false failed");
 }
@@ -79,11 +80,11 @@ fn operator_nodes(operator: Operator, ex1: bool, ex2: bool, ex3: bool, ex4: bool
 #[test_case(Node::Sentence{neg: Negation::new(1), sen: sen0("A")}, "¬A".to_string() ; "Denied Variable")]
 #[test_case(Node::Constant(Negation::new(0), true), "TRUE".to_string() ; "True Constant")]
 #[test_case(Node::Constant(Negation::new(0), false), "FALSE".to_string() ; "False Constant")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "&".to_string() ; "And Operator")]
-#[test_case(Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "¬&".to_string() ; "Denied Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "∨".to_string() ; "Or Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "➞".to_string() ; "Con Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::BICON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "⟷".to_string() ; "Bicon Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "&".to_string() ; "And Operator")]
+#[test_case(Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "¬&".to_string() ; "Denied Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "∨".to_string() ; "Or Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "➞".to_string() ; "Con Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::BICON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "⟷".to_string() ; "Bicon Operator")]
 fn to_string(node: Node, expected: String){
     assert_eq!(node.to_string(), expected);
 }
@@ -92,22 +93,22 @@ fn to_string(node: Node, expected: String){
 #[test_case(Node::Sentence{neg: Negation::new(1), sen: sen0("A")}, "~A".to_string() ; "Denied Variable")]
 #[test_case(Node::Constant(Negation::new(0), true), "TRUE".to_string() ; "True Constant")]
 #[test_case(Node::Constant(Negation::new(0), false), "FALSE".to_string() ; "False Constant")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "&".to_string() ; "And Operator")]
-#[test_case(Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "~&".to_string() ; "Denied Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "v".to_string() ; "Or Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "->".to_string() ; "Con Operator")]
-#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::BICON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Constant(Negation::new(0), true))}, "<->".to_string() ; "Bicon Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "&".to_string() ; "And Operator")]
+#[test_case(Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "~&".to_string() ; "Denied Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "v".to_string() ; "Or Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "->".to_string() ; "Con Operator")]
+#[test_case(Node::Operator{neg: Negation::new(0), op: Operator::BICON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Constant(Negation::new(0), true))}, "<->".to_string() ; "Bicon Operator")]
 fn to_ascii(node: Node, expected: String){
     assert_eq!(node.to_ascii(), expected);
 }
 
 #[test_case(
-    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(1), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(1), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
     ; "AND")]
 #[test_case(
-    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(1), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(1), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
     ; "OR")]
 fn demorgans(mut node: Node, expected: Node){
     node.demorgans();
@@ -115,16 +116,16 @@ fn demorgans(mut node: Node, expected: Node){
 }
 
 #[test_case(
-    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})},
+    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})},
     Node::Operator { neg: Negation::new(0), op: Operator::AND, 
-        left: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
-        right: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")}), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})})} 
+        left: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
+        right: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")}), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})})} 
     ; "BICON")]
 #[test_case(
     Node::Operator { neg: Negation::new(0), op: Operator::AND, 
-        left: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
-        right: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")}), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})})}, 
-    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}
+        left: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
+        right: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")}), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})})}, 
+    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}
     ; "AND")]
 fn mat_eq(mut node: Node, expected: Node){
     node.mat_eq();
@@ -132,12 +133,12 @@ fn mat_eq(mut node: Node, expected: Node){
 }
 
 #[test_case(
-    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(1), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(1), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})}
     ; "CON")]
 #[test_case(
-    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(1), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(0), op: Operator::OR, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(1), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})}
     ; "OR")]
 fn implication(mut node: Node, expected: Node){
     node.implication();
@@ -145,12 +146,12 @@ fn implication(mut node: Node, expected: Node){
 }
 
 #[test_case(
-    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
     ; "AND")]
 #[test_case(
-    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
-    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Box::new(Node::Constant(Negation::new(0), true)), right: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
+    Node::Operator{neg: Negation::new(0), op: Operator::CON, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")})},
+    Node::Operator{neg: Negation::new(1), op: Operator::AND, left: Arc::new(Node::Constant(Negation::new(0), true)), right: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")})}
     ; "CON")]
 fn ncon(mut node: Node, expected: Node){
     node.ncon();
@@ -158,10 +159,10 @@ fn ncon(mut node: Node, expected: Node){
 }
 
 #[test_case(
-    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})},
+    Node::Operator { neg: Negation::new(0), op: Operator::BICON, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right:  Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})},
     Node::Operator { neg: Negation::new(0), op: Operator::OR, 
-        left: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Box::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
-        right: Box::new(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")}), right: Box::new(Node::Sentence{neg: Negation::new(1), sen: sen0("B")})})} 
+        left: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}), right: Arc::new(Node::Sentence{neg: Negation::new(0), sen: sen0("B")})}), 
+        right: Arc::new(Node::Operator{neg: Negation::new(0), op: Operator::AND, left: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("A")}), right: Arc::new(Node::Sentence{neg: Negation::new(1), sen: sen0("B")})})} 
     ; "BICON")]
 fn mat_eq_mono(mut node: Node, expected: Node){
     node.mat_eq_mono();
@@ -177,4 +178,12 @@ fn retaining_negations(val: bool){
     assert_eq!(node.double_negate().evaluate(&uni, &mut HashMap::new()).unwrap(), val);
     assert_eq!(node.double_deny().evaluate(&uni, &mut HashMap::new()).unwrap(), val);
     assert_eq!(node.reduce_negation().evaluate(&uni, &mut HashMap::new()).unwrap(), val);
-}
\ No newline at end of file
+}
+
+#[test_case(Node::Constant(Negation::new(0), true), Some(true) ; "true constant")]
+#[test_case(Node::Constant(Negation::new(1), true), Some(false) ; "denied true")]
+#[test_case(Node::Constant(Negation::new(2), false), Some(false) ; "double denied false")]
+#[test_case(Node::Sentence{neg: Negation::new(0), sen: sen0("A")}, None ; "sentence")]
+fn constant_value(node: Node, expected: Option<bool>){
+    assert_eq!(node.constant_value(), expected);
+}
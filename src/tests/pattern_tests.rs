@@ -0,0 +1,81 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+#[test_case("?p->?q", "A->B", vec![("p", "A"), ("q", "B")] ; "simple")]
+#[test_case("?p->?q", "(A&B)->~C", vec![("p", "A&B"), ("q", "~C")] ; "subexpressions")]
+#[test_case("?p&(?p->?q)", "(AvB)&((AvB)->C)", vec![("p", "AvB"), ("q", "C")] ; "repeated metavariable")]
+#[test_case("~?p", "~~A", vec![("p", "~A")] ; "negated metavariable")]
+#[test_case("?p&A", "~B&A", vec![("p", "~B")] ; "fixed sentence")]
+#[test_case("@(x)(P(x)&?p)", "@(x)(P(x)&A)", vec![("p", "A")] ; "quantifier")]
+#[test_case("@(x)?p", "@(x)#(y)Q(y)", vec![("p", "#(y)Q(y)")] ; "variable bound inside the binding")]
+#[test_case("@(x)?p&?p", "@(x)P(y)&P(y)", vec![("p", "P(y)")] ; "variable the quantifier doesn't bind")]
+fn match_ok(pattern: &str, expr: &str, expected: Vec<(&str, &str)>){
+    let bindings = Pattern::new(pattern).unwrap().match_against(tree(expr).node()).unwrap();
+    assert_eq!(bindings.len(), expected.len());
+    for (metavar, sub) in expected{
+        assert_eq!(ExpressionTree::from(bindings.get(metavar).unwrap().clone()), tree(sub), "?{metavar}");
+    }
+}
+
+#[test_case("?p->?q", "A&B" ; "wrong operator")]
+#[test_case("?p&(?p->?q)", "A&(B->C)" ; "inconsistent metavariable")]
+#[test_case("~?p", "A" ; "not enough tildes")]
+#[test_case("?p&A", "B&C" ; "fixed sentence")]
+#[test_case("@(x)?p", "#(x)P(x)" ; "wrong quantifier")]
+#[test_case("?p->?q", "~(A->B)" ; "denied operator")]
+#[test_case("@(x)?p", "@(x)P(x)" ; "captured variable")]
+#[test_case("?p&(@(x)?p)", "P(x)&(@(x)P(x))" ; "captured by a later occurrence")]
+fn match_none(pattern: &str, expr: &str){
+    assert!(Pattern::new(pattern).unwrap().match_against(tree(expr).node()).is_none());
+}
+
+#[test_case("?p->(?q->?p)", "A->((B&C)->A)" ; "axiom")]
+#[test_case("~?p&?q", "~A&(B&C)" ; "negated metavariable")]
+#[test_case("(?p v Z)vY", "(AvZ)vY" ; "placeholder letters in use")]
+fn instantiate(pattern: &str, expected: &str){
+    let bindings = Bindings::new()
+        .with("p", tree("A").node().clone())
+        .with("q", tree("B&C").node().clone());
+    assert_eq!(Pattern::new(pattern).unwrap().instantiate(&bindings).unwrap().infix(None), tree(expected).infix(None));
+}
+
+#[test]
+fn instantiate_unbound(){
+    let bindings = Bindings::new().with("p", tree("A").node().clone());
+    let result = Pattern::new("?p->?q").unwrap().instantiate(&bindings);
+    assert_eq!(result.unwrap_err(), ClawgicError::UnboundMetavariable("q".to_string()));
+}
+
+#[test]
+fn instantiate_captured(){
+    let bindings = Bindings::new().with("p", tree("P(x)").node().clone());
+    assert_eq!(Pattern::new("@(x)?p").unwrap().instantiate(&bindings).unwrap_err(), ClawgicError::CapturedVariable("p".to_string()));
+    assert_eq!(Pattern::new("@(y)?p").unwrap().instantiate(&bindings).unwrap(), tree("@(y)P(x)"));
+}
+
+#[test]
+fn round_trip(){
+    let pattern = Pattern::new("(?p->?q)&(?q->?r)").unwrap();
+    let expr = tree("(A->~B)&(~B->(CvD))");
+    let bindings = pattern.match_against(expr.node()).unwrap();
+    assert_eq!(pattern.instantiate(&bindings).unwrap(), expr);
+}
+
+#[test_case("?p->(?q1->?p)", "?p➞(?q1➞?p)" ; "metavariables")]
+#[test_case("?p&Z", "?p&Z" ; "placeholder letter in use")]
+fn infix(pattern: &str, expected: &str){
+    let pattern = Pattern::new(pattern).unwrap();
+    assert_eq!(pattern.infix(None), expected);
+    assert_eq!(Pattern::new(&pattern.infix(None)).unwrap().infix(None), expected);
+}
+
+#[test]
+fn metavars(){
+    assert_eq!(Pattern::new("?q&(?p->?q)").unwrap().metavars(), vec!["p", "q"]);
+}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+fn axiom(name: &str) -> Justification{
+    Justification::Axiom(name.to_string())
+}
+
+/// Proof of A->A from K and S.
+fn identity() -> Proof{
+    let mut proof = Proof::new();
+    proof.push(tree("(A->((A->A)->A))->((A->(A->A))->(A->A))"), axiom("S"))
+        .push(tree("A->((A->A)->A)"), axiom("K"))
+        .push(tree("(A->(A->A))->(A->A)"), Justification::ModusPonens(1, 0))
+        .push(tree("A->(A->A)"), axiom("K"))
+        .push(tree("A->A"), Justification::ModusPonens(3, 2));
+    proof
+}
+
+#[test]
+fn valid(){
+    let proof = identity();
+    assert_eq!(proof.check(), Ok(()));
+    assert_eq!(proof.len(), 5);
+    assert_eq!(proof.conclusion(), Some(&tree("A->A")));
+}
+
+#[test]
+fn premises(){
+    let mut proof = Proof::new();
+    proof.push(tree("A"), Justification::Premise)
+        .push(tree("A->B"), Justification::Premise)
+        .push(tree("B"), Justification::ModusPonens(0, 1))
+        .push(tree("B->(C->B)"), axiom("K"))
+        .push(tree("C->B"), Justification::ModusPonens(2, 3));
+    assert_eq!(proof.check(), Ok(()));
+    assert_eq!(proof.premises(), vec![&tree("A"), &tree("A->B")]);
+}
+
+#[test_case(1, tree("A->(A->B)"), axiom("K") ; "not an instance")]
+#[test_case(1, tree("A->((A->A)->A)"), axiom("Q") ; "unknown schema")]
+#[test_case(1, tree("A->((A->A)->A)"), axiom("S") ; "wrong schema")]
+#[test_case(2, tree("(A->(A->A))->(A->A)"), Justification::ModusPonens(0, 1) ; "modus ponens backwards")]
+#[test_case(2, tree("(A->(A->A))->(A->A)"), Justification::ModusPonens(1, 2) ; "modus ponens on itself")]
+#[test_case(4, tree("A->B"), Justification::ModusPonens(3, 2) ; "wrong conclusion")]
+fn invalid(line: usize, formula: ExpressionTree, justification: Justification){
+    let mut proof = Proof::new();
+    for (i, (f, j)) in identity().lines().iter().enumerate(){
+        match i == line{
+            true => proof.push(formula.clone(), justification.clone()),
+            false => proof.push(f.clone(), j.clone()),
+        };
+    }
+    assert_eq!(proof.check(), Err(ClawgicError::InvalidProofStep(line)));
+}
+
+#[test]
+fn first_bad_step(){
+    let mut proof = Proof::new();
+    proof.push(tree("B"), Justification::ModusPonens(0, 0))
+        .push(tree("C"), axiom("K"));
+    assert_eq!(proof.check(), Err(ClawgicError::InvalidProofStep(0)));
+}
+
+#[test]
+fn custom_schemas(){
+    let mut proof = Proof::with_schemas(vec![Schema::double_negation()]);
+    proof.push(tree("~~A"), Justification::Premise)
+        .push(tree("~~A->A"), axiom("DN"))
+        .push(tree("A"), Justification::ModusPonens(0, 1))
+        .push(tree("A->(B->A)"), axiom("K"));
+    assert_eq!(proof.check(), Err(ClawgicError::InvalidProofStep(3)));
+}
+
+#[test]
+fn to_bussproofs(){
+    let mut proof = Proof::new();
+    proof.push(tree("A1"), Justification::Premise)
+        .push(tree("A1->(B->A1)"), axiom("K"))
+        .push(tree("B->A1"), Justification::ModusPonens(0, 1));
+    let expected = "\\begin{prooftree}\n\
+        \\AxiomC{$A_{1}$}\n\
+        \\AxiomC{}\n\
+        \\RightLabel{\\scriptsize K}\n\
+        \\UnaryInfC{$A_{1} \\rightarrow (B \\rightarrow A_{1})$}\n\
+        \\RightLabel{\\scriptsize MP}\n\
+        \\BinaryInfC{$B \\rightarrow A_{1}$}\n\
+        \\end{prooftree}\n";
+    assert_eq!(proof.to_bussproofs().unwrap(), expected);
+}
+
+#[test]
+fn export_invalid(){
+    let mut proof = Proof::new();
+    proof.push(tree("A"), axiom("K"));
+    assert_eq!(proof.to_latex(), Err(ClawgicError::InvalidProofStep(0)));
+    assert_eq!(proof.to_bussproofs(), Err(ClawgicError::InvalidProofStep(0)));
+    assert_eq!(Proof::new().to_bussproofs(), Ok(String::new()));
+}
+
+#[test]
+fn replacement(){
+    let mut proof = Proof::new();
+    proof.push(tree("~(A&B)"), Justification::Premise)
+        .push(tree("~Av~B"), Justification::Replacement(0, "DeMorgan".to_string()))
+        .push(tree("A->~B"), Justification::Replacement(1, "Implication".to_string()))
+        .push(tree("~B->~B"), Justification::Replacement(2, "Implication".to_string()));
+    assert_eq!(proof.check(), Err(ClawgicError::InvalidProofStep(3)));
+
+    proof.equivalences_mut().register(Equivalence::new("Lemma", "?p->~?q", "?q->~?p").unwrap());
+    let mut fixed = Proof::new();
+    for (i, (f, j)) in proof.lines().iter().enumerate(){
+        match i == 3{
+            true => fixed.push(tree("B->~A"), Justification::Replacement(2, "Lemma".to_string())),
+            false => fixed.push(f.clone(), j.clone()),
+        };
+    }
+    *fixed.equivalences_mut() = proof.equivalences().clone();
+    assert_eq!(fixed.check(), Ok(()));
+    assert!(fixed.to_latex().unwrap().contains("Lemma 3"));
+}
+
+#[test]
+fn replacement_under_quantifier(){
+    //only sound when ?p doesn't use x
+    let vacuous = Equivalence::new("Vacuous", "@x?p", "?p").unwrap();
+    assert_ne!(vacuous.apply(&tree("@xPx")), Some(tree("Px")));
+    assert_eq!(vacuous.apply(&tree("@xPa")), Some(tree("Pa")));
+    assert_eq!(vacuous.apply(&tree("Px")), None);
+
+    let mut proof = Proof::new();
+    proof.equivalences_mut().register(vacuous);
+    proof.push(tree("Px"), Justification::Premise)
+        .push(tree("@xPx"), Justification::Replacement(0, "Vacuous".to_string()));
+    assert_eq!(proof.check(), Err(ClawgicError::InvalidProofStep(1)));
+}
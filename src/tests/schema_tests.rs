@@ -0,0 +1,46 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+#[test_case(Schema::k(), &["A", "B"], "A->(B->A)" ; "k")]
+#[test_case(Schema::s(), &["A", "B", "C"], "(A->(B->C))->((A->B)->(A->C))" ; "s")]
+#[test_case(Schema::contraposition(), &["A&B", "C"], "(~(A&B)->~C)->(C->(A&B))" ; "contraposition")]
+#[test_case(Schema::double_negation(), &["~A"], "~~~A->~A" ; "double negation")]
+fn instantiate(schema: Schema, expressions: &[&str], expected: &str){
+    let expressions: Vec<ExpressionTree> = expressions.iter().map(|e| tree(e)).collect();
+    let instance = schema.instantiate(&expressions).unwrap();
+    assert_eq!(instance, tree(expected));
+    assert!(schema.matches(&instance).is_some());
+}
+
+#[test_case(&["A"] ; "too few")]
+#[test_case(&["A", "B", "C"] ; "too many")]
+fn instantiate_wrong_count(expressions: &[&str]){
+    let expressions: Vec<ExpressionTree> = expressions.iter().map(|e| tree(e)).collect();
+    assert_eq!(Schema::k().instantiate(&expressions).unwrap_err(), ClawgicError::WrongArgumentCount(2, expressions.len()));
+}
+
+#[test_case("A->(B->B)" ; "wrong consequent")]
+#[test_case("A->B" ; "too shallow")]
+fn not_an_instance(expr: &str){
+    assert!(Schema::k().matches(&tree(expr)).is_none());
+}
+
+#[test]
+fn custom(){
+    let schema = Schema::new("Explosion", "?p->(~?p->?q)").unwrap();
+    assert_eq!(schema.name(), "Explosion");
+    assert_eq!(schema.arity(), 2);
+    assert_eq!(schema.instantiate(&[tree("A"), tree("B")]).unwrap(), tree("A->(~A->B)"));
+}
+
+#[test]
+fn standard(){
+    let names: Vec<String> = Schema::standard().iter().map(|s| s.name().to_string()).collect();
+    assert_eq!(names, vec!["K", "S", "Contraposition"]);
+}
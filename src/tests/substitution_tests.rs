@@ -0,0 +1,82 @@
+#![cfg(test)]
+use std::sync::Arc;
+
+use test_case::test_case;
+
+use crate::expression_tree::node::Node;
+use crate::prelude::*;
+
+fn sen0(name: &str) -> Sentence{
+    Sentence::new(&Predicate::new(name, 0).unwrap(), &vec![]).unwrap()
+}
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+#[test_case("A->B", "B->A" ; "swap")]
+#[test_case("~A&B", "~B&A" ; "swap with denial")]
+#[test_case("CvD", "CvD" ; "nothing to replace")]
+fn simultaneous(expr: &str, expected: &str){
+    let sub = Substitution::new()
+        .with(sen0("A"), tree("B"))
+        .with(sen0("B"), tree("A"));
+
+    assert!(sub.apply(&tree(expr)).lit_eq(&tree(expected)));
+}
+
+#[test]
+fn shared_across_trees(){
+    let sub = Substitution::new().with(sen0("A"), tree("CvD"));
+    let t1 = tree("A&B");
+    let t2 = tree("~A");
+
+    assert!(sub.apply(&t1).lit_eq(&tree("(CvD)&B")));
+    assert!(sub.apply(&t2).lit_eq(&tree("~(CvD)")));
+    assert!(t1.lit_eq(&tree("A&B")), "target was modified");
+}
+
+#[test]
+fn bound_sentences_untouched(){
+    let sub = Substitution::new().with(Sentence::new_from_strings(&Predicate::new("A", 1).unwrap(), &vec!["x".to_string()]).unwrap(), tree("B"));
+    let t = tree("(@xAx)&Ax");
+
+    assert!(sub.apply(&t).lit_eq(&tree("(@xAx)&B")));
+}
+
+#[test]
+fn compose(){
+    let first = Substitution::new().with(sen0("A"), tree("B&C"));
+    let second = Substitution::new()
+        .with(sen0("B"), tree("D"))
+        .with(sen0("E"), tree("F"));
+    let composed = first.compose(&second);
+    let t = tree("A->E");
+
+    assert!(composed.apply(&t).lit_eq(&second.apply(&first.apply(&t))));
+    assert!(composed.apply(&t).lit_eq(&tree("D&C->F")));
+}
+
+#[test]
+fn keeps_tvals(){
+    let mut t = tree("A&B");
+    t.set_tval(&sen0("B"), true);
+    let mut replacement = tree("C");
+    replacement.set_tval(&sen0("C"), true);
+    let sub = Substitution::new().with(sen0("A"), replacement);
+    let result = sub.apply(&t);
+
+    assert!(result.evaluate().unwrap());
+}
+
+#[test]
+fn shares_unchanged_subtrees(){
+    let original = tree("(A&B)->(C&D)");
+    let applied = Substitution::new().with(sen0("A"), tree("E")).apply(&original);
+    let (Node::Operator { left: old_left, right: old_right, .. }, Node::Operator { left: new_left, right: new_right, .. }) = (original.node(), applied.node())
+        else {panic!("expected operators")};
+
+    assert!(Arc::ptr_eq(old_right, new_right));
+    assert!(!Arc::ptr_eq(old_left, new_left));
+    assert_eq!(applied, tree("(E&B)->(C&D)"));
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+#[test_case("P1: A -> B\nP2: A", &[("P1", "A->B"), ("P2", "A")] ; "simple")]
+#[test_case("// header\n\n  b: B v C // trailing\na_1:~A\n", &[("b", "BvC"), ("a_1", "~A")] ; "comments and blank lines")]
+#[test_case("Q: @x(Px -> Qx)", &[("Q", "@x(Px->Qx)")] ; "quantifier")]
+#[test_case("", &[] ; "empty")]
+fn parse(input: &str, expected: &[(&str, &str)]){
+    let theory = parse_theory(input).unwrap();
+    assert_eq!(theory.len(), expected.len());
+    for ((label, formula), (expected_label, expected_formula)) in theory.iter().zip(expected){
+        assert_eq!(label, *expected_label);
+        assert!(formula.lit_eq(&ExpressionTree::new(expected_formula).unwrap()), "{}", formula.infix(None));
+    }
+}
+
+#[test]
+fn errors(){
+    let input = "P1: A\nno label here\nP1: B\n: C\nbad label: D\nP2: A &\nP3: B";
+    let errors = parse_theory(input).unwrap_err();
+    assert_eq!(errors, vec![
+        (2, ClawgicError::MissingLabel),
+        (3, ClawgicError::DuplicateLabel("P1".to_string())),
+        (4, ClawgicError::InvalidLabel("".to_string())),
+        (5, ClawgicError::InvalidLabel("bad label".to_string())),
+        (6, ClawgicError::TooManyOperators),
+    ]);
+}
+
+#[test]
+fn with_config(){
+    let mut config = ParserConfig::default();
+    config.set_long_names(true);
+    let theory = parse_theory_with_config("rule: Rain -> Wet", None, &config).unwrap();
+    assert!(theory["rule"].lit_eq(&ExpressionTree::new_with_config("Rain -> Wet", None, &config).unwrap()));
+}
+
+#[test]
+fn insert(){
+    let mut theory = Theory::new();
+    assert!(theory.insert("a", ExpressionTree::new("A").unwrap()).is_none());
+    theory.insert("b", ExpressionTree::new("B").unwrap());
+    let old = theory.insert("a", ExpressionTree::new("C").unwrap()).unwrap();
+    assert!(old.lit_eq(&ExpressionTree::new("A").unwrap()));
+    assert_eq!(theory.labels().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert!(theory.get("a").unwrap().lit_eq(&ExpressionTree::new("C").unwrap()));
+    assert!(theory.get("c").is_none());
+}
@@ -0,0 +1,144 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn sen(s: &str) -> Sentence{
+    Sentence::try_from(s).unwrap()
+}
+
+fn attached(exprs: &[&str], context: &VarContext) -> Vec<ExpressionTree>{
+    exprs.iter().map(|e| {
+        let mut tree = ExpressionTree::new(e).unwrap();
+        tree.attach(context);
+        tree
+    }).collect()
+}
+
+#[test_case(&["A&B", "AvB", "A->C"], &[("A", true), ("B", false), ("C", false)], &[false, true, false] ; "propositional")]
+#[test_case(&["~A", "A<->B"], &[("A", true), ("A", false), ("B", false)], &[true, true] ; "set twice")]
+#[test_case(&["@xPx", "#xPx"], &[("P(a)", true), ("P(b)", false)], &[false, true] ; "new individuals")]
+#[test_case(&["@xPx", "Pa"], &[("P(a)", true)], &[true, true] ; "quantified and free")]
+fn set_variable(exprs: &[&str], tvals: &[(&str, bool)], expected: &[bool]){
+    let context = VarContext::new();
+    let trees = attached(exprs, &context);
+    //evaluate once first so there's something cached to throw away
+    for tree in trees.iter(){
+        let _ = tree.evaluate();
+    }
+    for (s, b) in tvals{
+        context.set_variable(s, *b).unwrap();
+    }
+    let results: Vec<bool> = trees.iter().map(|t| t.evaluate().unwrap()).collect();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn set_through_tree(){
+    let context = VarContext::new();
+    let mut trees = attached(&["A", "~A"], &context);
+    trees[0].set_tval(&sen("A"), true);
+    assert!(trees[0].evaluate().unwrap());
+    assert!(!trees[1].evaluate().unwrap());
+    assert_eq!(context.get_tval(&sen("A")), Some(true));
+}
+
+#[test]
+fn keeps_context_values(){
+    let context = VarContext::new();
+    context.set_variable("A", true).unwrap();
+    let mut tree = ExpressionTree::new("A&B").unwrap();
+    tree.set_tval(&sen("B"), true);
+    tree.set_tval(&sen("A"), false);
+    tree.attach(&context);
+    assert!(tree.evaluate().unwrap());
+    assert_eq!(context.tvals(), Assignment::new().set("A", true).unwrap().set("B", true).unwrap());
+}
+
+#[test]
+fn clones_and_detach(){
+    let context = VarContext::new();
+    let mut tree = attached(&["AvB"], &context).pop().unwrap();
+    let clone = tree.clone();
+    assert_eq!(context.attached(), 2);
+    context.set_variable("A", false).unwrap();
+    context.set_variable("B", true).unwrap();
+    assert!(clone.evaluate().unwrap());
+
+    assert!(tree.detach().is_some());
+    assert!(tree.context().is_none());
+    context.set_variable("B", false).unwrap();
+    //the detached tree keeps the values it had
+    assert!(tree.evaluate().unwrap());
+    assert!(!clone.evaluate().unwrap());
+    drop(clone);
+    assert_eq!(context.attached(), 0);
+}
+
+#[test]
+fn changes_shape(){
+    let context = VarContext::new();
+    let mut tree = attached(&["A"], &context).pop().unwrap();
+    context.set_variable("A", true).unwrap();
+    assert!(tree.evaluate().unwrap());
+    tree.replace_sentence(&sen("A"), &ExpressionTree::new("B&C").unwrap());
+    context.set_variable("B", true).unwrap();
+    assert_eq!(tree.evaluate(), Err(ClawgicError::UninitializedSentence("C".to_string())));
+    context.set_variable("C", true).unwrap();
+    assert!(tree.evaluate().unwrap());
+}
+
+#[test]
+fn combined_with_unattached(){
+    let context = VarContext::new();
+    let b = attached(&["B"], &context).pop().unwrap();
+    context.set_variable("B", true).unwrap();
+    let mut a = ExpressionTree::new("A").unwrap();
+    a.set_tval(&sen("A"), true);
+
+    //whichever side is attached, the result is too
+    let joined = a.clone().and(b.clone());
+    assert!(joined.context().is_some_and(|c| c.same_as(&context)));
+    assert!(joined.evaluate().unwrap());
+    let joined = b.or(a);
+    assert!(joined.context().is_some_and(|c| c.same_as(&context)));
+    context.set_variable("B", false).unwrap();
+    assert!(joined.evaluate().unwrap());
+}
+
+#[test]
+fn combined_across_contexts(){
+    let (first, second) = (VarContext::new(), VarContext::new());
+    let a = attached(&["A"], &first).pop().unwrap();
+    let b = attached(&["B"], &second).pop().unwrap();
+    first.set_variable("A", true).unwrap();
+    second.set_variable("B", true).unwrap();
+
+    let joined = a.con(b);
+    assert!(joined.context().is_some_and(|c| c.same_as(&first)));
+    //the second tree's values came along
+    assert!(joined.evaluate().unwrap());
+    assert_eq!(first.get_tval(&sen("B")), Some(true));
+    second.set_variable("B", false).unwrap();
+    assert!(joined.evaluate().unwrap());
+    assert_eq!(second.attached(), 0);
+}
+
+#[test]
+fn observers(){
+    use std::sync::{Arc, Mutex};
+    let context = VarContext::new();
+    let changes = Arc::new(Mutex::new(0));
+    let count = changes.clone();
+    let mut tree = attached(&["A"], &context).pop().unwrap();
+    tree.on_variable_change(move |_, _| *count.lock().unwrap() += 1);
+    tree.set_tval(&sen("A"), true);
+    tree.set_tval(&sen("A"), true);
+    context.set_variable("A", false).unwrap();
+    assert_eq!(*changes.lock().unwrap(), 1);
+}
+
+#[test]
+fn invalid_name(){
+    assert_eq!(VarContext::new().set_variable("A&B", true), Err(ClawgicError::InvalidExpression));
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+use test_case::test_case;
+
+use crate::prelude::*;
+
+fn tree(expr: &str) -> ExpressionTree{
+    ExpressionTree::new(expr).unwrap()
+}
+
+fn sentences(names: &[&str]) -> Vec<Sentence>{
+    names.iter().map(|s| Sentence::try_from(*s).unwrap()).collect()
+}
+
+#[test_case("A" ; "single")]
+#[test_case("A&~A" ; "contradiction")]
+#[test_case("Av~A" ; "tautology")]
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("~((A&B)v(~C->D))" ; "denied")]
+#[test_case("(#(x)Px)&~Pa" ; "existential")]
+fn count(expr: &str){
+    let expr = tree(expr);
+    let zdd = expr.to_zdd();
+    assert_eq!(zdd.count(), expr.satisfy_count()[0]);
+    assert_eq!(zdd.is_empty(), !expr.is_satisfiable());
+}
+
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("(A<->~B)v(C&~A)" ; "mixed")]
+fn assignments(expr: &str){
+    let expr = tree(expr);
+    let mut expected = expr.satisfy_all();
+    let mut models = expr.to_zdd().assignments();
+    expected.sort_by_key(|a| a.to_string());
+    models.sort_by_key(|a| a.to_string());
+    assert_eq!(models, expected);
+}
+
+#[test]
+fn sets(){
+    let zdd = tree("(A&~B)v(~A&B)").to_zdd();
+    let mut sets = zdd.sets();
+    sets.sort();
+    assert_eq!(sets, vec![sentences(&["A"]), sentences(&["B"])]);
+    assert!(zdd.contains(&sentences(&["A"])));
+    assert!(!zdd.contains(&sentences(&[])));
+    assert!(!zdd.contains(&sentences(&["A", "B"])));
+    assert!(!zdd.contains(&sentences(&["Z"])));
+}
+
+#[test]
+fn contains_empty_set(){
+    let zdd = tree("~A&~B").to_zdd();
+    assert_eq!(zdd.sets(), vec![Vec::<Sentence>::new()]);
+    assert!(zdd.contains(&[]));
+    assert_eq!(zdd.size(), 0);
+}
+
+#[test]
+fn sparse_is_smaller(){
+    //at most one of six atoms
+    let names = ["A", "B", "C", "D", "E", "F"];
+    let mut expr = ExpressionTree::TRUE();
+    for (i, x) in names.iter().enumerate(){
+        for y in names[i + 1..].iter(){
+            expr &= tree(&format!("~({x}&{y})"));
+        }
+    }
+    let bdd = expr.to_bdd();
+    let zdd = bdd.to_zdd();
+    assert_eq!(zdd.count(), 7);
+    assert!(zdd.size() < bdd.size());
+}
+
+#[test_case("(A->B)&(B->C)" ; "chain")]
+#[test_case("((A&D)v(B&E))v(C&F)" ; "pairs")]
+#[test_case("A&~A" ; "contradiction")]
+#[test_case("Av~A" ; "tautology")]
+fn round_trip(expr: &str){
+    let expr = tree(expr);
+    let bdd = expr.to_bdd();
+    let back = bdd.to_zdd().to_bdd();
+    assert_eq!(back.size(), bdd.size());
+    assert_eq!(back.order(), bdd.order());
+    assert_eq!(back.model_count(), bdd.model_count());
+    for model in expr.satisfy_all(){
+        assert!(back.evaluate(&model));
+    }
+}
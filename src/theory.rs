@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::prelude::{ExpressionTree, OperatorNotation, ParserConfig};
+use crate::ClawgicError;
+
+/// Named formulas, kept in the order they were added.
+/// ```
+/// use clawgic::prelude::*;
+/// let theory = parse_theory("
+///     // the weather
+///     P1: R -> W
+///     P2: R   // it's raining
+/// ").unwrap();
+/// assert_eq!(theory.labels().collect::<Vec<_>>(), vec!["P1", "P2"]);
+/// assert!(theory["P2"].lit_eq(&ExpressionTree::new("R").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Theory{
+    /// Every label and its formula, in order.
+    formulas: Vec<(String, ExpressionTree)>,
+    /// Where each label is in `formulas`.
+    index: HashMap<String, usize>,
+}
+
+impl Theory{
+    /// Constructs an empty `Theory`.
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Adds the formula under the label. If the label was already there, its formula gets replaced
+    /// (keeping its place) and the old one is returned.
+    pub fn insert(&mut self, label: &str, formula: ExpressionTree) -> Option<ExpressionTree>{
+        match self.index.get(label){
+            Some(i) => Some(std::mem::replace(&mut self.formulas[*i].1, formula)),
+            None => {
+                self.index.insert(label.to_string(), self.formulas.len());
+                self.formulas.push((label.to_string(), formula));
+                None
+            },
+        }
+    }
+
+    /// Gets the formula with the label.
+    pub fn get(&self, label: &str) -> Option<&ExpressionTree>{
+        self.index.get(label).map(|i| &self.formulas[*i].1)
+    }
+
+    /// Whether there's a formula with the label.
+    pub fn contains(&self, label: &str) -> bool{
+        self.index.contains_key(label)
+    }
+
+    /// creates an iterator over every label (in order).
+    pub fn labels(&self) -> impl Iterator<Item = &str>{
+        self.formulas.iter().map(|(label, _)| label.as_str())
+    }
+
+    /// creates an iterator over every formula (in order).
+    pub fn formulas(&self) -> impl Iterator<Item = &ExpressionTree>{
+        self.formulas.iter().map(|(_, formula)| formula)
+    }
+
+    /// creates an iterator over every label and its formula (in order).
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ExpressionTree)>{
+        self.formulas.iter().map(|(label, formula)| (label.as_str(), formula))
+    }
+
+    /// Number of formulas.
+    pub fn len(&self) -> usize{
+        self.formulas.len()
+    }
+
+    /// Whether there are no formulas.
+    pub fn is_empty(&self) -> bool{
+        self.formulas.is_empty()
+    }
+}
+
+impl std::ops::Index<&str> for Theory{
+    type Output = ExpressionTree;
+
+    /// panics if there's no formula with the label.
+    fn index(&self, label: &str) -> &Self::Output {
+        match self.get(label){
+            Some(formula) => formula,
+            None => panic!("no formula labeled \"{label}\""),
+        }
+    }
+}
+
+/// Reads a theory: one labeled formula per line (i.e. `P1: A -> B`).
+///
+/// Everything after `//` is a comment, and blank lines are skipped. If any line is wrong, returns every
+/// error along with its line number (starting at 1).
+pub fn parse_theory(input: &str) -> Result<Theory, Vec<(usize, ClawgicError)>>{
+    parse_theory_with_config(input, None, &ParserConfig::default())
+}
+
+/// `parse_theory()`, but with an (optional) `OperatorNotation` detailing the accepted operators, and a `ParserConfig`.
+pub fn parse_theory_with_config(input: &str, notation: Option<&OperatorNotation>, config: &ParserConfig) -> Result<Theory, Vec<(usize, ClawgicError)>>{
+    let mut theory = Theory::new();
+    let mut errors = Vec::new();
+    for (i, line) in input.lines().enumerate(){
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty(){
+            continue;
+        }
+        match parse_line(line, notation, config, &theory){
+            Ok((label, formula)) => {theory.insert(label, formula);},
+            Err(e) => errors.push((i + 1, e)),
+        }
+    }
+
+    if errors.is_empty() {Ok(theory)} else {Err(errors)}
+}
+
+/// Reads a single (non-empty, comment free) line of a theory.
+fn parse_line<'a>(line: &'a str, notation: Option<&OperatorNotation>, config: &ParserConfig, theory: &Theory) -> Result<(&'a str, ExpressionTree), ClawgicError>{
+    let (label, formula) = line.split_once(':').ok_or(ClawgicError::MissingLabel)?;
+    let label = label.trim();
+    if label.is_empty() || !label.chars().all(|c| c.is_alphanumeric() || c == '_'){
+        return Err(ClawgicError::InvalidLabel(label.to_string()));
+    }
+    if theory.contains(label){
+        return Err(ClawgicError::DuplicateLabel(label.to_string()));
+    }
+
+    Ok((label, ExpressionTree::new_with_config(formula, notation, config)?))
+}
@@ -2,8 +2,7 @@ use crate::prelude::ExpressionVar;
 
 /// Returns whether the given string is a valid var name
 pub fn is_valid_var_name(var: &str) -> bool{
-    let name = var.trim().to_string();
-    let mut chars = name.chars();
+    let mut chars = var.trim().chars();
     let first = chars.next();
     if first.is_none_or(|c| !c.is_lowercase()){
         return false;
@@ -20,8 +19,7 @@ pub fn is_valid_var_name(var: &str) -> bool{
 
 /// Returns whether the given string is a valid predicate name
 pub fn is_valid_predicate_name(name: &str) -> bool{
-    let name = name.trim().to_string();
-    let mut chars = name.chars();
+    let mut chars = name.trim().chars();
     let first = chars.next();
     if first.is_none_or(|c| !c.is_uppercase()){
         return false;
@@ -36,6 +34,84 @@ pub fn is_valid_predicate_name(name: &str) -> bool{
     true
 }
 
+/// Returns whether the given string is a valid long name.
+/// 
+/// Long names start with a char that passes `first` and are followed by any ascii letters, digits, or underscores.
+pub fn is_valid_long_name(name: &str, first: fn(char) -> bool) -> bool{
+    let name = name.trim();
+    let mut chars = name.chars();
+    if chars.next().is_none_or(|c| !first(c)){
+        return false;
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replaces subscript digits (i.e. "₁") with normal ones.
+pub fn normalize_subscripts(s: &str) -> String{
+    s.chars().map(|c| match c{
+        '₀'..='₉' => char::from_u32('0' as u32 + (c as u32 - '₀' as u32)).unwrap(),
+        _ => c,
+    }).collect()
+}
+
+/// Replaces digits with subscript digits.
+pub fn to_subscripts(s: &str) -> String{
+    s.chars().map(|c| match c{
+        '0'..='9' => char::from_u32('₀' as u32 + (c as u32 - '0' as u32)).unwrap(),
+        _ => c,
+    }).collect()
+}
+
+/// Writes every name in the string as LaTeX (i.e. "A12" becomes "A_{12}").
+///
+/// Digits at the end of a name become a subscript (dropping the `_` before them, if there is one), and
+/// anything longer than one letter is wrapped in `\mathit{}` with its underscores escaped, so "Rain_1" becomes
+/// "\mathit{Rain}_{1}" and "Wet_day" becomes "\mathit{Wet\_day}".
+pub fn to_latex_subscripts(s: &str) -> String{
+    let mut latex = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next(){
+        if !c.is_ascii_alphanumeric() && c != '_'{
+            latex.push(c);
+            continue;
+        }
+        let mut name = String::from(c);
+        while let Some(d) = chars.next_if(|d| d.is_ascii_alphanumeric() || *d == '_'){
+            name.push(d);
+        }
+
+        let stem = name.trim_end_matches(|d: char| d.is_ascii_digit());
+        let digits = &name[stem.len()..];
+        let stem = if digits.is_empty() {stem} else {stem.strip_suffix('_').unwrap_or(stem)};
+        if stem.chars().count() > 1{
+            latex.push_str(&format!("\\mathit{{{}}}", stem.replace('_', "\\_")));
+        }else{
+            latex.push_str(stem);
+        }
+        if !digits.is_empty(){
+            if stem.is_empty(){
+                latex.push_str(digits);
+            }else{
+                latex.push_str(&format!("_{{{digits}}}"));
+            }
+        }
+    }
+
+    latex
+}
+
+/// Multiplies a model count by `2^atoms`, saturating at `u128::MAX`.
+pub(crate) fn scale_count(count: u128, atoms: usize) -> u128{
+    if count == 0{
+        0
+    }else if atoms >= 128 || count.leading_zeros() < atoms as u32{
+        u128::MAX
+    }else{
+        count << atoms
+    }
+}
+
 pub fn print_variables_verbose(vars: &Vec<ExpressionVar>) -> String{
     if vars.is_empty(){
         "".to_string()